@@ -0,0 +1,52 @@
+use anyhow::{Context, Error};
+use serde::Deserialize;
+use std::path::Path;
+
+/// Name of the suppression file, looked up next to the metadata file
+pub const ACCEPT_FILE_NAME: &str = ".covenant-accept.toml";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct AcceptFile {
+    accept: Vec<Acceptance>,
+}
+
+/// Intentional deviation, matched by check id (`TPP-LP-005`) or field (`liquid_pooler_config.pool_address`)
+#[derive(Clone, Debug, Deserialize)]
+pub struct Acceptance {
+    pub check: Option<String>,
+    pub field: Option<String>,
+    pub justification: String,
+}
+
+impl Acceptance {
+    pub fn matches(&self, check_id: Option<&str>, key: &str, field: &str) -> bool {
+        let check_matches = match (&self.check, check_id) {
+            (Some(check), Some(check_id)) => check.eq_ignore_ascii_case(check_id),
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        let field_matches = match &self.field {
+            Some(accepted_field) => {
+                accepted_field == key || *accepted_field == format!("{}.{}", key, field)
+            }
+            None => true,
+        };
+        (self.check.is_some() || self.field.is_some()) && check_matches && field_matches
+    }
+}
+
+/// Load the accepted deviations of the covenant described by the metadata file
+pub fn load_acceptances(metadata_file: &str) -> Result<Vec<Acceptance>, Error> {
+    let accept_file = Path::new(metadata_file)
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join(ACCEPT_FILE_NAME);
+    if !accept_file.exists() {
+        return Ok(vec![]);
+    }
+    let content = std::fs::read_to_string(&accept_file)?;
+    let accept_file: AcceptFile = toml::from_str(&content)
+        .with_context(|| format!("failed loading {}", accept_file.display()))?;
+    Ok(accept_file.accept)
+}
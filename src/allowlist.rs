@@ -0,0 +1,112 @@
+use anyhow::{Context, Error};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Name of the allowlist file, looked up next to the metadata file
+pub const ALLOWLIST_FILE_NAME: &str = "allowlist.toml";
+
+/// Addresses a party expects to receive its funds on, keyed by party name in the allowlist
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AllowlistEntry {
+    pub receiver_address: String,
+    pub fallback_address: Option<String>,
+    /// Base64 compressed secp256k1 public key of the party, self-declared: only informative,
+    /// it must match the trusted key of the party
+    pub public_key: Option<String>,
+    /// Base64 signature of the SHA-256 of the `statement` by the party key
+    pub signature: Option<String>,
+}
+
+pub type Allowlist = BTreeMap<String, AllowlistEntry>;
+
+/// Outcome of the verification of an allowlist entry signature
+#[derive(Debug, PartialEq)]
+pub enum SignatureStatus {
+    /// Signed by the trusted key of the party
+    Verified,
+    /// Signed, but no trusted key of the party is configured
+    Unverified,
+    Invalid(String),
+    Unsigned,
+}
+
+impl AllowlistEntry {
+    /// Statement signed by the party, one `name=value` line per committed value
+    pub fn statement(&self, party_name: &str) -> String {
+        format!(
+            "party={}\nreceiver_address={}\nfallback_address={}\n",
+            party_name,
+            self.receiver_address,
+            self.fallback_address.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// Verify the signature of the statement against the trusted key of the party (operator
+    /// configuration): a key declared by the entry itself proves nothing
+    pub fn verify_signature(&self, party_name: &str, trusted_key: Option<&str>) -> SignatureStatus {
+        let Some(signature) = &self.signature else {
+            return SignatureStatus::Unsigned;
+        };
+        let Some(public_key) = trusted_key else {
+            return SignatureStatus::Unverified;
+        };
+        if self
+            .public_key
+            .as_deref()
+            .is_some_and(|key| key != public_key)
+        {
+            return SignatureStatus::Invalid(
+                "public_key differs from the trusted key of the party".to_owned(),
+            );
+        }
+        let decode = |value: &str, name: &str| {
+            STANDARD
+                .decode(value)
+                .map_err(|e| format!("invalid {}: {}", name, e))
+        };
+        let verify = || {
+            let public_key = decode(public_key, "public_key")?;
+            let signature = decode(signature, "signature")?;
+            let hash = Sha256::digest(self.statement(party_name).as_bytes());
+            // Same secp256k1 verification as the chains, without the test-only mock API
+            match cosmwasm_crypto::secp256k1_verify(&hash, &signature, &public_key) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err("signature does not match the addresses".to_owned()),
+                Err(e) => Err(format!("failed to verify the signature: {}", e)),
+            }
+        };
+        match verify() {
+            Ok(()) => SignatureStatus::Verified,
+            Err(e) => SignatureStatus::Invalid(e),
+        }
+    }
+}
+
+/// Load the allowlist file, else the allowlist next to the metadata file when present
+pub fn load_allowlist(
+    allowlist_file: Option<&str>,
+    metadata_file: &str,
+) -> Result<Option<Allowlist>, Error> {
+    let allowlist_file = match allowlist_file {
+        Some(allowlist_file) => Path::new(allowlist_file).to_path_buf(),
+        None => {
+            let allowlist_file = Path::new(metadata_file)
+                .parent()
+                .unwrap_or(Path::new("."))
+                .join(ALLOWLIST_FILE_NAME);
+            if !allowlist_file.exists() {
+                return Ok(None);
+            }
+            allowlist_file
+        }
+    };
+    let content = std::fs::read_to_string(&allowlist_file)
+        .with_context(|| format!("failed reading {}", allowlist_file.display()))?;
+    let allowlist = toml::from_str(&content)
+        .with_context(|| format!("failed loading {}", allowlist_file.display()))?;
+    Ok(Some(allowlist))
+}
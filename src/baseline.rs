@@ -0,0 +1,101 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::display::ReportDisplay;
+use crate::validations::CovenantValidationContext;
+
+const PASS: &str = "PASS";
+const WARN: &str = "WARN";
+const FAIL: &str = "FAIL";
+
+/// Status of every (key, field) of a validation run, the worst of its messages
+fn field_statuses(ctx: &CovenantValidationContext) -> BTreeMap<(String, String), &'static str> {
+    let mut statuses = BTreeMap::new();
+    for (entries, status) in [
+        (ctx.checks(), PASS),
+        (ctx.warnings(), WARN),
+        (ctx.errors(), FAIL),
+    ] {
+        collect(&mut statuses, entries, status);
+    }
+    statuses
+}
+
+fn collect(
+    statuses: &mut BTreeMap<(String, String), &'static str>,
+    entries: &HashMap<&str, Vec<String>>,
+    status: &'static str,
+) {
+    for (key, messages) in entries {
+        for message in messages {
+            let field = message.split_once(": ").map(|x| x.0).unwrap_or_default();
+            // Entries are collected from the best to the worst status
+            statuses.insert((key.to_string(), field.to_owned()), status);
+        }
+    }
+}
+
+/// Fields whose status changed since the baseline run, as a markdown table; the newly
+/// failing fields first
+pub fn render_baseline_diff(
+    baseline: &CovenantValidationContext,
+    current: &CovenantValidationContext,
+    display: &ReportDisplay,
+) -> String {
+    let before = field_statuses(baseline);
+    let after = field_statuses(current);
+    let mut changes = before
+        .keys()
+        .chain(after.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter_map(|field| {
+            let (was, is) = (before.get(field).copied(), after.get(field).copied());
+            (was != is).then_some((field, was, is))
+        })
+        .collect::<Vec<_>>();
+    changes.sort_by_key(|(_, was, is)| rank(*was, *is));
+
+    let newly_failing = changes.iter().filter(|c| c.2 == Some(FAIL)).count();
+    let newly_passing = changes
+        .iter()
+        .filter(|c| c.1 == Some(FAIL) && c.2.is_some_and(|is| is != FAIL))
+        .count();
+    let mut out = format!(
+        "\n**Baseline**: {} newly failing, {} newly passing, {} other changes\n\n",
+        newly_failing,
+        newly_passing,
+        changes.len() - newly_failing - newly_passing
+    );
+    if changes.is_empty() {
+        return out;
+    }
+    out.push_str("| Key | Field | Baseline | Current | Change |\n");
+    out.push_str("| :--- | :--- | :---: | :---: | :---: |\n");
+    for ((key, field), was, is) in changes {
+        let change = match (was, is) {
+            (_, Some(FAIL)) => "⛔️ newly failing",
+            (Some(FAIL), None) => "removed",
+            (Some(FAIL), _) => "✅ newly passing",
+            (None, _) => "new",
+            (_, None) => "removed",
+            _ => "changed",
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            display.key(key),
+            field,
+            was.unwrap_or("-"),
+            is.unwrap_or("-"),
+            change
+        ));
+    }
+    out
+}
+
+fn rank(was: Option<&str>, is: Option<&str>) -> u8 {
+    match (was, is) {
+        (_, Some(FAIL)) => 0,
+        (Some(FAIL), Some(_)) => 1,
+        _ => 2,
+    }
+}
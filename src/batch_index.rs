@@ -0,0 +1,126 @@
+use anyhow::Context;
+use serde::Serialize;
+use std::path::Path;
+
+use crate::validations::CovenantValidationContext;
+
+/// Summary of one covenant of a batch validation
+#[derive(Clone, Debug, Serialize)]
+pub struct BatchEntry {
+    /// Name of the covenant directory
+    pub name: String,
+    pub label: String,
+    pub contract: String,
+    /// Party names (or chain names when unnamed)
+    pub parties: Vec<String>,
+    /// `PASS`, `FAIL`, or `ERROR` when the validation could not run
+    pub status: &'static str,
+    pub errors: usize,
+    pub warnings: usize,
+    /// Report path, relative to the index
+    pub report: Option<String>,
+    /// Reason the validation could not run
+    pub error: Option<String>,
+}
+
+impl BatchEntry {
+    pub fn from_context(name: &str, ctx: &CovenantValidationContext, report: String) -> Self {
+        let count = |entries: &std::collections::HashMap<&str, Vec<String>>| {
+            entries.values().map(Vec::len).sum()
+        };
+        let parties = [
+            (ctx.party_a_name(), ctx.party_a_chain_name()),
+            (ctx.party_b_name(), ctx.party_b_chain_name()),
+        ]
+        .into_iter()
+        .filter(|(name, chain_name)| name.is_some() || !chain_name.is_empty())
+        .map(|(name, chain_name)| match name {
+            Some(name) if !chain_name.is_empty() => format!("{} ({})", name, chain_name),
+            Some(name) => name,
+            None => chain_name,
+        })
+        .collect();
+        BatchEntry {
+            name: name.to_owned(),
+            label: ctx.covenant_label(),
+            contract: ctx.covenant_contract(),
+            parties,
+            status: if ctx.has_errors() { "FAIL" } else { "PASS" },
+            errors: count(ctx.errors()),
+            warnings: count(ctx.warnings()),
+            report: Some(report),
+            error: None,
+        }
+    }
+
+    pub fn failed(name: &str, error: &anyhow::Error) -> Self {
+        BatchEntry {
+            name: name.to_owned(),
+            label: String::new(),
+            contract: String::new(),
+            parties: vec![],
+            status: "ERROR",
+            errors: 0,
+            warnings: 0,
+            report: None,
+            error: Some(format!("{:#}", error)),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.status == "PASS"
+    }
+}
+
+/// Governance round summary of the batch, as markdown
+pub fn render_index_markdown(entries: &[BatchEntry]) -> String {
+    let passed = entries.iter().filter(|e| e.passed()).count();
+    let mut out = format!(
+        "**Covenants**: {} validated, {} passed, {} failed\n\n",
+        entries.len(),
+        passed,
+        entries.len() - passed
+    );
+    out.push_str("| Covenant | Label | Type | Parties | Status | Errors | Warnings | Report |\n");
+    out.push_str("| :--- | :--- | :--- | :--- | :---: | ---: | ---: | :--- |\n");
+    for entry in entries {
+        let status = match entry.status {
+            "PASS" => "✅",
+            "FAIL" => "⛔️",
+            _ => "❌",
+        };
+        let report = match (&entry.report, &entry.error) {
+            (Some(report), _) => format!("[{}]({})", report, report),
+            (None, Some(error)) => markdown_cell(error),
+            (None, None) => String::new(),
+        };
+        out.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            markdown_cell(&entry.name),
+            markdown_cell(&entry.label),
+            markdown_cell(&entry.contract),
+            markdown_cell(&entry.parties.join(", ")),
+            status,
+            entry.errors,
+            entry.warnings,
+            report
+        ));
+    }
+    out
+}
+
+// Names and labels are free text, they must not break the table
+fn markdown_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Write the `index.md` and `index.json` summaries of the batch to the output directory
+pub fn write_index(output_dir: &Path, entries: &[BatchEntry]) -> Result<(), anyhow::Error> {
+    let index_md = output_dir.join("index.md");
+    std::fs::write(&index_md, render_index_markdown(entries))
+        .with_context(|| format!("failed writing {}", index_md.display()))?;
+    let index_json = output_dir.join("index.json");
+    std::fs::write(&index_json, serde_json::to_string_pretty(entries)?)
+        .with_context(|| format!("failed writing {}", index_json.display()))?;
+    Ok(())
+}
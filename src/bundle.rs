@@ -0,0 +1,170 @@
+use anyhow::{Context, Error};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+use std::path::Path;
+
+use crate::context::{Bundle, RecordedResponse};
+
+// Index of the archive: status and body file of every recorded URL
+const MANIFEST_FILE: &str = "manifest.json";
+// Response bodies, named by their SHA-256
+const RESPONSES_DIR: &str = "responses";
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct Manifest {
+    version: String,
+    responses: BTreeMap<String, ManifestEntry>,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+struct ManifestEntry {
+    status: u16,
+    /// Body file in the archive, error bodies are not kept
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<String>,
+}
+
+/// Write a bundle as a gzipped tarball: the manifest and one file per response body
+pub fn write_bundle(path: &Path, bundle: &Bundle) -> Result<(), Error> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("failed creating {}", path.display()))?;
+    let mut archive = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    let mut manifest = Manifest {
+        version: bundle.version.clone(),
+        responses: BTreeMap::new(),
+    };
+    let mut bodies = BTreeMap::new();
+    for (url, response) in &bundle.responses {
+        let body = response.is_success().then(|| {
+            let name = format!(
+                "{}/{}",
+                RESPONSES_DIR,
+                base16ct::lower::encode_string(&Sha256::digest(response.body.as_bytes()))
+            );
+            bodies.insert(name.clone(), response.body.as_bytes());
+            name
+        });
+        manifest.responses.insert(
+            url.clone(),
+            ManifestEntry {
+                status: response.status,
+                body,
+            },
+        );
+    }
+    append_file(
+        &mut archive,
+        MANIFEST_FILE,
+        serde_json::to_string_pretty(&manifest)?.as_bytes(),
+    )?;
+    for (name, body) in bodies {
+        append_file(&mut archive, &name, body)?;
+    }
+    archive
+        .into_inner()?
+        .finish()
+        .with_context(|| format!("failed writing {}", path.display()))?;
+    Ok(())
+}
+
+fn append_file<W: Write>(
+    archive: &mut tar::Builder<W>,
+    name: &str,
+    content: &[u8],
+) -> Result<(), Error> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o644);
+    archive.append_data(&mut header, name, content)?;
+    Ok(())
+}
+
+/// Read a bundle written by `write_bundle`
+pub fn read_bundle(path: &Path) -> Result<Bundle, Error> {
+    let file =
+        std::fs::File::open(path).with_context(|| format!("failed reading {}", path.display()))?;
+    let mut archive = tar::Archive::new(GzDecoder::new(file));
+    let mut files = BTreeMap::new();
+    for entry in archive
+        .entries()
+        .with_context(|| format!("{} is not a bundle archive", path.display()))?
+    {
+        let mut entry = entry?;
+        let name = entry.path()?.to_string_lossy().into_owned();
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+        files.insert(name, content);
+    }
+    let manifest: Manifest = serde_json::from_str(
+        files
+            .get(MANIFEST_FILE)
+            .with_context(|| format!("no {} in {}", MANIFEST_FILE, path.display()))?,
+    )
+    .with_context(|| format!("failed loading the manifest of {}", path.display()))?;
+    let mut responses = BTreeMap::new();
+    for (url, entry) in manifest.responses {
+        let body = match &entry.body {
+            Some(name) => files
+                .get(name)
+                .cloned()
+                .with_context(|| format!("no body {} for {} in the bundle", name, url))?,
+            None => String::new(),
+        };
+        responses.insert(
+            url,
+            RecordedResponse {
+                status: entry.status,
+                body,
+            },
+        );
+    }
+    Ok(Bundle {
+        version: manifest.version,
+        responses,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_round_trips_without_error_bodies() {
+        let dir = std::env::temp_dir().join(format!("covenant-cli-bundle-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bundle.tar.gz");
+        let bundle = Bundle {
+            version: "0.1.0".to_owned(),
+            responses: BTreeMap::from([
+                (
+                    "https://rest.cosmos.directory/neutron/ok".to_owned(),
+                    RecordedResponse {
+                        status: 200,
+                        body: "{\"data\":{}}".to_owned(),
+                    },
+                ),
+                (
+                    "https://rest.cosmos.directory/neutron/missing".to_owned(),
+                    RecordedResponse {
+                        status: 404,
+                        body: "not found".to_owned(),
+                    },
+                ),
+            ]),
+        };
+        write_bundle(&path, &bundle).unwrap();
+
+        let read = read_bundle(&path).unwrap();
+        assert_eq!(read.version, "0.1.0");
+        let ok = &read.responses["https://rest.cosmos.directory/neutron/ok"];
+        assert_eq!((ok.status, ok.body.as_str()), (200, "{\"data\":{}}"));
+        let missing = &read.responses["https://rest.cosmos.directory/neutron/missing"];
+        assert_eq!((missing.status, missing.body.as_str()), (404, ""));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,29 @@
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+/// Serialize a JSON value with sorted object keys and no whitespace
+pub fn canonical_json(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            let entries = entries
+                .into_iter()
+                .map(|(key, value)| {
+                    format!("{}:{}", Value::from(key.as_str()), canonical_json(value))
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(","))
+        }
+        Value::Array(values) => {
+            let values = values.iter().map(canonical_json).collect::<Vec<_>>();
+            format!("[{}]", values.join(","))
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Hex encoded SHA-256 of the canonical JSON of a message
+pub fn message_hash(value: &Value) -> String {
+    base16ct::lower::encode_string(&Sha256::digest(canonical_json(value).as_bytes()))
+}
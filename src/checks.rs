@@ -0,0 +1,789 @@
+use anyhow::Error;
+
+/// Documented validation check, identified by `<covenant>-<section>-<number>` (e.g. `TPP-LP-003`)
+struct Check {
+    number: u16,
+    field: &'static str,
+    description: &'static str,
+    rationale: &'static str,
+    remediation: &'static str,
+}
+
+/// Report section (validation context key) with its checks
+struct Section {
+    key: &'static str,
+    code: &'static str,
+    description: &'static str,
+    checks: &'static [Check],
+}
+
+const COVENANTS: &[(&str, &str)] = &[
+    ("SPP", "valence-covenant-single-party-pol"),
+    ("TPP", "valence-covenant-two-party-pol"),
+    ("SWP", "valence-covenant-swap"),
+];
+
+const MSG_CHECKS: &[Check] = &[Check {
+    number: 1,
+    field: "",
+    description: "The instantiation message matches the message schema of the metadata `contract` (detected from the message shape when omitted).",
+    rationale: "A message the covenant contract cannot parse fails at instantiation, after the covenant was announced to the parties.",
+    remediation: "Fix the field reported at the given JSON path, unknown fields are rejected, or declare the matching `contract`.",
+}];
+
+const COVENANT_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "label",
+        description: "The covenant label is set, matches the metadata `label_pattern` and is not used by another covenant (on-chain mode).",
+        rationale: "The label is the only human readable handle on the deployed covenant, a duplicate makes it ambiguous in explorers and tooling.",
+        remediation: "Set a descriptive, unique label following the naming convention.",
+    },
+    Check {
+        number: 2,
+        field: "covenant_type",
+        description: "The covenant type matches the parties (share or side based).",
+        rationale: "The covenant type decides whether funds are split by share or by side, a mismatch sends liquidity to the wrong party on withdrawal.",
+        remediation: "Use `share` for interchain parties and `side` for native parties.",
+    },
+    Check {
+        number: 3,
+        field: "party_a_share",
+        description: "The party shares sum up to 100%.",
+        rationale: "Shares not summing up to 100% leave part of the withdrawn liquidity unassigned.",
+        remediation: "Adjust the party shares so they sum up to 100.",
+    },
+    Check {
+        number: 4,
+        field: "party_b_share",
+        description: "The party shares sum up to 100%.",
+        rationale: "Shares not summing up to 100% leave part of the withdrawn liquidity unassigned.",
+        remediation: "Adjust the party shares so they sum up to 100.",
+    },
+    Check {
+        number: 5,
+        field: "deposit_deadline",
+        description: "The deposit deadline is in the future and leaves a buffer after the expected execution date.",
+        rationale: "A deadline passing before the parties deposit expires the covenant and refunds them without providing liquidity.",
+        remediation: "Set a deposit deadline later than the expected execution date plus the buffer.",
+    },
+    Check {
+        number: 6,
+        field: "lockup_config",
+        description: "The lockup expires in the future, after the deposit deadline.",
+        rationale: "A lockup ending before the deposit deadline lets a party withdraw before the liquidity was ever provided.",
+        remediation: "Set a lockup expiration later than the deposit deadline.",
+    },
+    Check {
+        number: 7,
+        field: "lockup_period",
+        description: "The lockup period expires in the future.",
+        rationale: "An expired lockup period lets the liquidity be withdrawn as soon as it is provided.",
+        remediation: "Set a lockup expiration later than the current block height or time.",
+    },
+    Check {
+        number: 8,
+        field: "ragequit_config",
+        description: "The ragequit configuration is consistent with the lockup.",
+        rationale: "A ragequit penalty of 100% or more confiscates the whole position of the party leaving the covenant.",
+        remediation: "Disable ragequit or set a penalty lower than 100%.",
+    },
+    Check {
+        number: 9,
+        field: "clock_tick_max_gas",
+        description: "The clock tick gas limit is set.",
+        rationale: "Ticks running out of gas stall the covenant, funds stay in the contract that failed to tick.",
+        remediation: "Set a gas limit large enough for a tick of every contract.",
+    },
+    Check {
+        number: 10,
+        field: "emergency_committee",
+        description: "The emergency committee address is set.",
+        rationale: "Without an emergency committee, funds stuck after a failed transfer can't be recovered.",
+        remediation: "Set the address of the multisig able to unwind the covenant.",
+    },
+    Check {
+        number: 11,
+        field: "fallback_split",
+        description: "The fallback split routes unexpected denoms to the parties.",
+        rationale: "Denoms without a split (airdrops, rewards) are lost unless the fallback split routes them.",
+        remediation: "Set receivers for both parties with shares summing up to 100%.",
+    },
+    Check {
+        number: 12,
+        field: "clock_whitelist",
+        description: "The clock whitelist and initial queue are empty, the covenant enqueues its children.",
+        rationale: "Whitelisted addresses can tick the clock on behalf of the covenant and enqueue foreign contracts.",
+        remediation: "Remove the whitelist or queue entries, they grant privileges on the clock.",
+    },
+    Check {
+        number: 13,
+        field: "covenant_terms",
+        description: "The swap covenant terms amounts match the contributions of the parties.",
+        rationale: "The swap only completes once both amounts are deposited, an amount off by a decimal blocks or underfunds the swap.",
+        remediation: "Set each party amount to its contribution, in base units of the denom.",
+    },
+    Check {
+        number: 14,
+        field: "",
+        description: "The party contributions are worth the same at the current pool price, within the metadata `contribution_parity_tolerance_pct`, and the deployed covenant routes the split denoms to its interchain routers.",
+        rationale: "Lopsided contributions hand part of the value of one party to the other once the liquidity is shared.",
+        remediation: "Adjust the contributions to the pool price, or redeploy a covenant whose routers match the splits.",
+    },
+];
+
+const FEES_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "ica_timeout",
+        description: "The ICA timeout is positive and below one day.",
+        rationale: "ICA messages timing out close the ICA channel, and an overly long timeout keeps funds in flight past the deadlines.",
+        remediation: "Use a timeout long enough for relayers, usually a few hours.",
+    },
+    Check {
+        number: 2,
+        field: "ibc_transfer_timeout",
+        description: "The IBC transfer timeout is positive and below one day.",
+        rationale: "Transfers timing out are refunded and retried, an overly long timeout keeps funds in flight past the deadlines.",
+        remediation: "Use a timeout long enough for relayers, usually a few hours.",
+    },
+    Check {
+        number: 3,
+        field: "ack_fee",
+        description: "The IBC ack fee covers the Neutron fee refunder minimum.",
+        rationale: "The Neutron fee refunder rejects IBC messages with fees below its minimum.",
+        remediation: "Raise the ack fee to at least the `feerefunder` minimum.",
+    },
+    Check {
+        number: 4,
+        field: "timeout_fee",
+        description: "The IBC timeout fee covers the Neutron fee refunder minimum.",
+        rationale: "The Neutron fee refunder rejects IBC messages with fees below its minimum.",
+        remediation: "Raise the timeout fee to at least the `feerefunder` minimum.",
+    },
+    Check {
+        number: 5,
+        field: "fee_denom",
+        description: "The IBC fee denom is a fee token of the host chain and the fees cover the relaying gas at its average gas price.",
+        rationale: "Relayers skip packets whose fees do not cover their gas, leaving transfers pending until they time out.",
+        remediation: "Pay the fees in an accepted fee token, sized from the registry gas prices.",
+    },
+];
+
+const CODE_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "checksum",
+        description: "The code ids release artifact matches the expected SHA256 digest.",
+        rationale: "Code ids from an unexpected artifact may point to contracts that were never audited.",
+        remediation: "Check `contract_codes_sha256` or the release the code ids come from.",
+    },
+    Check {
+        number: 2,
+        field: "ibc_forwarder_code",
+        description: "The IBC forwarder code id matches the release.",
+        rationale: "A code id of another release instantiates a contract with an incompatible message or behavior.",
+        remediation: "Use the code id published for the covenant release.",
+    },
+    Check {
+        number: 3,
+        field: "holder_code",
+        description: "The holder code id matches the release.",
+        rationale: "A code id of another release instantiates a contract with an incompatible message or behavior.",
+        remediation: "Use the code id published for the covenant release.",
+    },
+    Check {
+        number: 4,
+        field: "clock_code",
+        description: "The clock code id matches the release.",
+        rationale: "A code id of another release instantiates a contract with an incompatible message or behavior.",
+        remediation: "Use the code id published for the covenant release.",
+    },
+    Check {
+        number: 5,
+        field: "interchain_router_code",
+        description: "The interchain router code id matches the release.",
+        rationale: "A code id of another release instantiates a contract with an incompatible message or behavior.",
+        remediation: "Use the code id published for the covenant release.",
+    },
+    Check {
+        number: 6,
+        field: "native_router_code",
+        description: "The native router code id matches the release.",
+        rationale: "A code id of another release instantiates a contract with an incompatible message or behavior.",
+        remediation: "Use the code id published for the covenant release.",
+    },
+    Check {
+        number: 7,
+        field: "liquid_pooler_code",
+        description: "The liquid pooler code id matches the release.",
+        rationale: "A code id of another release instantiates a contract with an incompatible message or behavior.",
+        remediation: "Use the code id published for the covenant release.",
+    },
+    Check {
+        number: 8,
+        field: "remote_chain_splitter_code",
+        description: "The remote chain splitter code id matches the release.",
+        rationale: "A code id of another release instantiates a contract with an incompatible message or behavior.",
+        remediation: "Use the code id published for the covenant release.",
+    },
+    Check {
+        number: 9,
+        field: "liquid_staker_code",
+        description: "The liquid staker code id matches the release.",
+        rationale: "A code id of another release instantiates a contract with an incompatible message or behavior.",
+        remediation: "Use the code id published for the covenant release.",
+    },
+    Check {
+        number: 10,
+        field: "contract_version",
+        description:
+            "The deployed covenant and child contracts report the cw2 version of the release.",
+        rationale: "A contract migrated after instantiation may no longer behave as the audited release.",
+        remediation:
+            "Redeploy the contracts of another release from the declared release code ids.",
+    },
+];
+
+const PARTY_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "party_receiver_addr",
+        description: "The party receiver address is set (and, for deployed covenants, routes funds where expected).",
+        rationale: "The receiver is where the party gets its liquidity back, a wrong address loses the party funds.",
+        remediation: "Set the address receiving the party funds on its chain.",
+    },
+    Check {
+        number: 2,
+        field: "party_chain_connection_id",
+        description: "The connection id matches the chain registry path to the party chain.",
+        rationale: "The ICA is registered over this connection, a connection to another chain creates the ICA on the wrong chain.",
+        remediation: "Use the connection id of the canonical path in the chain registry.",
+    },
+    Check {
+        number: 3,
+        field: "ibc_transfer_timeout",
+        description: "The party IBC transfer timeout is positive and below one day.",
+        rationale: "Transfers timing out are refunded and retried, an overly long timeout keeps funds in flight past the deadlines.",
+        remediation: "Use a timeout long enough for relayers, usually a few hours.",
+    },
+    Check {
+        number: 4,
+        field: "party_to_host_chain_channel_id",
+        description: "The party to host chain channel matches the chain registry transfer channel.",
+        rationale: "Tokens sent over a non canonical channel arrive under another IBC denom and are not recognized by the covenant.",
+        remediation: "Use the transfer channel of the canonical path in the chain registry.",
+    },
+    Check {
+        number: 5,
+        field: "host_to_party_chain_channel_id",
+        description: "The host to party chain channel matches the chain registry transfer channel.",
+        rationale: "Tokens sent over a non canonical channel arrive under another IBC denom and are not recognized by the covenant.",
+        remediation: "Use the transfer channel of the canonical path in the chain registry.",
+    },
+    Check {
+        number: 6,
+        field: "remote_chain_denom",
+        description: "The remote chain denom is known by the party chain asset list.",
+        rationale: "The covenant waits for a deposit in this denom, any other denom is never forwarded.",
+        remediation: "Use the base denom of the asset on the party chain.",
+    },
+    Check {
+        number: 7,
+        field: "addr",
+        description: "The party address is set.",
+        rationale: "Only this address may deposit to and withdraw from the covenant on behalf of the party.",
+        remediation: "Set the party address authorized to deposit and withdraw.",
+    },
+    Check {
+        number: 8,
+        field: "native_denom",
+        description: "The native denom is the IBC denom of the remote denom on the host chain.",
+        rationale: "The host chain contracts only match funds arriving under this denom.",
+        remediation: "Use `ibc/<SHA256(transfer/<channel>/<denom>)>` for the host to party channel.",
+    },
+    Check {
+        number: 9,
+        field: "contribution",
+        description: "The party contribution is set in the expected denom, above the metadata `min_contributions`.",
+        rationale: "An empty or dust contribution fulfills the deposit condition without committing the party.",
+        remediation: "Set a positive contribution amount in the party denom.",
+    },
+    Check {
+        number: 10,
+        field: "denom_to_pfm_map",
+        description: "The packet forward middleware map routes multi-hop denoms.",
+        rationale: "Multi-hop denoms sent directly arrive under a denom the covenant does not expect.",
+        remediation: "Add an entry for every denom not native to the party chain.",
+    },
+    Check {
+        number: 11,
+        field: "fallback_address",
+        description: "The fallback address is set or intentionally left out.",
+        rationale: "Funds failing to route to the party chain are otherwise stuck on the host chain.",
+        remediation: "Set a party chain address receiving funds if the routing fails.",
+    },
+    Check {
+        number: 12,
+        field: "channel_uses_wasm_port",
+        description: "The host to party chain channel port matches the declared wasm port usage.",
+        rationale: "Transfers over a `wasm.` port are handled by a contract, not by the transfer module the covenant expects.",
+        remediation: "Set `party_a_channel_uses_wasm_port` to match the chain registry channel.",
+    },
+    Check {
+        number: 13,
+        field: "outpost_contract",
+        description: "The contract bound to the wasm port of the party channel is the declared outpost.",
+        rationale: "The outpost contract owning the port receives and releases the party funds.",
+        remediation: "Set `party_a_outpost_contract` to the contract owning the `wasm.` port.",
+    },
+    Check {
+        number: 14,
+        field: "refund_config",
+        description: "The deployed holder refunds the party deposit to its receiver, over its channel and in its denom.",
+        rationale: "The holder sends refunds using its own configuration, a mismatch refunds the deposit to another address or chain.",
+        remediation: "Redeploy the covenant with the party receiver, channel and denom of the party config.",
+    },
+    Check {
+        number: 15,
+        field: "",
+        description: "The IBC clients of the party path are active, its transfer channel is the canonical one and refunds unwind the denom trace back to the party chain.",
+        rationale: "Transfers over an expired or frozen client fail, and refunds over another route arrive as a foreign IBC denom.",
+        remediation: "Use the preferred channel of the chain registry path, or wait for the client to be recovered.",
+    },
+];
+
+const LS_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "ls_denom",
+        description: "The liquid staked denom is known by the LS chain asset list.",
+        rationale: "The liquid staker can only stake towards a denom its provider mints.",
+        remediation: "Use the liquid staked base denom of the LS provider.",
+    },
+    Check {
+        number: 2,
+        field: "ls_denom_on_neutron",
+        description: "The liquid staked denom on the host chain is its IBC denom.",
+        rationale: "The liquid staked tokens arrive on the host chain under this denom, any other denom is never pooled.",
+        remediation:
+            "Use `ibc/<SHA256(transfer/<channel>/<ls_denom>)>` for the host chain channel.",
+    },
+    Check {
+        number: 3,
+        field: "ls_chain_to_neutron_channel_id",
+        description: "The LS chain to host chain channel matches the chain registry.",
+        rationale: "Liquid staked tokens sent over a non canonical channel arrive under another IBC denom.",
+        remediation: "Use the transfer channel of the canonical path in the chain registry.",
+    },
+    Check {
+        number: 4,
+        field: "ls_neutron_connection_id",
+        description: "The host chain to LS chain connection matches the chain registry.",
+        rationale: "The liquid staker ICA is registered over this connection.",
+        remediation: "Use the connection id of the canonical path in the chain registry.",
+    },
+    Check {
+        number: 5,
+        field: "",
+        description: "The IBC clients of the LS path are active and the party chain is a registered host chain of the LS provider.",
+        rationale: "The liquid staker can't stake on a chain its provider does not support, and transfers over an inactive client fail.",
+        remediation: "Use a party chain and denom registered by the LS provider.",
+    },
+];
+
+const SPLITTER_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "channel_id",
+        description: "The splitter channel matches the party chain transfer channel.",
+        rationale: "The splitter transfers from the party chain ICA, over this channel.",
+        remediation: "Use the same channel as the covenant party config.",
+    },
+    Check {
+        number: 2,
+        field: "connection_id",
+        description: "The splitter connection matches the party chain connection.",
+        rationale: "The splitter ICA is registered over this connection.",
+        remediation: "Use the same connection as the covenant party config.",
+    },
+    Check {
+        number: 3,
+        field: "denom",
+        description: "The splitter denom is the party remote chain denom.",
+        rationale: "The splitter only splits the configured denom, the party deposit is otherwise never split.",
+        remediation: "Use the same denom as the covenant party config.",
+    },
+    Check {
+        number: 4,
+        field: "amount",
+        description: "The split amount is the party contribution.",
+        rationale: "The splitter waits for this amount, a larger amount is never reached and a smaller one leaves funds behind.",
+        remediation: "Use the covenant party contribution amount.",
+    },
+    Check {
+        number: 5,
+        field: "ls_share",
+        description: "The LS share is a valid ratio.",
+        rationale: "A share outside `[0, 1]` splits more than the deposit or a negative amount.",
+        remediation: "Use a decimal ratio between 0 and 1.",
+    },
+    Check {
+        number: 6,
+        field: "native_share",
+        description: "The native share is a valid ratio, summing to 1 with the LS share.",
+        rationale: "Shares not summing up to 1 leave part of the deposit behind or overdraw it.",
+        remediation: "Use a decimal ratio between 0 and 1, the complement of the LS share.",
+    },
+    Check {
+        number: 7,
+        field: "fallback_address",
+        description: "The splitter fallback address is set or intentionally left out.",
+        rationale: "Funds failing to split are otherwise stuck in the splitter ICA.",
+        remediation: "Set a party chain address receiving funds if the split fails.",
+    },
+    Check {
+        number: 8,
+        field: "splits",
+        description: "The split amounts match the LS and LP forwarder contributions.",
+        rationale: "Each forwarder waits for its own contribution, a mismatch with the split amounts blocks it.",
+        remediation: "Set the forwarder contributions to `amount * share` for each share.",
+    },
+];
+
+const LP_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "pool_address",
+        description: "The pool address is an existing Astroport pool, registered in the factory with the same assets and pair type.",
+        rationale: "A contract only mimicking the pair queries would receive the liquidity of the covenant.",
+        remediation: "Use the address of the Astroport pair for the covenant assets.",
+    },
+    Check {
+        number: 2,
+        field: "pool_pair_type",
+        description: "The pool pair type matches the Astroport pool.",
+        rationale: "The liquid pooler provides liquidity with the message of its pair type, other pair types reject it.",
+        remediation: "Use the pair type reported by the Astroport pair contract.",
+    },
+    Check {
+        number: 3,
+        field: "asset_a_denom",
+        description: "The asset A denom is one of the pool assets.",
+        rationale: "Liquidity is provided in the pool asset denoms, other denoms are rejected by the pair.",
+        remediation: "Use the host chain denom of the party A asset.",
+    },
+    Check {
+        number: 4,
+        field: "asset_b_denom",
+        description: "The asset B denom is one of the pool assets.",
+        rationale: "Liquidity is provided in the pool asset denoms, other denoms are rejected by the pair.",
+        remediation: "Use the host chain denom of the party B asset.",
+    },
+    Check {
+        number: 5,
+        field: "single_side_lp_limits_asset_a",
+        description: "The asset A single side limit stays within the configured share of the pool.",
+        rationale: "Single side liquidity is swapped at the pool price, a large limit lets the pool price move against the party.",
+        remediation: "Lower the limit or raise `single_side_lp_limit_pct_asset_a` (or `single_side_lp_limit_pct`) in the metadata.",
+    },
+    Check {
+        number: 6,
+        field: "single_side_lp_limits_asset_b",
+        description: "The asset B single side limit stays within the configured share of the pool.",
+        rationale: "Single side liquidity is swapped at the pool price, a large limit lets the pool price move against the party.",
+        remediation: "Lower the limit or raise `single_side_lp_limit_pct_asset_b` (or `single_side_lp_limit_pct`) in the metadata.",
+    },
+    Check {
+        number: 7,
+        field: "slippage_tolerance",
+        description: "The slippage tolerance is within the metadata `slippage_tolerance_bounds_pct` and below the acceptable price spread.",
+        rationale: "A tolerance above the price spread accepts swaps the price check would have rejected, a zero tolerance fails every swap.",
+        remediation: "Set a slippage tolerance within the bounds and below the acceptable price spread.",
+    },
+];
+
+const PRICE_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "expected_spot_price",
+        description: "The expected spot price matches the current pool price, expressed as pool asset A per asset B.",
+        rationale: "Liquidity is only provided while the pool price is within the spread of the expected price, an outdated or inverted price blocks the covenant.",
+        remediation: "Use the current pool price, inverted if expressed as asset B per asset A.",
+    },
+    Check {
+        number: 2,
+        field: "acceptable_price_spread",
+        description: "The acceptable price spread is a reasonable share of the spot price.",
+        rationale: "A wide spread lets liquidity be provided at a manipulated pool price.",
+        remediation: "Lower the spread to limit the price impact when providing liquidity.",
+    },
+    Check {
+        number: 3,
+        field: "pool_share",
+        description: "The covenant liquidity stays below the metadata `max_pool_share_pct` of the pool.",
+        rationale: "A position owning most of the pool makes the covenant the pool, exposed to its own price impact on withdrawal.",
+        remediation: "Lower the contributions or choose a deeper pool.",
+    },
+];
+
+const SPLITS_CHECKS: &[Check] = &[Check {
+    number: 1,
+    field: "",
+    description: "The denom splits route the party denoms to the party receivers, and the deployed routers distribute exactly the party denoms.",
+    rationale: "Funds of a denom without a split, or routed to unknown receivers, never reach the parties.",
+    remediation: "Set a split for both party denoms with shares summing up to 100%.",
+}];
+
+const MIGRATION_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "",
+        description: "The exit and entry pools are distinct and the holder owns the exit pool liquidity tokens (deployed mode).",
+        rationale: "Migrating the liquidity of a pool the covenant does not hold leaves the position in the exit pool.",
+        remediation: "Set the pool the covenant liquidity is in as the exit pool.",
+    },
+    Check {
+        number: 2,
+        field: "exit_pool",
+        description: "The exit pool pair type, assets and price match its configuration.",
+        rationale: "The migration withdraws from the exit pool with its pair message, a wrong configuration fails the withdrawal.",
+        remediation: "Fix the exit pool configuration from the pair and pool queries.",
+    },
+    Check {
+        number: 3,
+        field: "entry_pool",
+        description: "The entry pool pair type, assets and price match its configuration.",
+        rationale: "The migration provides liquidity with the entry pool message, a wrong configuration fails the deposit.",
+        remediation: "Fix the entry pool configuration from the pair and pool queries.",
+    },
+];
+
+const METADATA_CHECKS: &[Check] = &[
+    Check {
+        number: 1,
+        field: "",
+        description: "The metadata has a `[covenant]` table with a known contract and valid settings.",
+        rationale: "The validation settings come from the metadata, a covenant validated without them is checked against defaults.",
+        remediation: "Fix the metadata value reported in the message.",
+    },
+    Check {
+        number: 2,
+        field: "host_chain_name",
+        description: "The host chain name resolves to a chain registry chain.",
+        rationale: "Every registry and chain query of the host chain is made under this name.",
+        remediation: "Use the chain registry name of the host chain, aliases and chain ids are resolved.",
+    },
+    Check {
+        number: 3,
+        field: "party_a_chain_name",
+        description: "The party A chain name resolves to a chain registry chain.",
+        rationale: "The paths, channels and assets of party A are looked up under this name.",
+        remediation: "Use the chain registry name of the party A chain, aliases and chain ids are resolved.",
+    },
+    Check {
+        number: 4,
+        field: "party_b_chain_name",
+        description: "The party B chain name resolves to a chain registry chain.",
+        rationale: "The paths, channels and assets of party B are looked up under this name.",
+        remediation: "Use the chain registry name of the party B chain, aliases and chain ids are resolved.",
+    },
+];
+
+const SECURITY_CHECKS: &[Check] = &[Check {
+    number: 1,
+    field: "",
+    description: "The deployed covenant may only be migrated by the declared DAO, its children by the covenant or the DAO.",
+    rationale: "Any other admin can migrate a contract to code that withdraws the covenant funds.",
+    remediation: "Clear the admin or transfer it to the DAO declared as `admin_address`.",
+}];
+
+const DEPLOYMENT_CHECKS: &[Check] = &[Check {
+    number: 1,
+    field: "",
+    description: "The holder of the deployed covenant is in a known lifecycle phase, within its expected duration.",
+    rationale: "A covenant stuck in a phase past its lockup usually waits on a failed transfer or an expired client.",
+    remediation: "Check the pending transfers and clock ticks of the covenant.",
+}];
+
+const PLUGINS_CHECKS: &[Check] = &[Check {
+    number: 1,
+    field: "",
+    description: "The checks of the configured plugins pass.",
+    rationale: "Plugins carry the rules of a team that are not part of the CLI.",
+    remediation: "See the plugin documentation for the reported check.",
+}];
+
+const SECTIONS: &[Section] = &[
+    Section {
+        key: "instantiation",
+        code: "MSG",
+        description: "Instantiation message",
+        checks: MSG_CHECKS,
+    },
+    Section {
+        key: "covenant",
+        code: "COV",
+        description: "Covenant settings",
+        checks: COVENANT_CHECKS,
+    },
+    Section {
+        key: "fees",
+        code: "FEE",
+        description: "Timeouts and IBC fees",
+        checks: FEES_CHECKS,
+    },
+    Section {
+        key: "contract_codes",
+        code: "CODE",
+        description: "Contract code ids",
+        checks: CODE_CHECKS,
+    },
+    Section {
+        key: "covenant_party_config",
+        code: "PTY",
+        description: "Covenant party",
+        checks: PARTY_CHECKS,
+    },
+    Section {
+        key: "party_a_config",
+        code: "PA",
+        description: "Party A",
+        checks: PARTY_CHECKS,
+    },
+    Section {
+        key: "party_b_config",
+        code: "PB",
+        description: "Party B",
+        checks: PARTY_CHECKS,
+    },
+    Section {
+        key: "ls_forwarder_config",
+        code: "LSF",
+        description: "Liquid staking forwarder",
+        checks: PARTY_CHECKS,
+    },
+    Section {
+        key: "lp_forwarder_config",
+        code: "LPF",
+        description: "Liquid pooling forwarder",
+        checks: PARTY_CHECKS,
+    },
+    Section {
+        key: "ls_info",
+        code: "LS",
+        description: "Liquid staking",
+        checks: LS_CHECKS,
+    },
+    Section {
+        key: "remote_chain_splitter_config",
+        code: "RCS",
+        description: "Remote chain splitter",
+        checks: SPLITTER_CHECKS,
+    },
+    Section {
+        key: "liquid_pooler_config",
+        code: "LP",
+        description: "Liquid pooler",
+        checks: LP_CHECKS,
+    },
+    Section {
+        key: "pool_price_config",
+        code: "PP",
+        description: "Pool price",
+        checks: PRICE_CHECKS,
+    },
+    Section {
+        key: "pool_migration",
+        code: "MIG",
+        description: "Pool migration",
+        checks: MIGRATION_CHECKS,
+    },
+    Section {
+        key: "splits",
+        code: "SPL",
+        description: "Denom splits",
+        checks: SPLITS_CHECKS,
+    },
+    Section {
+        key: "metadata",
+        code: "META",
+        description: "Covenant metadata",
+        checks: METADATA_CHECKS,
+    },
+    Section {
+        key: "security",
+        code: "SEC",
+        description: "Contract admins",
+        checks: SECURITY_CHECKS,
+    },
+    Section {
+        key: "deployment",
+        code: "DEP",
+        description: "Deployed covenant lifecycle",
+        checks: DEPLOYMENT_CHECKS,
+    },
+    Section {
+        key: "plugins",
+        code: "PLG",
+        description: "Plugin checks",
+        checks: PLUGINS_CHECKS,
+    },
+];
+
+/// Static key of a documented report section
+pub fn section_key(key: &str) -> Option<&'static str> {
+    SECTIONS.iter().find(|s| s.key == key).map(|s| s.key)
+}
+
+/// Stable identifier of the check of a covenant field, if documented
+pub fn check_id(covenant_contract: &str, key: &str, field: &str) -> Option<String> {
+    let (covenant_code, _) = COVENANTS.iter().find(|(_, c)| *c == covenant_contract)?;
+    let section = SECTIONS.iter().find(|s| s.key == key)?;
+    // Section wide checks (empty field) cover the messages without a documented field
+    let check = section
+        .checks
+        .iter()
+        .find(|c| c.field == field)
+        .or_else(|| section.checks.iter().find(|c| c.field.is_empty()))?;
+    Some(format!(
+        "{}-{}-{:03}",
+        covenant_code, section.code, check.number
+    ))
+}
+
+/// Print the description, rationale and remediation of a check
+pub fn explain_check(id: &str) -> Result<(), Error> {
+    let id = id.to_uppercase();
+    let parts = id.split('-').collect::<Vec<&str>>();
+    let [covenant_code, section_code, number] = parts[..] else {
+        return Err(anyhow::anyhow!("invalid check id {}", id));
+    };
+    let (_, covenant_contract) = COVENANTS
+        .iter()
+        .find(|(code, _)| *code == covenant_code)
+        .ok_or_else(|| anyhow::anyhow!("unknown covenant in check id {}", id))?;
+    let section = SECTIONS
+        .iter()
+        .find(|s| s.code == section_code)
+        .ok_or_else(|| anyhow::anyhow!("unknown section in check id {}", id))?;
+    let check = number
+        .parse::<u16>()
+        .ok()
+        .and_then(|number| section.checks.iter().find(|c| c.number == number))
+        .ok_or_else(|| anyhow::anyhow!("unknown check {}", id))?;
+
+    println!("{}: {}", id, check.description);
+    println!("Covenant: {}", covenant_contract);
+    if check.field.is_empty() {
+        println!("Section: {} (`{}`)", section.description, section.key);
+    } else {
+        println!(
+            "Field: {} (`{}.{}`)",
+            section.description, section.key, check.field
+        );
+    }
+    println!("Rationale: {}", check.rationale);
+    println!("Remediation: {}", check.remediation);
+    Ok(())
+}
@@ -1,36 +1,374 @@
 use anyhow::Context;
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, Utc};
 use itertools::Itertools;
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
+use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use tokio::task::JoinSet;
 
 use crate::{
-    types::Commands,
+    acceptances::load_acceptances,
+    allowlist::load_allowlist,
+    baseline::render_baseline_diff,
+    batch_index::{render_index_markdown, write_index, BatchEntry},
+    bundle::{read_bundle, write_bundle},
+    canonical::message_hash,
+    checks::explain_check,
+    compare,
+    context::Bundle,
+    csv_report::render_csv,
+    diagnostics::detect_covenant_contracts,
+    display::ReportDisplay,
+    doctor,
+    error::CovenantCliError,
+    fixes::json_patch,
+    fund_check,
+    github_comment::render_github_comment,
+    incremental::{self, IncrementalCache},
+    init, inspect,
+    lifecycle::{verify_deployed_phase, StuckThresholds},
+    lint,
+    notify::{batch_summary, notify, validation_summary},
+    plugins::run_plugins,
+    proposal::render_proposal,
+    review,
+    saved_result::{load_result, save_result},
+    schema,
+    templates::{init_template, list_templates},
+    terminal_report::render_terminal,
+    types::{BundleCommands, Commands, Locale, OutputFormat, TemplateCommands},
+    utils::path::get_registry_commit,
     validations::{
-        CovenantValidationContext, SinglePartyPolCovenantInstMsg, SwapCovenantInstMsg,
-        TwoPartyPolCovenantInstMsg,
+        covenant_validator, normalize_chain_names, prefetch_registry, verify_admin_surface,
+        verify_allowlist, verify_clock_privileges, verify_contract_versions, verify_pool_migration,
+        CovenantValidationContext, CovenantVersion, PoolMigrationConfig, NEUTRON_CHAIN_NAME,
+        POOL_MIGRATION_FIELDS, POOL_MIGRATION_KEY, SECURITY_KEY,
     },
+    watch,
 };
 
 const DEFAULT_SINGLE_SIDE_LP_LIMIT_PCT: u32 = 10;
+pub(crate) const DEFAULT_CONTRACT_CODES_VERSION: &str = "v0.1.0";
+// Report key of the metadata errors
+const METADATA_KEY: &str = "metadata";
+// Covenant contracts supported by the validation
+pub(crate) const SUPPORTED_COVENANT_CONTRACTS: &[&str] = &[
+    "valence-covenant-single-party-pol",
+    "valence-covenant-two-party-pol",
+    "valence-covenant-swap",
+];
+
+type MetadataTable = toml::map::Map<String, toml::Value>;
 
 pub(crate) async fn execute_cmd(
-    _ctx: &crate::CliContext,
+    ctx: &crate::CliContext,
     cmd: &Commands,
 ) -> Result<(), anyhow::Error> {
     match cmd {
         Commands::Validate {
             metadata_file,
             instantiation_file,
+            bundle,
+            deployed_address,
+            verify_onchain,
+            code_ids_file,
+            allowlist,
+            plugins,
+            no_incremental,
+            party,
+            output_format,
+            locale,
+            summary_only,
+            report_file,
+            emit_fixes,
+            emit_proposal,
+            baseline,
+            save_result: result_file,
+            json_logs_file,
         } => {
-            let mut ctx = CovenantValidationContext::default();
-            validate_covenant(metadata_file, instantiation_file, &mut ctx).await?;
-            render_markdown_table(&ctx);
-            if ctx.has_errors() {
+            let ctx = match bundle {
+                Some(bundle_file) => ctx.with_bundle(load_bundle(bundle_file)?),
+                None => ctx.clone(),
+            };
+            let ctx = match json_logs_file {
+                Some(_) => ctx.with_audit_log(),
+                None => ctx,
+            };
+            let mut validation_ctx = CovenantValidationContext::new(ctx.clone());
+            if let Some(deployed_address) = deployed_address {
+                validation_ctx.set_deployed_address(deployed_address.clone());
+            }
+            validation_ctx.set_verify_onchain(*verify_onchain);
+            if let Some(code_ids_file) = code_ids_file {
+                validation_ctx.set_code_ids_file(code_ids_file.clone());
+            }
+            if let Some(allowlist_file) = allowlist {
+                validation_ctx.set_allowlist_file(allowlist_file.clone());
+            }
+            // Plugins are executed: only the operator (flags, config file) may declare them
+            for plugin in plugins.iter().chain(&ctx.config().plugins) {
+                validation_ctx.add_plugin(plugin.clone());
+            }
+            validation_ctx.set_skipped_keys(party.skipped_keys());
+            // A plan validates against unanswered queries, its results are not cached
+            validation_ctx
+                .set_incremental(!*no_incremental && json_logs_file.is_none() && !ctx.is_plan());
+            let validation =
+                validate_covenant(metadata_file, instantiation_file, &mut validation_ctx).await;
+            if ctx.is_plan() {
+                if let Err(e) = validation {
+                    debug!("[plan] validation stopped: {:#}", e);
+                }
+                render_planned_queries(&ctx);
+                return Ok(());
+            }
+            validation?;
+            let display = ReportDisplay::load(&validation_ctx, ctx.config().locale(*locale)).await;
+            if let Some(json_logs_file) = json_logs_file {
+                let hash = write_audit_log(&ctx, json_logs_file)?;
+                validation_ctx.set_audit_log_hash(hash);
+            }
+            if let Some(result_file) = result_file {
+                save_result(result_file, &validation_ctx, display.symbols())?;
+            }
+            let report = render_report(
+                &validation_ctx,
+                &display,
+                ctx.config().output_format(*output_format),
+            );
+            if let Some(report_file) = report_file {
+                std::fs::write(report_file, &report)
+                    .with_context(|| format!("failed writing {}", report_file))?;
+            }
+            if let Some(fixes_file) = emit_fixes {
+                let fixes = validation_ctx.fixes();
+                let patch = json_patch(&load_instantiation(instantiation_file)?, &fixes);
+                std::fs::write(fixes_file, serde_json::to_string_pretty(&patch)?)
+                    .with_context(|| format!("failed writing {}", fixes_file))?;
+                info!("{} fixes written to {}", patch.len(), fixes_file);
+            }
+            if let Some(proposal_file) = emit_proposal {
+                if validation_ctx.has_errors() {
+                    warn!("Covenant validation failed, no proposal written");
+                } else {
+                    let proposal = render_proposal(
+                        &validation_ctx,
+                        &display,
+                        &load_instantiation(instantiation_file)?,
+                    );
+                    std::fs::write(proposal_file, proposal)
+                        .with_context(|| format!("failed writing {}", proposal_file))?;
+                    info!("Proposal description written to {}", proposal_file);
+                }
+            }
+            if *summary_only {
+                println!("{}", render_summary_line(&validation_ctx));
+            } else {
+                print!("{}", report);
+            }
+            if let Some(baseline_file) = baseline {
+                let content = std::fs::read_to_string(baseline_file)
+                    .with_context(|| format!("failed reading {}", baseline_file))?;
+                let baseline = load_result(&content)?;
+                let diff = render_baseline_diff(&baseline.context, &validation_ctx, &display);
+                // Keep the CSV output parseable
+                if ctx.config().output_format(*output_format) == OutputFormat::Csv {
+                    eprint!("{}", diff);
+                } else {
+                    print!("{}", diff);
+                }
+            }
+            notify(&ctx, &validation_summary(&validation_ctx)).await;
+            if validation_ctx.has_errors() {
                 let err_msg = "Covenant validation failed";
                 error!("{}", err_msg);
                 anyhow::bail!(err_msg);
             }
             Ok(())
         }
+        Commands::ValidateBatch {
+            covenants_dir,
+            output_dir,
+            locale,
+            jobs,
+        } => {
+            let output_dir = std::path::Path::new(output_dir);
+            std::fs::create_dir_all(output_dir)
+                .with_context(|| format!("failed creating {}", output_dir.display()))?;
+            // Covenants of a round share chains and pools: the clones of the context share
+            // the run cache, so an identical fetch is only sent once for the whole batch
+            let covenant_dirs = batch_covenant_dirs(covenants_dir)?;
+            let locale = ctx.config().locale(*locale);
+            let mut entries = Vec::with_capacity(covenant_dirs.len());
+            entries.resize_with(covenant_dirs.len(), || None);
+            // The validations are I/O bound and not `Send`, they run concurrently on this thread
+            let batch = tokio::task::LocalSet::new();
+            batch
+                .run_until(async {
+                    let mut tasks = JoinSet::new();
+                    for (index, covenant_dir) in covenant_dirs.into_iter().enumerate() {
+                        if tasks.len() >= (*jobs).max(1) {
+                            if let Some(result) = tasks.join_next().await {
+                                let (index, entry) = result?;
+                                entries[index] = Some(entry);
+                            }
+                        }
+                        let ctx = ctx.clone();
+                        let output_dir = output_dir.to_path_buf();
+                        tasks.spawn_local(async move {
+                            let entry =
+                                validate_batch_covenant(&ctx, &covenant_dir, &output_dir, locale)
+                                    .await;
+                            (index, entry)
+                        });
+                    }
+                    while let Some(result) = tasks.join_next().await {
+                        let (index, entry) = result?;
+                        entries[index] = Some(entry);
+                    }
+                    Ok::<_, anyhow::Error>(())
+                })
+                .await?;
+            // Entries stay in the name order of the covenant directories
+            let entries = entries.into_iter().flatten().collect::<Vec<_>>();
+            write_index(output_dir, &entries)?;
+            print!("{}", render_index_markdown(&entries));
+            notify(ctx, &batch_summary(&entries, output_dir)).await;
+            let failures = entries.iter().filter(|e| !e.passed()).count();
+            if failures > 0 {
+                anyhow::bail!("{} of {} covenants failed", failures, entries.len());
+            }
+            Ok(())
+        }
+        Commands::Render {
+            result_file,
+            output_format,
+            locale,
+        } => {
+            let content = std::fs::read_to_string(result_file)
+                .with_context(|| format!("failed reading {}", result_file))?;
+            let result = load_result(&content)?;
+            let display = ReportDisplay::new(
+                &result.context,
+                result.symbols.clone(),
+                ctx.config().locale(*locale),
+            );
+            print!(
+                "{}",
+                render_report(
+                    &result.context,
+                    &display,
+                    ctx.config().output_format(*output_format),
+                )
+            );
+            Ok(())
+        }
+        Commands::Init { output_file } => init::run(ctx, output_file).await,
+        Commands::Review {
+            metadata_file,
+            instantiation_file,
+        } => {
+            let mut validation_ctx = CovenantValidationContext::new(ctx.clone());
+            validate_covenant(metadata_file, instantiation_file, &mut validation_ctx).await?;
+            let instantiation = load_instantiation(instantiation_file)?;
+            review::run(
+                &validation_ctx,
+                &serde_json::to_string_pretty(&instantiation)?,
+            )
+        }
+        Commands::Watch {
+            covenant_address,
+            host_chain_name,
+            interval,
+            metrics_listen,
+            stuck_thresholds,
+        } => {
+            let stuck_thresholds = StuckThresholds::new(stuck_thresholds)?;
+            // Polled values must not be served from the run cache
+            watch::run(
+                &ctx.without_cache(),
+                host_chain_name,
+                covenant_address,
+                *interval,
+                metrics_listen.as_deref(),
+                &stuck_thresholds,
+            )
+            .await
+        }
+        Commands::Bundle {
+            command:
+                BundleCommands::Create {
+                    metadata_file,
+                    instantiation_file,
+                    bundle_file,
+                },
+        } => {
+            let recording_ctx = ctx.with_recording();
+            let mut validation_ctx = CovenantValidationContext::new(recording_ctx.clone());
+            validate_covenant(metadata_file, instantiation_file, &mut validation_ctx).await?;
+            let bundle = recording_ctx.recorded_bundle();
+            write_bundle(std::path::Path::new(bundle_file), &bundle)?;
+            info!(
+                "Bundle with {} remote responses written to {}",
+                bundle.responses.len(),
+                bundle_file
+            );
+            Ok(())
+        }
+        Commands::Compare {
+            instantiation_file,
+            covenant_address,
+            host_chain_name,
+        } => {
+            let instantiation = load_instantiation(instantiation_file)?;
+            if compare::run(ctx, host_chain_name, &instantiation, covenant_address).await? {
+                let err_msg = "Deployed covenant differs from the instantiation message";
+                error!("{}", err_msg);
+                anyhow::bail!(err_msg);
+            }
+            Ok(())
+        }
+        Commands::Inspect {
+            covenant_address,
+            host_chain_name,
+        } => inspect::run(ctx, host_chain_name, covenant_address).await,
+        Commands::FundCheck {
+            covenant_address,
+            party,
+            host_chain_name,
+        } => fund_check::run(ctx, host_chain_name, covenant_address, *party).await,
+        Commands::ExplainCheck { id } => explain_check(id),
+        Commands::Doctor => doctor::run(ctx).await,
+        Commands::LintMetadata { metadata_file } => {
+            lint::run(ctx, &load_toml(metadata_file)?).await
+        }
+        Commands::Schema { schema_type } => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&schema::schema(*schema_type)?)?
+            );
+            Ok(())
+        }
+        Commands::Hash { file } => {
+            println!("{}", message_hash(&load_instantiation(file)?));
+            Ok(())
+        }
+        #[cfg(feature = "mock-server")]
+        Commands::MockServer { fixtures, listen } => {
+            crate::mock_server::run(fixtures, listen).await
+        }
+        Commands::Template { command } => match command {
+            TemplateCommands::List => {
+                list_templates();
+                Ok(())
+            }
+            TemplateCommands::Init {
+                name,
+                output_dir,
+                params,
+            } => init_template(name, output_dir, params),
+        },
     }
 }
 
@@ -43,87 +381,568 @@ async fn validate_covenant<'a>(
 
     // Read Covenant metadata file
     let metadata: toml::Value = load_toml(metadata_file)?;
-    let covenant_metadata = metadata.get("covenant").unwrap().as_table().unwrap();
+    let Some(covenant_metadata) = metadata.get("covenant").and_then(|c| c.as_table()) else {
+        validation_context.invalid(METADATA_KEY, "missing [covenant] table".to_owned());
+        return Ok(());
+    };
+    // Settings of the config file apply unless the metadata sets them
+    let covenant_metadata = &validation_context
+        .cli_context()
+        .config()
+        .merge_metadata(covenant_metadata);
     debug!("[covenant-metadata] {:?}", covenant_metadata);
 
-    let covenant_contract = configure_context(covenant_metadata, validation_context)?;
-
     // Read Covenant instantiation file
-    let instantiation: serde_json::Value = load_json(instantiation_file)?;
-
-    // Match on covenant type and create wrapper to validate
-    let covenant = match covenant_contract.as_ref() {
-        "valence-covenant-single-party-pol" => SinglePartyPolCovenantInstMsg::new(
-            serde_json::from_value(instantiation)
-                .with_context(|| "failed loading single party POL covenant")?,
-        )
-        .into_boxed(),
-        "valence-covenant-swap" => SwapCovenantInstMsg::new(
-            serde_json::from_value(instantiation)
-                .with_context(|| "failed loading swap covenant")?,
-        )
-        .into_boxed(),
-        "valence-covenant-two-party-pol" => TwoPartyPolCovenantInstMsg::new(
-            serde_json::from_value(instantiation)
-                .with_context(|| "failed loading two party POL covenant")?,
-        )
-        .into_boxed(),
-        _ => panic!("Unsupported covenant contract"),
+    let mut instantiation: serde_json::Value = load_instantiation(instantiation_file)?;
+    validation_context.set_message_hash(message_hash(&instantiation));
+    let full_instantiation = instantiation.clone();
+    // The pools of a migration covenant extend the message of the covenant contract
+    let pool_migration = instantiation
+        .as_object_mut()
+        .and_then(|msg| msg.remove(POOL_MIGRATION_KEY));
+
+    let declared_contract = match metadata_str(covenant_metadata, "contract") {
+        Ok(contract) => contract,
+        Err(e) => {
+            validation_context.invalid(METADATA_KEY, e.to_string());
+            return Ok(());
+        }
+    };
+    let Some(covenant_contract) =
+        resolve_covenant_contract(declared_contract, &instantiation, validation_context)
+    else {
+        // The contract conflict is reported, skip the validation
+        return Ok(());
     };
+    // The pools are validated apart, the covenant contract schema does not declare them
+    if pool_migration.is_some() {
+        validation_context.warn(
+            "instantiation",
+            format!(
+                "`{}` is not a field of the {} instantiation message, unknown fields are \
+                 rejected unless the deployed contract version supports pool migrations",
+                POOL_MIGRATION_KEY, covenant_contract
+            ),
+        );
+    }
+    // Metadata errors are reported, skip the validation
+    if let Err(e) = configure_context(covenant_metadata, &covenant_contract, validation_context) {
+        validation_context.invalid(METADATA_KEY, e.to_string());
+        return Ok(());
+    }
+    if let Err(e) = normalize_chain_names(validation_context).await {
+        warn!("Chain names not normalized: {}", e);
+    }
+    prefetch_registry(validation_context).await;
+    let covenant_version =
+        match CovenantVersion::try_from(validation_context.contract_codes_version().as_str()) {
+            Ok(version) => version,
+            Err(e) => {
+                validation_context.invalid(METADATA_KEY, e.to_string());
+                return Ok(());
+            }
+        };
+    info!("Covenant version: {:?}", covenant_version);
+
+    if let Some(label) = instantiation.get("label").and_then(|l| l.as_str()) {
+        validation_context.set_covenant_label(label.to_owned());
+    }
+    if let Ok(commit) = get_registry_commit(validation_context.cli_context()).await {
+        validation_context.set_registry_commit(commit);
+    }
+
+    // Match on covenant version and type and create wrapper to validate
+    let covenant = match covenant_validator(
+        validation_context,
+        covenant_version,
+        &covenant_contract,
+        instantiation.clone(),
+    ) {
+        Ok(covenant) => covenant,
+        Err(e) => {
+            validation_context.invalid("instantiation", e.to_string());
+            None
+        }
+    };
+    let Some(covenant) = covenant else {
+        // Deserialization errors are reported, skip the validation
+        return Ok(());
+    };
+
+    // Sections unchanged since the last run are restored from the incremental cache
+    let cache_path = IncrementalCache::path(metadata_file);
+    let snapshot =
+        if validation_context.incremental() && validation_context.deployed_address().is_none() {
+            incremental::snapshot(validation_context, covenant_metadata, metadata_file)
+        } else {
+            None
+        };
+    let cache = match snapshot {
+        Some(_) => IncrementalCache::load(&cache_path),
+        None => IncrementalCache::default(),
+    };
+    let mut unchanged = false;
+    if let Some(snapshot) = &snapshot {
+        let reusable = cache.reusable(snapshot, &full_instantiation);
+        unchanged = cache.is_complete(&reusable);
+        for (key, section) in reusable {
+            debug!("[incremental] {} unchanged", key);
+            validation_context.reuse_section(
+                key,
+                &section.checks,
+                &section.warnings,
+                &section.errors,
+                &section.fixes,
+                &section.checked_fields,
+            );
+        }
+    }
+
+    if unchanged {
+        info!("Covenant unchanged since the last run, cached results reused");
+    } else {
+        // Validate the covenant
+        let mut expected_fields = covenant.fields();
+        if pool_migration.is_some() {
+            expected_fields.extend(POOL_MIGRATION_FIELDS);
+        }
+        validation_context.set_expected_fields(expected_fields);
+        covenant
+            .validate(validation_context)
+            .await
+            .map_err(|e| anyhow::anyhow!(e))?;
+        if let Some(pool_migration) = pool_migration {
+            match serde_json::from_value::<PoolMigrationConfig>(pool_migration) {
+                Ok(config) => verify_pool_migration(validation_context, &config).await,
+                Err(e) => validation_context.invalid(
+                    POOL_MIGRATION_KEY,
+                    format!("invalid pool migration config: {}", e),
+                ),
+            }
+        }
+        verify_clock_privileges(validation_context, &instantiation);
+        if let Some(covenant_addr) = validation_context.deployed_address() {
+            verify_deployed_phase(validation_context, &covenant_addr).await;
+            verify_admin_surface(validation_context, &covenant_addr).await;
+            verify_contract_versions(validation_context, &covenant_addr).await;
+        }
+        validation_context.dedupe_checks();
+        if let Some(snapshot) = &snapshot {
+            if let Err(e) = cache.save(
+                &cache_path,
+                snapshot,
+                validation_context,
+                &full_instantiation,
+            ) {
+                warn!("{:#}", e);
+            }
+        }
+    }
+
+    // Checks of the inputs outside the message run on every validation, they are not cached
+    validation_context.end_reuse();
+    if let Some(allowlist) = load_allowlist(
+        validation_context.allowlist_file().as_deref(),
+        metadata_file,
+    )? {
+        verify_allowlist(validation_context, &allowlist, &instantiation);
+    }
+    // Plugins do not query through the CLI, a plan has nothing to list for them
+    if !validation_context.cli_context().is_plan() {
+        run_plugins(validation_context, &instantiation).await;
+    }
+    validation_context.dedupe_checks();
 
-    // Validate the covenant
-    covenant
-        .validate(validation_context)
-        .await
-        .map_err(|e| anyhow::anyhow!(e))
+    // Accepted deviations are reported separately and do not fail the validation
+    let acceptances = load_acceptances(metadata_file)?;
+    validation_context.accept_deviations(&acceptances);
+    Ok(())
+}
+
+/// Check the declared covenant contract against the shape of the instantiation message,
+/// the contract is detected from the message when the metadata omits it
+fn resolve_covenant_contract(
+    declared_contract: Option<&str>,
+    instantiation: &serde_json::Value,
+    validation_context: &mut CovenantValidationContext,
+) -> Option<String> {
+    let key = "instantiation";
+    let detected = detect_covenant_contracts(instantiation);
+    debug!(
+        "[covenant-contract] declared {:?}, detected {:?}",
+        declared_contract, detected
+    );
+    if let Some(contract) = declared_contract {
+        validation_context.set_covenant_contract(contract.to_owned());
+    }
+    match (declared_contract, detected.as_slice()) {
+        (Some(contract), _) if !SUPPORTED_COVENANT_CONTRACTS.contains(&contract) => {
+            validation_context.invalid(
+                key,
+                format!("unsupported covenant contract {} in the metadata", contract),
+            );
+            None
+        }
+        (Some(contract), detected) if detected.contains(&contract) => {
+            validation_context.valid(
+                key,
+                format!("message shape matches the declared contract {}", contract),
+            );
+            Some(contract.to_owned())
+        }
+        // The deserialization reports the fields not matching the declared contract
+        (Some(contract), []) => Some(contract.to_owned()),
+        (Some(contract), detected) => {
+            validation_context.invalid(
+                key,
+                format!(
+                    "metadata declares the contract {} but the message is a {} message",
+                    contract,
+                    detected.join(" / ")
+                ),
+            );
+            None
+        }
+        (None, [contract]) => {
+            validation_context.set_covenant_contract(contract.to_string());
+            validation_context.warn(
+                key,
+                format!(
+                    "contract {} detected from the message shape, declare it in the metadata",
+                    contract
+                ),
+            );
+            Some(contract.to_string())
+        }
+        (None, []) => {
+            validation_context.invalid(
+                key,
+                "no contract in the metadata and the message matches no covenant contract"
+                    .to_owned(),
+            );
+            None
+        }
+        (None, detected) => {
+            validation_context.invalid(
+                key,
+                format!(
+                    "no contract in the metadata and the message matches several contracts ({})",
+                    detected.join(", ")
+                ),
+            );
+            None
+        }
+    }
 }
 
 fn configure_context(
-    covenant_metadata: &toml::map::Map<String, toml::Value>,
+    covenant_metadata: &MetadataTable,
+    covenant_contract: &str,
     validation_context: &mut CovenantValidationContext,
-) -> Result<String, anyhow::Error> {
-    let covenant_contract = covenant_metadata.get("contract").unwrap().as_str().unwrap();
+) -> Result<(), CovenantCliError> {
     info!("Covenant contract: {:?}", covenant_contract);
 
-    let covenant_party_a_chain_name = covenant_metadata
-        .get("party_a_chain_name")
-        .unwrap()
-        .as_str()
-        .unwrap();
+    if let Some(contract_codes_version) = metadata_str(covenant_metadata, "contract_codes_version")?
+    {
+        validation_context.set_contract_codes_version(contract_codes_version.to_owned());
+    } else {
+        validation_context.set_contract_codes_version(DEFAULT_CONTRACT_CODES_VERSION.to_owned());
+    }
+
+    if let Some(contract_codes_checksum) = metadata_str(covenant_metadata, "contract_codes_sha256")?
+    {
+        validation_context.set_contract_codes_checksum(contract_codes_checksum.into());
+    }
+
+    // The code ids file given on the command line takes precedence over the metadata
+    if let Some(code_ids_file) = metadata_str(covenant_metadata, "contract_codes_file")? {
+        if validation_context.code_ids_file().is_none() {
+            validation_context.set_code_ids_file(code_ids_file.to_owned());
+        }
+    }
+
+    if let Some(code_ids_registry) = metadata_str(covenant_metadata, "contract_codes_registry")? {
+        validation_context.set_code_ids_registry(code_ids_registry.to_owned());
+    }
+
+    if let Some(host_chain_name) = metadata_str(covenant_metadata, "host_chain_name")? {
+        validation_context.set_host_chain_name(host_chain_name.to_owned());
+    } else {
+        validation_context.set_host_chain_name(NEUTRON_CHAIN_NAME.to_owned());
+    }
+
+    let covenant_party_a_chain_name = required_str(covenant_metadata, "party_a_chain_name")?;
     validation_context.set_party_a_chain_name(covenant_party_a_chain_name.to_string());
 
     if covenant_contract == "valence-covenant-two-party-pol"
         || covenant_contract == "valence-covenant-swap"
     {
-        let covenant_party_b_chain_name = covenant_metadata
-            .get("party_b_chain_name")
-            .unwrap()
-            .as_str()
-            .unwrap();
+        let covenant_party_b_chain_name = required_str(covenant_metadata, "party_b_chain_name")?;
         validation_context.set_party_b_chain_name(covenant_party_b_chain_name.to_string());
     }
 
-    if let Some(bool_setting) = covenant_metadata.get("party_a_channel_uses_wasm_port") {
-        let party_a_channel_uses_wasm_port = bool_setting.as_bool().unwrap();
-        if party_a_channel_uses_wasm_port {
-            validation_context.set_party_a_channel_uses_wasm_port(true);
-        }
+    if let Some(party_a_name) = metadata_str(covenant_metadata, "party_a_name")? {
+        validation_context.set_party_a_name(party_a_name.to_owned());
+    }
+    if let Some(party_b_name) = metadata_str(covenant_metadata, "party_b_name")? {
+        validation_context.set_party_b_name(party_b_name.to_owned());
+    }
+
+    if let Some(party_a_channel_uses_wasm_port) = metadata_value(
+        covenant_metadata,
+        "party_a_channel_uses_wasm_port",
+        "a boolean",
+        |v| v.as_bool(),
+    )? {
+        validation_context.set_party_a_channel_uses_wasm_port(party_a_channel_uses_wasm_port);
+    }
+    if let Some(outpost_contract) = metadata_str(covenant_metadata, "party_a_outpost_contract")? {
+        validation_context.set_party_a_outpost_contract(outpost_contract.to_owned());
+    }
+    if let Some(outpost_contract) = metadata_str(covenant_metadata, "party_b_outpost_contract")? {
+        validation_context.set_party_b_outpost_contract(outpost_contract.to_owned());
     }
 
-    if let Some(ls_provider_setting) = covenant_metadata.get("ls_provider") {
-        let ls_provider = ls_provider_setting.as_str().unwrap();
-        validation_context.set_ls_provider(ls_provider.into());
+    if let Some(withdrawal_address) = metadata_str(covenant_metadata, "withdrawal_address")? {
+        validation_context.set_withdrawal_address(withdrawal_address.to_owned());
     }
 
-    if let Some(pct_setting) = covenant_metadata.get("single_side_lp_limit_pct") {
-        let single_side_lp_limit_pct = pct_setting.as_integer().unwrap();
-        validation_context
-            .set_single_side_lp_limit_pct(single_side_lp_limit_pct.try_into().unwrap());
+    if let Some(ls_provider) = metadata_str(covenant_metadata, "ls_provider")? {
+        validation_context.set_ls_provider(ls_provider.try_into()?);
+    }
+
+    if let Some(single_side_lp_limit_pct) =
+        metadata_int(covenant_metadata, "single_side_lp_limit_pct")?
+    {
+        validation_context.set_single_side_lp_limit_pct(single_side_lp_limit_pct);
     } else {
         validation_context.set_single_side_lp_limit_pct(DEFAULT_SINGLE_SIDE_LP_LIMIT_PCT);
     }
 
-    Ok(covenant_contract.to_owned())
+    if let Some(limit_pct) = metadata_int(covenant_metadata, "single_side_lp_limit_pct_asset_a")? {
+        validation_context.set_single_side_lp_limit_pct_asset_a(limit_pct);
+    }
+
+    if let Some(limit_pct) = metadata_int(covenant_metadata, "single_side_lp_limit_pct_asset_b")? {
+        validation_context.set_single_side_lp_limit_pct_asset_b(limit_pct);
+    }
+
+    if let Some(rounding) = metadata_str(covenant_metadata, "single_side_lp_limit_rounding")? {
+        validation_context.set_single_side_lp_limit_rounding(rounding.try_into()?);
+    }
+
+    if let Some(tolerance) = metadata_int(covenant_metadata, "single_side_lp_limit_tolerance")? {
+        validation_context.set_single_side_lp_limit_tolerance(tolerance);
+    }
+
+    if let Some(pct) = metadata_int(covenant_metadata, "contribution_parity_tolerance_pct")? {
+        validation_context.set_contribution_parity_tolerance_pct(pct);
+    }
+
+    if let Some(factory_address) = metadata_str(covenant_metadata, "astroport_factory_address")? {
+        validation_context.set_astroport_factory_address(factory_address.to_owned());
+    }
+
+    if let Some(label_pattern) = metadata_str(covenant_metadata, "label_pattern")? {
+        validation_context.set_label_pattern(label_pattern.to_owned());
+    }
+
+    if let Some(admin_address) = metadata_str(covenant_metadata, "admin_address")? {
+        validation_context.set_expected_admin(admin_address.to_owned());
+    }
+
+    let bounds = metadata_value(
+        covenant_metadata,
+        "slippage_tolerance_bounds_pct",
+        "[min, max] percents",
+        |v| match v.as_array()?.as_slice() {
+            [min, max] => Some((metadata_pct(min)?, metadata_pct(max)?)),
+            _ => None,
+        },
+    )?;
+    if let Some((min_pct, max_pct)) = bounds {
+        validation_context.set_slippage_tolerance_bounds_pct(min_pct, max_pct);
+    }
+
+    if let Some(pct) = metadata_int(covenant_metadata, "max_pool_share_pct")? {
+        validation_context.set_max_pool_share_pct(pct);
+    }
+
+    let minimums = metadata_value(
+        covenant_metadata,
+        "min_contributions",
+        "a table of denom amounts",
+        |v| {
+            v.as_table()?
+                .iter()
+                .map(|(denom, amount)| {
+                    let amount = u128::try_from(amount.as_integer()?).ok()?;
+                    Some((denom.to_owned(), amount))
+                })
+                .collect::<Option<Vec<_>>>()
+        },
+    )?;
+    for (denom, amount) in minimums.unwrap_or_default() {
+        validation_context.set_min_contribution(denom, amount);
+    }
+
+    if let Some(date) = metadata_str(covenant_metadata, "expected_execution_date")? {
+        validation_context.set_expected_execution_time(parse_execution_date(date)?);
+    }
+
+    if let Some(hours) = metadata_int(covenant_metadata, "execution_deadline_buffer_hours")? {
+        validation_context.set_execution_deadline_buffer_hours(hours);
+    }
+
+    Ok(())
+}
+
+/// Typed metadata setting, an error when the setting is of another type
+fn metadata_value<'m, T>(
+    covenant_metadata: &'m MetadataTable,
+    key: &str,
+    expected: &str,
+    get: impl FnOnce(&'m toml::Value) -> Option<T>,
+) -> Result<Option<T>, CovenantCliError> {
+    covenant_metadata
+        .get(key)
+        .map(|value| {
+            get(value).ok_or_else(|| {
+                CovenantCliError::InvalidMetadata(format!("{}: expected {}", key, expected))
+            })
+        })
+        .transpose()
+}
+
+fn metadata_str<'m>(
+    covenant_metadata: &'m MetadataTable,
+    key: &str,
+) -> Result<Option<&'m str>, CovenantCliError> {
+    metadata_value(covenant_metadata, key, "a string", |v| v.as_str())
+}
+
+fn required_str<'m>(
+    covenant_metadata: &'m MetadataTable,
+    key: &str,
+) -> Result<&'m str, CovenantCliError> {
+    metadata_str(covenant_metadata, key)?
+        .ok_or_else(|| CovenantCliError::InvalidMetadata(format!("{}: missing", key)))
+}
+
+fn metadata_int<T: TryFrom<i64>>(
+    covenant_metadata: &MetadataTable,
+    key: &str,
+) -> Result<Option<T>, CovenantCliError> {
+    metadata_value(covenant_metadata, key, "a non-negative integer", |v| {
+        T::try_from(v.as_integer()?).ok()
+    })
+}
+
+fn metadata_pct(value: &toml::Value) -> Option<Decimal> {
+    match value {
+        toml::Value::Integer(pct) => Some(Decimal::from(*pct)),
+        toml::Value::Float(pct) => Decimal::try_from(*pct).ok(),
+        _ => None,
+    }
+}
+
+/// Seconds of an RFC 3339 date time, or of the start of a `YYYY-MM-DD` day (UTC)
+fn parse_execution_date(date: &str) -> Result<u64, CovenantCliError> {
+    let time = DateTime::parse_from_rfc3339(date)
+        .map(|time| time.timestamp())
+        .or_else(|_| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map(|day| day.and_time(NaiveTime::MIN).and_utc().timestamp())
+        })
+        .map_err(|_| {
+            CovenantCliError::InvalidMetadata(format!(
+                "expected_execution_date: expected an RFC 3339 date time or a YYYY-MM-DD date, got {}",
+                date
+            ))
+        })?;
+    u64::try_from(time).map_err(|_| {
+        CovenantCliError::InvalidMetadata(format!("expected_execution_date: {} before 1970", date))
+    })
+}
+
+/// Write the audit trail of the run, one JSON object per line; returns the file SHA-256
+fn write_audit_log(ctx: &crate::CliContext, json_logs_file: &str) -> Result<String, anyhow::Error> {
+    let mut content = String::new();
+    for entry in ctx.audit_entries() {
+        content.push_str(&serde_json::to_string(&entry)?);
+        content.push('\n');
+    }
+    std::fs::write(json_logs_file, &content)
+        .with_context(|| format!("failed writing {}", json_logs_file))?;
+    info!("Audit trail written to {}", json_logs_file);
+    let hash = Sha256::digest(content.as_bytes());
+    Ok(base16ct::lower::encode_string(&hash))
+}
+
+/// Validate a covenant of a batch and write its report, a failed validation is an entry
+async fn validate_batch_covenant(
+    ctx: &crate::CliContext,
+    covenant_dir: &std::path::Path,
+    output_dir: &std::path::Path,
+    locale: Locale,
+) -> BatchEntry {
+    let name = covenant_dir
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    info!("Validating covenant {}", name);
+    let mut validation_ctx = CovenantValidationContext::new(ctx.clone());
+    for plugin in &ctx.config().plugins {
+        validation_ctx.add_plugin(plugin.clone());
+    }
+    let validation = match batch_instantiation_file(covenant_dir) {
+        Some(instantiation_file) => {
+            let metadata_file = covenant_dir.join("metadata.toml");
+            validate_covenant(
+                &metadata_file.display().to_string(),
+                &instantiation_file.display().to_string(),
+                &mut validation_ctx,
+            )
+            .await
+        }
+        None => Err(anyhow::anyhow!("no instantiation file")),
+    };
+    if let Err(e) = validation {
+        error!("Covenant {} not validated: {:#}", name, e);
+        return BatchEntry::failed(&name, &e);
+    }
+    let display = ReportDisplay::load(&validation_ctx, locale).await;
+    let report = format!("{}.md", name);
+    let report_file = output_dir.join(&report);
+    // The other covenants of the batch are still reported
+    if let Err(e) = std::fs::write(
+        &report_file,
+        render_markdown_table(&validation_ctx, &display),
+    )
+    .with_context(|| format!("failed writing {}", report_file.display()))
+    {
+        error!("Covenant {} not reported: {:#}", name, e);
+        return BatchEntry::failed(&name, &e);
+    }
+    BatchEntry::from_context(&name, &validation_ctx, report)
+}
+
+/// Covenant directories of a batch, in name order
+fn batch_covenant_dirs(covenants_dir: &str) -> Result<Vec<std::path::PathBuf>, anyhow::Error> {
+    let mut dirs = std::fs::read_dir(covenants_dir)
+        .with_context(|| format!("failed reading {}", covenants_dir))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.join("metadata.toml").is_file())
+        .collect::<Vec<_>>();
+    dirs.sort();
+    Ok(dirs)
+}
+
+fn batch_instantiation_file(covenant_dir: &std::path::Path) -> Option<std::path::PathBuf> {
+    ["json", "yaml", "yml", "toml"]
+        .iter()
+        .map(|extension| covenant_dir.join(format!("instantiation.{}", extension)))
+        .find(|path| path.is_file())
 }
 
 fn load_toml(metadata_file: &String) -> Result<toml::Value, anyhow::Error> {
@@ -131,38 +950,247 @@ fn load_toml(metadata_file: &String) -> Result<toml::Value, anyhow::Error> {
         .with_context(|| "failed loading TOML file")
 }
 
-fn load_json(instantiation_file: &String) -> Result<serde_json::Value, anyhow::Error> {
-    serde_json::from_str(&std::fs::read_to_string(instantiation_file)?)
-        .with_context(|| "failed loading JSON file")
+fn load_bundle(bundle_file: &String) -> Result<Bundle, anyhow::Error> {
+    read_bundle(std::path::Path::new(bundle_file)).with_context(|| "failed loading bundle file")
 }
 
-fn render_markdown_table(ctx: &CovenantValidationContext) {
-    let mut is_first_key_msg = true;
-    println!("| Key | Field | Message | Status |\n| :--- | :--- | :--- | :---: |");
-    for (key, messages) in ctx.checks().iter().sorted_by_key(|x| x.0) {
-        for message in messages {
-            let parts = message.split(": ").collect::<Vec<&str>>();
-            println!(
-                "| {} | {} | {} | ✅ |",
-                if is_first_key_msg { key } else { "" },
-                parts.first().unwrap(),
-                parts.last().unwrap().replace('|', "&#124;")
-            );
-            if is_first_key_msg {
-                is_first_key_msg = false;
-            }
+/// Load an instantiation message written in JSON, or in YAML or TOML (by file extension)
+fn load_instantiation(instantiation_file: &String) -> Result<serde_json::Value, anyhow::Error> {
+    let content = std::fs::read_to_string(instantiation_file)?;
+    match std::path::Path::new(instantiation_file)
+        .extension()
+        .and_then(|ext| ext.to_str())
+    {
+        Some("yaml" | "yml") => {
+            serde_yaml::from_str(&content).with_context(|| "failed loading YAML file")
+        }
+        Some("toml") => {
+            let value: toml::Value =
+                toml::from_str(&content).with_context(|| "failed loading TOML file")?;
+            Ok(serde_json::to_value(value)?)
+        }
+        _ => serde_json::from_str(&content).with_context(|| "failed loading JSON file"),
+    }
+}
+
+fn render_planned_queries(ctx: &crate::CliContext) {
+    println!("Remote queries (the queries built from a response are not listed)\n");
+    println!("| Remote query |\n| :--- |");
+    for query in ctx.planned_queries() {
+        println!("| {} |", query);
+    }
+}
+
+/// One line verdict of a validation (`label: PASS/FAIL, N errors, M warnings`)
+fn render_summary_line(ctx: &CovenantValidationContext) -> String {
+    let count =
+        |entries: &HashMap<&str, Vec<String>>| entries.values().map(Vec::len).sum::<usize>();
+    format!(
+        "{}: {}, {} errors, {} warnings",
+        ctx.covenant_label(),
+        if ctx.has_errors() { "FAIL" } else { "PASS" },
+        count(ctx.errors()),
+        count(ctx.warnings())
+    )
+}
+
+fn render_report(
+    ctx: &CovenantValidationContext,
+    display: &ReportDisplay,
+    output_format: OutputFormat,
+) -> String {
+    match output_format {
+        OutputFormat::Markdown => render_markdown_table(ctx, display),
+        OutputFormat::GithubComment => render_github_comment(ctx, display),
+        OutputFormat::Csv => render_csv(ctx, display),
+        OutputFormat::Terminal => render_terminal(ctx, display, ctx.cli_context().use_color()),
+    }
+}
+
+fn render_markdown_table(ctx: &CovenantValidationContext, display: &ReportDisplay) -> String {
+    let mut out = String::new();
+    render_summary(&mut out, ctx);
+    out.push_str(
+        "| Key | Field | Message | Check | Status |\n| :--- | :--- | :--- | :--- | :---: |\n",
+    );
+    let is_security = |key: &str| key == SECURITY_KEY;
+    let is_not_security = |key: &str| key != SECURITY_KEY;
+    render_markdown_rows(&mut out, ctx, display, ctx.checks(), "✅", is_not_security);
+    render_markdown_rows(
+        &mut out,
+        ctx,
+        display,
+        ctx.warnings(),
+        "⚠️",
+        is_not_security,
+    );
+    render_markdown_rows(&mut out, ctx, display, ctx.errors(), "⛔️", is_not_security);
+    // Admin and migration checks of deployed covenants get their own section
+    if [ctx.checks(), ctx.warnings(), ctx.errors()]
+        .iter()
+        .any(|entries| entries.contains_key(SECURITY_KEY))
+    {
+        out.push_str("\n**Security**\n\n");
+        out.push_str(
+            "| Key | Contract | Message | Check | Status |\n| :--- | :--- | :--- | :--- | :---: |\n",
+        );
+        render_markdown_rows(&mut out, ctx, display, ctx.checks(), "✅", is_security);
+        render_markdown_rows(&mut out, ctx, display, ctx.warnings(), "⚠️", is_security);
+        render_markdown_rows(&mut out, ctx, display, ctx.errors(), "⛔️", is_security);
+    }
+    render_accepted_deviations(&mut out, ctx, display);
+    render_unchecked_fields(&mut out, ctx);
+    out
+}
+
+fn render_summary(out: &mut String, ctx: &CovenantValidationContext) {
+    let count =
+        |entries: &HashMap<&str, Vec<String>>| entries.values().map(Vec::len).sum::<usize>();
+    let party_chains = [
+        (ctx.party_a_name(), ctx.party_a_chain_name()),
+        (ctx.party_b_name(), ctx.party_b_chain_name()),
+    ]
+    .into_iter()
+    .filter(|(_, chain)| !chain.is_empty())
+    .map(|(name, chain)| match name {
+        Some(name) => format!("{} ({})", chain, name),
+        None => chain,
+    })
+    .join(", ");
+
+    out.push_str("## Covenant validation report\n\n");
+    out.push_str("| | |\n| :--- | :--- |\n");
+    out.push_str(&format!("| Label | {} |\n", ctx.covenant_label()));
+    out.push_str(&format!("| Covenant | {} |\n", ctx.covenant_contract()));
+    out.push_str(&format!(
+        "| Contract codes version | {} |\n",
+        ctx.contract_codes_version()
+    ));
+    out.push_str(&format!("| Host chain | {} |\n", ctx.host_chain_name()));
+    out.push_str(&format!("| Party chains | {} |\n", party_chains));
+    out.push_str(&format!(
+        "| Instantiation message SHA-256 | `{}` |\n",
+        ctx.message_hash()
+    ));
+    out.push_str(&format!(
+        "| Chain registry commit | {} |\n",
+        ctx.registry_commit()
+            .unwrap_or_else(|| "unknown".to_owned())
+    ));
+    if let Some(audit_log_hash) = ctx.audit_log_hash() {
+        out.push_str(&format!("| Audit trail SHA-256 | `{}` |\n", audit_log_hash));
+    }
+    out.push_str(&format!(
+        "| Tool version | {} {} |\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    ));
+    out.push_str(&format!(
+        "| Generated at | {} |\n",
+        Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+    ));
+    out.push_str(&format!(
+        "| Results | ✅ {} ⚠️ {} ⛔️ {} |\n\n",
+        count(ctx.checks()),
+        count(ctx.warnings()),
+        count(ctx.errors())
+    ));
+    if !ctx.skipped_keys().is_empty() {
+        out.push_str(&format!(
+            "Skipped sections of the other party: {}\n\n",
+            ctx.skipped_keys().join(", ")
+        ));
+    }
+    if !ctx.reused_keys().is_empty() {
+        out.push_str(&format!(
+            "Sections unchanged since the last run (cached): {}\n\n",
+            ctx.reused_keys().join(", ")
+        ));
+    }
+
+    let registry_sources = ctx.cli_context().registry_sources();
+    if !registry_sources.is_empty() {
+        out.push_str("**Registry files**\n\n| File | Source |\n| :--- | :--- |\n");
+        for (path, source) in registry_sources {
+            out.push_str(&format!("| {} | {} |\n", path, source));
+        }
+        out.push('\n');
+    }
+}
+
+fn render_accepted_deviations(
+    out: &mut String,
+    ctx: &CovenantValidationContext,
+    display: &ReportDisplay,
+) {
+    if ctx.accepted().is_empty() {
+        return;
+    }
+    out.push_str("\n**Accepted deviations**\n\n");
+    out.push_str(
+        "| Key | Field | Message | Check | Justification |\n| :--- | :--- | :--- | :--- | :--- |\n",
+    );
+    for (key, deviations) in ctx.accepted().iter().sorted_by_key(|x| x.0) {
+        for (message, justification) in deviations {
+            let (field, message) = message.split_once(": ").unwrap_or(("", message));
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                key,
+                field,
+                display.message(message).replace('|', "&#124;"),
+                ctx.check_id(key, field).unwrap_or_default(),
+                justification.replace('|', "&#124;")
+            ));
+        }
+    }
+}
+
+fn render_unchecked_fields(out: &mut String, ctx: &CovenantValidationContext) {
+    let unchecked_fields = ctx.unchecked_fields();
+    if unchecked_fields.is_empty() {
+        return;
+    }
+    out.push_str("\n**Unchecked fields**\n\n");
+    for (key, field) in unchecked_fields {
+        if field.is_empty() {
+            out.push_str(&format!("- `{}`\n", key));
+        } else {
+            out.push_str(&format!("- `{}.{}`\n", key, field));
         }
-        is_first_key_msg = true;
     }
-    for (key, messages) in ctx.errors().iter().sorted_by_key(|x| x.0) {
+}
+
+fn render_markdown_rows(
+    out: &mut String,
+    ctx: &CovenantValidationContext,
+    display: &ReportDisplay,
+    entries: &HashMap<&str, Vec<String>>,
+    status: &str,
+    include_key: impl Fn(&str) -> bool,
+) {
+    let mut is_first_key_msg = true;
+    for (key, messages) in entries
+        .iter()
+        .filter(|(key, _)| include_key(key))
+        .sorted_by_key(|x| x.0)
+    {
         for message in messages {
             let parts = message.split(": ").collect::<Vec<&str>>();
-            println!(
-                "| {} | {} | {} | ⛔️ |",
-                if is_first_key_msg { key } else { "" },
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} |\n",
+                if is_first_key_msg {
+                    display.key(key)
+                } else {
+                    String::new()
+                },
                 parts.first().unwrap(),
-                parts.last().unwrap().replace('|', "&#124;")
-            );
+                display
+                    .message(parts.last().unwrap())
+                    .replace('|', "&#124;"),
+                ctx.check_id(key, parts.first().unwrap())
+                    .unwrap_or_default(),
+                status
+            ));
             if is_first_key_msg {
                 is_first_key_msg = false;
             }
@@ -0,0 +1,78 @@
+use anyhow::Error;
+use log::warn;
+use serde_json::Value;
+
+use crate::context::CliContext;
+use crate::utils::cosmwasm::{get_contract_history, INIT_OPERATION};
+
+/// Compare a proposed instantiation message with the message a covenant was instantiated
+/// with, print the drift and return whether any was found
+pub async fn run(
+    ctx: &CliContext,
+    host_chain_name: &str,
+    instantiation: &Value,
+    covenant_addr: &str,
+) -> Result<bool, Error> {
+    let history = get_contract_history(ctx, host_chain_name, covenant_addr).await?;
+    let init = history
+        .iter()
+        .find(|entry| entry.operation == INIT_OPERATION)
+        .ok_or_else(|| anyhow::anyhow!("instantiation not found for {}", covenant_addr))?;
+    if history.len() > 1 {
+        warn!(
+            "covenant {} was migrated {} time(s) after its instantiation",
+            covenant_addr,
+            history.len() - 1
+        );
+    }
+
+    let mut drift = vec![];
+    diff_json("", instantiation, &init.msg, &mut drift);
+
+    println!("| Field | Proposed | Deployed | Status |\n| :--- | :--- | :--- | :---: |");
+    for (field, proposed, deployed) in &drift {
+        println!(
+            "| {} | {} | {} | ⛔️ |",
+            field,
+            proposed.replace('|', "&#124;"),
+            deployed.replace('|', "&#124;")
+        );
+    }
+    if drift.is_empty() {
+        println!("| * | identical | code {} | ✅ |", init.code_id);
+    }
+    Ok(!drift.is_empty())
+}
+
+/// Collect the `(path, proposed, deployed)` values differing between two JSON documents
+fn diff_json(
+    path: &str,
+    proposed: &Value,
+    deployed: &Value,
+    drift: &mut Vec<(String, String, String)>,
+) {
+    match (proposed, deployed) {
+        (Value::Object(proposed), Value::Object(deployed)) => {
+            let mut keys = proposed.keys().chain(deployed.keys()).collect::<Vec<_>>();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                diff_json(
+                    &format!("{}/{}", path, key),
+                    proposed.get(key).unwrap_or(&Value::Null),
+                    deployed.get(key).unwrap_or(&Value::Null),
+                    drift,
+                );
+            }
+        }
+        (Value::Array(proposed), Value::Array(deployed)) if proposed.len() == deployed.len() => {
+            for (i, (proposed, deployed)) in proposed.iter().zip(deployed).enumerate() {
+                diff_json(&format!("{}/{}", path, i), proposed, deployed, drift);
+            }
+        }
+        (proposed, deployed) if proposed != deployed => {
+            drift.push((path.to_owned(), proposed.to_string(), deployed.to_string()));
+        }
+        _ => {}
+    }
+}
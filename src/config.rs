@@ -0,0 +1,90 @@
+use anyhow::{Context, Error};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+
+use crate::types::{Locale, OutputFormat};
+use crate::utils::chain::{get_rest_url, get_rpc_url};
+
+/// Endpoints of a chain replacing the default LCD and RPC endpoints
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EndpointConfig {
+    pub rest: Option<String>,
+    pub rpc: Option<String>,
+}
+
+/// Shared configuration file (`--config`), the environment and the command line flags
+/// take precedence over its settings
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliConfig {
+    pub max_concurrency: Option<usize>,
+    pub request_interval_ms: Option<u64>,
+    pub notify_webhook: Option<String>,
+    pub mock_server: Option<String>,
+    pub chain_registry_local_first: Option<String>,
+    pub output_format: Option<OutputFormat>,
+    pub locale: Option<Locale>,
+    pub no_color: bool,
+    /// Executables adding custom checks to the validation reports
+    pub plugins: Vec<String>,
+    /// Trusted base64 secp256k1 public keys of the parties, by party name: the allowlist
+    /// signatures are verified against them, not against the keys of the allowlist
+    pub party_keys: BTreeMap<String, String>,
+    /// Endpoints by chain name
+    pub endpoints: BTreeMap<String, EndpointConfig>,
+    /// Default `[covenant]` metadata settings (tolerances, code ids sources...), the
+    /// metadata file takes precedence
+    pub metadata: toml::Table,
+}
+
+impl CliConfig {
+    pub fn load(config_file: &str) -> Result<Self, Error> {
+        toml::from_str(
+            &std::fs::read_to_string(config_file)
+                .with_context(|| format!("failed reading {}", config_file))?,
+        )
+        .with_context(|| format!("failed loading config file {}", config_file))
+    }
+
+    /// Output format of the flag, else of the config file, else the terminal table when
+    /// stdout is a terminal
+    pub fn output_format(&self, flag: Option<OutputFormat>) -> OutputFormat {
+        flag.or(self.output_format).unwrap_or_else(|| {
+            if std::io::stdout().is_terminal() {
+                OutputFormat::Terminal
+            } else {
+                OutputFormat::Markdown
+            }
+        })
+    }
+
+    /// Locale of the flag, else of the config file
+    pub fn locale(&self, flag: Option<Locale>) -> Locale {
+        flag.or(self.locale).unwrap_or_default()
+    }
+
+    /// Default endpoint URLs and the configured URLs replacing them
+    pub fn endpoint_overrides(&self) -> Vec<(String, String)> {
+        self.endpoints
+            .iter()
+            .flat_map(|(chain_name, endpoints)| {
+                [
+                    (get_rest_url(chain_name), endpoints.rest.clone()),
+                    (get_rpc_url(chain_name), endpoints.rpc.clone()),
+                ]
+            })
+            .filter_map(|(default_url, url)| {
+                url.map(|url| (default_url, url.trim_end_matches('/').to_owned()))
+            })
+            .collect()
+    }
+
+    /// Metadata settings of the config file overridden by the metadata file ones
+    pub fn merge_metadata(&self, covenant_metadata: &toml::Table) -> toml::Table {
+        let mut merged = self.metadata.clone();
+        merged.extend(covenant_metadata.clone());
+        merged
+    }
+}
@@ -1,28 +1,415 @@
-use anyhow::Error;
+use anyhow::{Context, Error};
+use chrono::{SecondsFormat, Utc};
 use reqwest::Client;
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{OnceCell, Semaphore, SemaphorePermit};
+use tokio::time::Instant;
+
+use crate::config::CliConfig;
 
 static USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"),);
 
+/// Remote responses (keyed by URL) used to run a validation offline
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Bundle {
+    pub version: String,
+    pub responses: BTreeMap<String, RecordedResponse>,
+}
+
+/// HTTP status and body of a remote response, the body of an error response is not kept
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RecordedResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+impl RecordedResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+/// Remote data read during a run, recorded in the audit trail of the run
+#[derive(Clone, Debug, Serialize)]
+pub struct AuditEntry {
+    pub url: String,
+    /// Hex encoded SHA-256 of the response body
+    pub sha256: String,
+    pub fetched_at: String,
+    /// `remote`, `bundle` or `local`
+    pub source: &'static str,
+}
+
+/// Remote responses (keyed by URL) fetched during one run
+type ResponseCache = Arc<Mutex<HashMap<String, Arc<OnceCell<String>>>>>;
+
 #[derive(Clone, Debug, Default)]
 pub struct CliContext {
     // API clients
     api: Client,
+    // Remote queries recorded instead of being performed (plan mode)
+    planned_queries: Option<Arc<Mutex<Vec<String>>>>,
+    // Remote responses recorded while creating a bundle
+    recorded_responses: Option<Arc<Mutex<BTreeMap<String, RecordedResponse>>>>,
+    // Digests of the remote data read, for the audit trail
+    audit_log: Option<Arc<Mutex<Vec<AuditEntry>>>>,
+    // Remote responses served from a bundle instead of the network
+    bundle: Option<Arc<Bundle>>,
+    // Remote responses memoized for the run, identical requests are only sent once
+    cache: Option<ResponseCache>,
+    // Webhook notified of the command results
+    notify_webhook: Option<String>,
+    // Cap on the concurrent remote requests, shared by all the clones of the context
+    request_limiter: Option<Arc<Semaphore>>,
+    // Minimum delay between two remote requests and time of the next allowed request
+    request_pacing: Option<(Duration, Arc<tokio::sync::Mutex<Instant>>)>,
+    // Base URL of a mock server the remote requests are sent to (local development)
+    mock_server: Option<String>,
+    // Local chain registry checkout looked up before the remote registry
+    local_registry: Option<PathBuf>,
+    // Source (`local` or `remote`) of each registry file read in hybrid mode
+    registry_sources: Arc<Mutex<BTreeMap<String, &'static str>>>,
+    // Whether the terminal report is colored
+    color: bool,
+    // Shared configuration file settings
+    config: Arc<CliConfig>,
+    // Default endpoint URLs and the configured URLs replacing them
+    endpoint_overrides: Arc<Vec<(String, String)>>,
 }
 
 impl CliContext {
-    pub async fn init() -> Result<CliContext, Error> {
+    pub async fn init(plan: bool) -> Result<CliContext, Error> {
         Ok(CliContext {
             api: Client::builder().user_agent(USER_AGENT).build()?,
+            planned_queries: plan.then(Default::default),
+            cache: Some(Default::default()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns a context recording every remote response it fetches
+    pub fn with_recording(&self) -> CliContext {
+        CliContext {
+            recorded_responses: Some(Default::default()),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context recording the digest of every remote response it reads
+    pub fn with_audit_log(&self) -> CliContext {
+        CliContext {
+            audit_log: Some(Default::default()),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context serving remote responses from the given bundle
+    pub fn with_bundle(&self, bundle: Bundle) -> CliContext {
+        CliContext {
+            bundle: Some(Arc::new(bundle)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context always fetching fresh remote responses
+    pub fn without_cache(&self) -> CliContext {
+        CliContext {
+            cache: None,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context notifying the given webhook of the command results
+    pub fn with_notify_webhook(&self, webhook_url: String) -> CliContext {
+        CliContext {
+            notify_webhook: Some(webhook_url),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context sending at most `max_concurrency` remote requests at once
+    pub fn with_max_concurrency(&self, max_concurrency: usize) -> CliContext {
+        CliContext {
+            request_limiter: Some(Arc::new(Semaphore::new(max_concurrency.max(1)))),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context waiting at least `interval` between two remote requests
+    pub fn with_request_interval(&self, interval: Duration) -> CliContext {
+        CliContext {
+            request_pacing: Some((interval, Arc::new(tokio::sync::Mutex::new(Instant::now())))),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context sending its remote requests to a mock server
+    pub fn with_mock_server(&self, base_url: String) -> CliContext {
+        CliContext {
+            mock_server: Some(base_url.trim_end_matches('/').to_owned()),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context coloring (or not) the terminal report
+    pub fn with_color(&self, color: bool) -> CliContext {
+        CliContext {
+            color,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context applying the settings of a configuration file
+    pub fn with_config(&self, config: CliConfig) -> CliContext {
+        CliContext {
+            endpoint_overrides: Arc::new(config.endpoint_overrides()),
+            config: Arc::new(config),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a context reading registry files from a local directory first
+    pub fn with_local_registry(&self, dir: PathBuf) -> CliContext {
+        CliContext {
+            local_registry: Some(dir),
+            ..self.clone()
+        }
+    }
+
+    pub fn local_registry(&self) -> Option<&Path> {
+        self.local_registry.as_deref()
+    }
+
+    pub fn record_registry_source(&self, path: &str, source: &'static str) {
+        self.registry_sources
+            .lock()
+            .unwrap()
+            .insert(path.to_owned(), source);
+    }
+
+    /// Source of each registry file read so far (hybrid resolution mode)
+    pub fn registry_sources(&self) -> BTreeMap<String, &'static str> {
+        self.registry_sources.lock().unwrap().clone()
+    }
+
+    /// Record the digest of remote data in the audit trail, when enabled
+    pub fn audit(&self, url: &str, body: &str, source: &'static str) {
+        if let Some(audit_log) = &self.audit_log {
+            audit_log.lock().unwrap().push(AuditEntry {
+                url: url.to_owned(),
+                sha256: base16ct::lower::encode_string(&Sha256::digest(body.as_bytes())),
+                fetched_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+                source,
+            });
+        }
+    }
+
+    /// Audit trail of the remote data read so far, in order
+    pub fn audit_entries(&self) -> Vec<AuditEntry> {
+        self.audit_log
+            .as_ref()
+            .map(|a| a.lock().unwrap().clone())
+            .unwrap_or_default()
+    }
+
+    /// Where the remote data comes from (bundle content, mock server, configured endpoints)
+    pub fn data_sources(&self) -> Value {
+        serde_json::json!({
+            "bundle": self.bundle.as_ref().map(|bundle| {
+                let bundle = serde_json::to_string(&**bundle).unwrap_or_default();
+                base16ct::lower::encode_string(&Sha256::digest(bundle.as_bytes()))
+            }),
+            "mock_server": self.mock_server,
+            "endpoints": self.endpoint_overrides.as_slice(),
         })
     }
 
-    #[allow(dead_code)]
+    pub fn notify_webhook(&self) -> Option<&str> {
+        self.notify_webhook.as_deref()
+    }
+
+    /// Bundle of the remote responses recorded so far
+    pub fn recorded_bundle(&self) -> Bundle {
+        Bundle {
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+            responses: self
+                .recorded_responses
+                .as_ref()
+                .map(|r| r.lock().unwrap().clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    pub fn use_color(&self) -> bool {
+        self.color
+    }
+
+    pub fn config(&self) -> &CliConfig {
+        &self.config
+    }
+
+    pub fn is_plan(&self) -> bool {
+        self.planned_queries.is_some()
+    }
+
+    /// Remote queries that would have been performed, in order of first use
+    pub fn planned_queries(&self) -> Vec<String> {
+        let mut queries: Vec<String> = vec![];
+        if let Some(planned_queries) = &self.planned_queries {
+            for query in planned_queries.lock().unwrap().iter() {
+                if !queries.contains(query) {
+                    queries.push(query.clone());
+                }
+            }
+        }
+        queries
+    }
+
+    fn plan_query(&self, url: &str) -> bool {
+        if let Some(planned_queries) = &self.planned_queries {
+            planned_queries.lock().unwrap().push(url.to_owned());
+            true
+        } else {
+            false
+        }
+    }
+
     pub async fn api_get<T>(&self, url: &str) -> Result<T, Error>
     where
         T: core::fmt::Debug + DeserializeOwned,
     {
-        let response = self.api.get(url).send().await?;
-        response.json().await.map_err(Error::from)
+        if self.plan_query(url) {
+            anyhow::bail!("planned query, not performed: {}", url);
+        }
+        let body = self.fetch(url).await?;
+        serde_json::from_str(&body).map_err(Error::from)
+    }
+
+    pub async fn api_get_text(&self, url: &str) -> Result<String, Error> {
+        if self.plan_query(url) {
+            return Ok(String::new());
+        }
+        self.fetch(url).await
+    }
+
+    pub async fn api_post<T>(&self, url: &str, body: &T) -> Result<(), Error>
+    where
+        T: Serialize,
+    {
+        if self.plan_query(url) {
+            return Ok(());
+        }
+        let _permit = self.acquire_request_slot().await;
+        self.api
+            .post(url)
+            .json(body)
+            .send()
+            .await?
+            .error_for_status()?;
+        Ok(())
+    }
+
+    /// Status code of a GET request, bypassing the bundle and the run cache
+    pub async fn probe(&self, url: &str) -> Result<u16, Error> {
+        let _permit = self.acquire_request_slot().await;
+        let response = self.api.get(self.remote_url(url)).send().await?;
+        Ok(response.status().as_u16())
+    }
+
+    async fn fetch(&self, url: &str) -> Result<String, Error> {
+        if let Some(bundle) = &self.bundle {
+            let response = bundle
+                .responses
+                .get(url)
+                .with_context(|| format!("response not found in bundle: {}", url))?;
+            if !response.is_success() {
+                anyhow::bail!("HTTP {} recorded in bundle: {}", response.status, url);
+            }
+            self.audit(url, &response.body, "bundle");
+            return Ok(response.body.clone());
+        }
+        match &self.cache {
+            Some(cache) => {
+                let cell = cache
+                    .lock()
+                    .unwrap()
+                    .entry(url.to_owned())
+                    .or_default()
+                    .clone();
+                cell.get_or_try_init(|| self.fetch_remote(url))
+                    .await
+                    .cloned()
+            }
+            None => self.fetch_remote(url).await,
+        }
+    }
+
+    /// Body of a successful response, error responses are neither cached nor recorded with
+    /// their body
+    async fn fetch_remote(&self, url: &str) -> Result<String, Error> {
+        let (status, body) = {
+            let _permit = self.acquire_request_slot().await;
+            let response = self.api.get(self.remote_url(url)).send().await?;
+            let status = response.status();
+            (status, response.text().await?)
+        };
+        let body = if status.is_success() {
+            body
+        } else {
+            String::new()
+        };
+        if let Some(recorded_responses) = &self.recorded_responses {
+            recorded_responses.lock().unwrap().insert(
+                url.to_owned(),
+                RecordedResponse {
+                    status: status.as_u16(),
+                    body: body.clone(),
+                },
+            );
+        }
+        if !status.is_success() {
+            anyhow::bail!("HTTP {}: {}", status, url);
+        }
+        self.audit(url, &body, "remote");
+        Ok(body)
+    }
+
+    /// URL a remote request is sent to, the mock server is given the full original URL
+    fn remote_url(&self, url: &str) -> String {
+        let url = self
+            .endpoint_overrides
+            .iter()
+            .find_map(|(default_url, configured_url)| {
+                url.strip_prefix(default_url.as_str())
+                    .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+                    .map(|rest| format!("{}{}", configured_url, rest))
+            })
+            .unwrap_or_else(|| url.to_owned());
+        match &self.mock_server {
+            Some(base_url) => format!("{}/{}", base_url, url),
+            None => url,
+        }
+    }
+
+    /// Wait for a free request slot and for the pacing delay, the slot is held by the permit
+    async fn acquire_request_slot(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.request_limiter {
+            Some(limiter) => limiter.acquire().await.ok(),
+            None => None,
+        };
+        if let Some((interval, next_request)) = &self.request_pacing {
+            let mut next_request = next_request.lock().await;
+            tokio::time::sleep_until(*next_request).await;
+            *next_request = Instant::now() + *interval;
+        }
+        permit
     }
 }
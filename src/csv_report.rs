@@ -0,0 +1,121 @@
+use itertools::Itertools;
+
+use crate::display::ReportDisplay;
+use crate::validations::CovenantValidationContext;
+
+const HEADER: &str = "section,field,expected,actual,message,check,status,severity,reviewer";
+
+/// Render the validation results as CSV, one row per check with an empty reviewer column
+pub fn render_csv(ctx: &CovenantValidationContext, display: &ReportDisplay) -> String {
+    // The exported rows are signed off, their denoms must be verifiable
+    let display = &display.with_full_denoms();
+    let mut out = format!("{}\n", HEADER);
+    for (entries, status, severity) in [
+        (ctx.checks(), "pass", "info"),
+        (ctx.warnings(), "warning", "warning"),
+        (ctx.errors(), "fail", "error"),
+    ] {
+        for (key, messages) in entries.iter().sorted_by_key(|x| x.0) {
+            for message in messages {
+                let (field, message) = message.split_once(": ").unwrap_or(("", message));
+                push_row(
+                    &mut out, ctx, display, key, field, message, status, severity,
+                );
+            }
+        }
+    }
+    for (key, deviations) in ctx.accepted().iter().sorted_by_key(|x| x.0) {
+        for (message, _) in deviations {
+            let (field, message) = message.split_once(": ").unwrap_or(("", message));
+            push_row(
+                &mut out, ctx, display, key, field, message, "accepted", "warning",
+            );
+        }
+    }
+    out
+}
+
+#[allow(clippy::too_many_arguments)]
+fn push_row(
+    out: &mut String,
+    ctx: &CovenantValidationContext,
+    display: &ReportDisplay,
+    key: &str,
+    field: &str,
+    message: &str,
+    status: &str,
+    severity: &str,
+) {
+    let message = display.message(message);
+    let (expected, actual) = split_expected_actual(&message);
+    let section = display.key(key);
+    let row = [
+        &section,
+        field,
+        expected,
+        actual,
+        &message,
+        &ctx.check_id(key, field).unwrap_or_default(),
+        status,
+        severity,
+        "",
+    ]
+    .into_iter()
+    .map(escape)
+    .join(",");
+    out.push_str(&row);
+    out.push('\n');
+}
+
+/// Expected and actual values of `...: expected X | actual Y` messages
+fn split_expected_actual(message: &str) -> (&str, &str) {
+    message
+        .split_once("expected ")
+        .and_then(|(_, rest)| rest.split_once(" | actual "))
+        .unwrap_or(("", ""))
+}
+
+/// Quote a CSV value, values read as formulas by spreadsheets are prefixed with `'`
+fn escape(value: &str) -> String {
+    let value = if value.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", value)
+    } else {
+        value.to_owned()
+    };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formulas_are_neutralized() {
+        assert_eq!(escape("=HYPERLINK(\"x\")"), "\"'=HYPERLINK(\"\"x\"\")\"");
+        assert_eq!(escape("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(escape("-1+1"), "'-1+1");
+        assert_eq!(escape("neutron1abc"), "neutron1abc");
+    }
+
+    #[test]
+    fn ibc_denoms_are_kept_whole() {
+        let denom = "ibc/C4CFF46FD6DE35CA4CF4CE031E643C8FDC9BA4B99AE598E9B0ED98FE3A2319F9";
+        let mut ctx = CovenantValidationContext::new(crate::context::CliContext::default());
+        ctx.valid_field("party_a_config", "native_denom", denom.to_owned());
+        let symbols = [(denom.to_owned(), "ATOM".to_owned())].into();
+        let display = ReportDisplay::new(&ctx, symbols, crate::types::Locale::Raw);
+
+        let csv = render_csv(&ctx, &display);
+        assert!(csv.contains(&format!("ATOM ({})", denom)), "{}", csv);
+    }
+
+    #[test]
+    fn line_breaks_are_quoted() {
+        assert_eq!(escape("a\r\nb"), "\"a\r\nb\"");
+        assert_eq!(escape("a,b"), "\"a,b\"");
+    }
+}
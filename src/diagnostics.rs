@@ -0,0 +1,171 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::Segment;
+
+use crate::validations::CovenantValidationContext;
+
+/// Deserialize an instantiation message, recording a report error (with the JSON path
+/// of the failure and a field name suggestion) instead of aborting the validation.
+pub fn deserialize_msg<T>(ctx: &mut CovenantValidationContext, instantiation: Value) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    match serde_path_to_error::deserialize::<_, T>(instantiation.clone()) {
+        Ok(msg) => Some(msg),
+        Err(e) => {
+            let path = e.path().clone();
+            let inner = e.into_inner().to_string();
+            let suggestion = suggest_field(&instantiation, &path, &inner);
+            ctx.invalid(
+                "instantiation",
+                format!(
+                    "{}: {}{}",
+                    path,
+                    inner,
+                    suggestion.map(|s| format!(" ({})", s)).unwrap_or_default()
+                ),
+            );
+            None
+        }
+    }
+}
+
+/// Covenant contracts the instantiation message deserializes to, by shape (v0.1 messages)
+pub fn detect_covenant_contracts(instantiation: &Value) -> Vec<&'static str> {
+    let matches = [
+        (
+            "valence-covenant-single-party-pol",
+            serde_json::from_value::<single_party_pol_covenant::msg::InstantiateMsg>(
+                instantiation.clone(),
+            )
+            .is_ok(),
+        ),
+        (
+            "valence-covenant-two-party-pol",
+            serde_json::from_value::<two_party_pol_covenant::msg::InstantiateMsg>(
+                instantiation.clone(),
+            )
+            .is_ok(),
+        ),
+        (
+            "valence-covenant-swap",
+            serde_json::from_value::<swap_covenant::msg::InstantiateMsg>(instantiation.clone())
+                .is_ok(),
+        ),
+    ];
+    matches
+        .into_iter()
+        .filter_map(|(contract, matched)| matched.then_some(contract))
+        .collect()
+}
+
+/// Field name fix of an unknown or missing field error
+fn suggest_field(
+    instantiation: &Value,
+    path: &serde_path_to_error::Path,
+    error: &str,
+) -> Option<String> {
+    let field = backticked(error).into_iter().next()?;
+    if error.starts_with("unknown field") {
+        // serde lists the expected fields after the unknown one
+        let expected = closest(&field, backticked(error).into_iter().skip(1))?;
+        Some(format!("did you mean `{}`?", expected))
+    } else if error.starts_with("missing field") {
+        // The expected field is missing, a present key may be its misspelling
+        let object = value_at(instantiation, path)?.as_object()?;
+        let present = closest(&field, object.keys().cloned())?;
+        Some(format!("rename `{}` to `{}`?", present, field))
+    } else {
+        None
+    }
+}
+
+fn value_at<'v>(value: &'v Value, path: &serde_path_to_error::Path) -> Option<&'v Value> {
+    path.iter().try_fold(value, |value, segment| match segment {
+        Segment::Seq { index } => value.get(index),
+        Segment::Map { key } => value.get(key),
+        Segment::Enum { variant } => value.get(variant),
+        Segment::Unknown => None,
+    })
+}
+
+fn backticked(message: &str) -> Vec<String> {
+    message
+        .split('`')
+        .skip(1)
+        .step_by(2)
+        .map(|s| s.to_owned())
+        .collect()
+}
+
+fn closest(field: &str, candidates: impl Iterator<Item = String>) -> Option<String> {
+    candidates
+        .filter(|candidate| candidate != field)
+        .map(|candidate| (levenshtein(field, &candidate), candidate))
+        .filter(|(distance, _)| *distance <= field.len() / 3 + 1)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut costs = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = costs[0];
+        costs[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = costs[j + 1];
+            costs[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(cur).min(costs[j])
+            };
+            prev = cur;
+        }
+    }
+    costs[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    #[allow(dead_code)]
+    struct Msg {
+        label: String,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    #[allow(dead_code)]
+    struct StrictMsg {
+        label: String,
+        party_a_config: Option<u64>,
+    }
+
+    fn suggestion<T: DeserializeOwned>(instantiation: Value) -> Option<String> {
+        let e = serde_path_to_error::deserialize::<_, T>(instantiation.clone())
+            .err()
+            .expect("the message does not deserialize");
+        suggest_field(&instantiation, e.path(), &e.inner().to_string())
+    }
+
+    #[test]
+    fn missing_fields_suggest_renaming_the_misspelled_key() {
+        assert_eq!(
+            suggestion::<Msg>(serde_json::json!({ "labl": "covenant" })),
+            Some("rename `labl` to `label`?".to_owned())
+        );
+    }
+
+    #[test]
+    fn unknown_fields_suggest_the_expected_field() {
+        assert_eq!(
+            suggestion::<StrictMsg>(serde_json::json!({ "label": "covenant", "party_a_confg": 1 })),
+            Some("did you mean `party_a_config`?".to_owned())
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use log::debug;
+use std::collections::HashMap;
+
+use crate::types::Locale;
+use crate::utils::assets::get_chain_denom_symbols;
+use crate::validations::CovenantValidationContext;
+
+// Hex characters of an IBC denom hash kept in reports
+const IBC_HASH_DISPLAY_LEN: usize = 6;
+
+/// Rendering of the party names, denoms and numbers of report messages
+#[derive(Clone, Debug, Default)]
+pub struct ReportDisplay {
+    symbols: HashMap<String, String>,
+    locale: Locale,
+    // Party (`party_a`, `party_b`) labels declared in the metadata
+    party_names: Vec<(&'static str, String)>,
+    // Keep IBC denoms whole, for the exported reports the denoms are verified from
+    full_denoms: bool,
+}
+
+impl ReportDisplay {
+    /// Resolve the symbols of the host and party chain assets
+    pub async fn load(ctx: &CovenantValidationContext<'_>, locale: Locale) -> Self {
+        let mut symbols = HashMap::new();
+        for chain_name in [
+            ctx.host_chain_name(),
+            ctx.party_a_chain_name(),
+            ctx.party_b_chain_name(),
+        ] {
+            if chain_name.is_empty() {
+                continue;
+            }
+            match get_chain_denom_symbols(ctx.cli_context(), &chain_name).await {
+                Ok(chain_symbols) => {
+                    for (denom, symbol) in chain_symbols {
+                        symbols.entry(denom).or_insert(symbol);
+                    }
+                }
+                Err(e) => debug!("no denom symbols for {}: {}", chain_name, e),
+            }
+        }
+        Self::new(ctx, symbols, locale)
+    }
+
+    /// Display of already resolved symbols (e.g. of a saved result)
+    pub fn new(
+        ctx: &CovenantValidationContext<'_>,
+        symbols: HashMap<String, String>,
+        locale: Locale,
+    ) -> Self {
+        let party_names = [
+            ("party_a", ctx.party_a_name()),
+            ("party_b", ctx.party_b_name()),
+        ]
+        .into_iter()
+        .filter_map(|(party, name)| name.map(|name| (party, name)))
+        .collect();
+        ReportDisplay {
+            symbols,
+            locale,
+            party_names,
+            full_denoms: false,
+        }
+    }
+
+    /// Same display without shortening the IBC denoms
+    pub fn with_full_denoms(&self) -> Self {
+        ReportDisplay {
+            full_denoms: true,
+            ..self.clone()
+        }
+    }
+
+    pub fn symbols(&self) -> &HashMap<String, String> {
+        &self.symbols
+    }
+
+    /// Label the keys of a party (`party_a_config`) with the party name
+    pub fn key(&self, key: &str) -> String {
+        match self.party_name(key) {
+            Some(name) => format!("{} ({})", key, name),
+            None => key.to_owned(),
+        }
+    }
+
+    fn party_name(&self, key: &str) -> Option<&str> {
+        self.party_names
+            .iter()
+            .find(|(party, _)| key.starts_with(party))
+            .map(|(_, name)| name.as_str())
+    }
+
+    /// Replace the parties of a message by their name, the known denoms by their symbol
+    /// and format its numbers
+    pub fn message(&self, message: &str) -> String {
+        let mut out = String::with_capacity(message.len());
+        let mut word = String::new();
+        for c in message.chars() {
+            if c.is_ascii_alphanumeric() || "/._-".contains(c) {
+                word.push(c);
+            } else {
+                out.push_str(&self.word(&word));
+                out.push(c);
+                word.clear();
+            }
+        }
+        out.push_str(&self.word(&word));
+        out
+    }
+
+    fn word(&self, word: &str) -> String {
+        // Keep a sentence ending dot out of the word
+        let (word, suffix) = match word.strip_suffix('.') {
+            Some(stripped) => (stripped, "."),
+            None => (word, ""),
+        };
+        if let Some(name) = self.party_names.iter().find(|(party, _)| *party == word) {
+            return format!("{}{}", name.1, suffix);
+        }
+        if let Some(symbol) = self.symbols.get(word) {
+            let denom = if self.full_denoms {
+                word.to_owned()
+            } else {
+                truncate_denom(word)
+            };
+            return format!("{} ({}){}", symbol, denom, suffix);
+        }
+        format!(
+            "{}{}",
+            self.number(word).unwrap_or_else(|| word.to_owned()),
+            suffix
+        )
+    }
+
+    fn number(&self, word: &str) -> Option<String> {
+        let (thousands, decimal) = match self.locale {
+            Locale::Raw => return None,
+            Locale::En => (",", "."),
+            Locale::De => (".", ","),
+            Locale::Fr => (" ", ","),
+        };
+        let (integer, fraction) = match word.split_once('.') {
+            Some((integer, fraction)) => (integer, Some(fraction)),
+            None => (word, None),
+        };
+        let is_digits = |s: &str| !s.is_empty() && s.chars().all(|c| c.is_ascii_digit());
+        if !is_digits(integer) || !fraction.map_or(true, is_digits) {
+            return None;
+        }
+        let mut grouped = String::new();
+        for (i, digit) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push_str(thousands);
+            }
+            grouped.push(digit);
+        }
+        if let Some(fraction) = fraction {
+            grouped.push_str(decimal);
+            grouped.push_str(fraction);
+        }
+        Some(grouped)
+    }
+}
+
+/// Shorten IBC denoms to the first characters of their hash
+fn truncate_denom(denom: &str) -> String {
+    match denom.strip_prefix("ibc/") {
+        Some(hash) if hash.len() > IBC_HASH_DISPLAY_LEN => {
+            format!("ibc/{}…", &hash[..IBC_HASH_DISPLAY_LEN])
+        }
+        _ => denom.to_owned(),
+    }
+}
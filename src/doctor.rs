@@ -0,0 +1,149 @@
+use anyhow::Error;
+use reqwest::Url;
+use std::time::{Duration, Instant};
+use tokio::net::lookup_host;
+
+use crate::commands::DEFAULT_CONTRACT_CODES_VERSION;
+use crate::context::CliContext;
+use crate::utils::chain::{get_rest_url, get_rpc_url};
+use crate::utils::path::{RAW_FILE_REPO_URL, REPO_COMMITS_URL};
+use crate::validations::{NEUTRON_CHAIN_NAME, RELEASES_URL};
+
+// Latency above which an endpoint is reported as slow
+const SLOW_LATENCY: Duration = Duration::from_secs(2);
+
+// Environment variables affecting the runs
+const ENV_VARS: &[&str] = &[
+    "RUST_LOG",
+    "COVENANT_NOTIFY_WEBHOOK",
+    "COVENANT_CONFIG",
+    "HTTPS_PROXY",
+    "HTTP_PROXY",
+    "NO_PROXY",
+];
+
+struct Endpoint {
+    name: &'static str,
+    url: String,
+    fallback: &'static str,
+}
+
+fn endpoints() -> Vec<Endpoint> {
+    vec![
+        Endpoint {
+            name: "Chain registry (GitHub)",
+            url: format!(
+                "{}/HEAD/{}/chain.json",
+                RAW_FILE_REPO_URL, NEUTRON_CHAIN_NAME
+            ),
+            fallback: "validate offline from a bundle (`bundle create`, then `validate --bundle`)",
+        },
+        Endpoint {
+            name: "Chain registry commits (GitHub API)",
+            url: format!("{}/HEAD", REPO_COMMITS_URL),
+            fallback:
+                "the report will show an unknown registry commit, GitHub may be rate limiting",
+        },
+        Endpoint {
+            name: "cosmos.directory",
+            url: format!("https://chains.cosmos.directory/{}", NEUTRON_CHAIN_NAME),
+            fallback: "validate offline from a bundle (`bundle create`, then `validate --bundle`)",
+        },
+        Endpoint {
+            name: "Neutron LCD",
+            url: format!(
+                "{}/cosmos/base/tendermint/v1beta1/blocks/latest",
+                get_rest_url(NEUTRON_CHAIN_NAME)
+            ),
+            fallback: "on-chain checks will fail, skip `--deployed-address` and `--verify-onchain`",
+        },
+        Endpoint {
+            name: "Neutron RPC",
+            url: format!("{}/status", get_rpc_url(NEUTRON_CHAIN_NAME)),
+            fallback: "`watch` needs the RPC, retry later",
+        },
+        Endpoint {
+            name: "Covenant releases (GitHub)",
+            url: format!(
+                "{}/{}/contract_code_ids.txt",
+                RELEASES_URL, DEFAULT_CONTRACT_CODES_VERSION
+            ),
+            fallback: "pass the code ids with `--code-ids-file` or `contract_codes_file`",
+        },
+    ]
+}
+
+/// Check the reachability of the endpoints used by the CLI and print the effective configuration
+pub async fn run(ctx: &CliContext) -> Result<(), Error> {
+    println!("Configuration");
+    println!(
+        "  version: {} {}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    );
+    println!("  --plan: {}", ctx.is_plan());
+    for var in ENV_VARS {
+        let value = match std::env::var(var) {
+            // Webhook URLs embed a secret
+            Ok(_) if var.contains("WEBHOOK") => "(set)".to_owned(),
+            Ok(value) => value,
+            Err(_) => "(unset)".to_owned(),
+        };
+        println!("  {}: {}", var, value);
+    }
+
+    println!("\nEndpoints");
+    let mut failures = 0;
+    for endpoint in endpoints() {
+        match check_endpoint(ctx, &endpoint.url).await {
+            Ok((status, latency)) if (200..400).contains(&status) => {
+                let slow = if latency > SLOW_LATENCY {
+                    " (slow)"
+                } else {
+                    ""
+                };
+                println!(
+                    "  ✅ {}: HTTP {} in {} ms{}",
+                    endpoint.name,
+                    status,
+                    latency.as_millis(),
+                    slow
+                );
+            }
+            Ok((status, _)) => {
+                failures += 1;
+                println!("  ⚠️ {}: HTTP {} ({})", endpoint.name, status, endpoint.url);
+                println!("     fallback: {}", endpoint.fallback);
+            }
+            Err(e) => {
+                failures += 1;
+                println!("  ⛔️ {}: {:#} ({})", endpoint.name, e, endpoint.url);
+                println!("     fallback: {}", endpoint.fallback);
+            }
+        }
+    }
+    if failures > 0 {
+        anyhow::bail!("{} endpoints unhealthy", failures);
+    }
+    Ok(())
+}
+
+/// Resolve the endpoint host, then time a request (the TLS handshake included)
+async fn check_endpoint(ctx: &CliContext, url: &str) -> Result<(u16, Duration), Error> {
+    let parsed = Url::parse(url)?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| anyhow::anyhow!("no host in {}", url))?;
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    lookup_host((host, port))
+        .await
+        .map_err(|e| anyhow::anyhow!("DNS resolution failed: {}", e))?
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("DNS resolution failed: no address for {}", host))?;
+    let start = Instant::now();
+    let status = ctx
+        .probe(url)
+        .await
+        .map_err(|e| anyhow::anyhow!("request failed (TLS or connection): {}", e))?;
+    Ok((status, start.elapsed()))
+}
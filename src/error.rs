@@ -0,0 +1,74 @@
+use thiserror::Error;
+
+/// Source error of a failed parse
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Failure classes of the remote data and validation paths
+#[derive(Debug, Error)]
+pub enum CovenantCliError {
+    /// Chain registry or release data could not be found
+    #[error("registry error: {0}")]
+    RegistryError(String),
+    /// Chain registry or release data could not be fetched
+    #[error("registry error: {0:#}")]
+    RegistryRequest(#[source] anyhow::Error),
+    /// LCD or RPC query returned unexpected data
+    #[error("LCD error: {0}")]
+    LcdError(String),
+    /// LCD or RPC query failed
+    #[error("LCD error: {0:#}")]
+    LcdRequest(#[source] anyhow::Error),
+    /// Remote data or message value is not of the expected shape
+    #[error("deserialize error: {0}")]
+    DeserializeError(String),
+    /// Remote data or message value could not be parsed
+    #[error("deserialize error: {context}: {source}")]
+    ParseError {
+        context: String,
+        #[source]
+        source: BoxError,
+    },
+    /// Metadata setting missing or of the wrong type
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(String),
+    /// Local input file could not be read
+    #[error("failed reading {path}: {source}")]
+    ReadError {
+        path: String,
+        #[source]
+        source: std::io::Error,
+    },
+    /// Validation could not be carried on
+    #[error("validation aborted: {0}")]
+    ValidationAbort(String),
+}
+
+impl CovenantCliError {
+    pub fn registry(e: impl Into<anyhow::Error>) -> Self {
+        CovenantCliError::RegistryRequest(e.into())
+    }
+
+    pub fn lcd(e: impl Into<anyhow::Error>) -> Self {
+        CovenantCliError::LcdRequest(e.into())
+    }
+
+    /// Parse failure of the value described by `context` (e.g. `block height`)
+    pub fn parse(context: impl Into<String>, e: impl Into<BoxError>) -> Self {
+        CovenantCliError::ParseError {
+            context: context.into(),
+            source: e.into(),
+        }
+    }
+}
+
+impl From<rust_decimal::Error> for CovenantCliError {
+    fn from(e: rust_decimal::Error) -> Self {
+        CovenantCliError::parse("decimal", e)
+    }
+}
+
+impl From<std::num::TryFromIntError> for CovenantCliError {
+    fn from(e: std::num::TryFromIntError) -> Self {
+        CovenantCliError::parse("integer", e)
+    }
+}
@@ -0,0 +1,151 @@
+use log::warn;
+use serde_json::{json, Value};
+
+use crate::validations::SuggestedFix;
+
+/// JSON Patch (RFC 6902) replacing the fields of the instantiation message with their
+/// suggested values, fields not found in the message are skipped
+pub fn json_patch(instantiation: &Value, fixes: &[&SuggestedFix]) -> Vec<Value> {
+    fixes
+        .iter()
+        .filter_map(|fix| match field_pointer(instantiation, fix) {
+            Some(pointer) => Some(json!({
+                "op": "replace",
+                "path": pointer,
+                "value": fix.value,
+            })),
+            None => {
+                warn!("{}.{}: field not found in the message", fix.key, fix.field);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Locate the field below the first object member named after the key, or anywhere
+/// in the message when the key is not a message field and the field is unique
+fn field_pointer(instantiation: &Value, fix: &SuggestedFix) -> Option<String> {
+    let path = fix.path.split('/').collect::<Vec<_>>();
+    for (pointer, value) in find_members(instantiation, &fix.key, String::new()) {
+        if let Some(found) = find_path(value, &path, pointer).into_iter().next() {
+            return Some(found);
+        }
+    }
+    let found = find_path(instantiation, &path, String::new());
+    (found.len() == 1).then(|| found[0].clone())
+}
+
+fn find_members<'v>(value: &'v Value, name: &str, pointer: String) -> Vec<(String, &'v Value)> {
+    children(value, &pointer)
+        .into_iter()
+        .flat_map(|(member, child_pointer, child)| {
+            let mut found = vec![];
+            if member == name {
+                found.push((child_pointer.clone(), child));
+            }
+            found.extend(find_members(child, name, child_pointer));
+            found
+        })
+        .collect()
+}
+
+fn find_path(value: &Value, path: &[&str], pointer: String) -> Vec<String> {
+    let mut found = vec![];
+    if path
+        .iter()
+        .try_fold(value, |node, segment| node.get(segment))
+        .is_some()
+    {
+        let suffix = path.iter().map(|s| escape(s)).collect::<Vec<_>>().join("/");
+        found.push(format!("{}/{}", pointer, suffix));
+    }
+    for (_, child_pointer, child) in children(value, &pointer) {
+        found.extend(find_path(child, path, child_pointer));
+    }
+    found
+}
+
+/// Members (or items) of a value with their pointer
+fn children<'v>(value: &'v Value, pointer: &str) -> Vec<(String, String, &'v Value)> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(member, child)| {
+                let child_pointer = format!("{}/{}", pointer, escape(member));
+                (member.clone(), child_pointer, child)
+            })
+            .collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, child)| (i.to_string(), format!("{}/{}", pointer, i), child))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Escape a reference token of a JSON pointer (RFC 6901)
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fix(key: &str, field: &str, path: &str, value: Value) -> SuggestedFix {
+        SuggestedFix {
+            key: key.to_owned(),
+            field: field.to_owned(),
+            path: path.to_owned(),
+            value,
+        }
+    }
+
+    #[test]
+    fn fields_below_the_key_are_replaced() {
+        let instantiation = json!({
+            "liquid_pooler_config": {
+                "astroport": { "single_side_lp_limits": { "asset_a_limit": "1" } }
+            }
+        });
+        let fix = fix(
+            "liquid_pooler_config",
+            "single_side_lp_limits_asset_a",
+            "single_side_lp_limits/asset_a_limit",
+            json!("100"),
+        );
+        assert_eq!(
+            json_patch(&instantiation, &[&fix]),
+            vec![json!({
+                "op": "replace",
+                "path": "/liquid_pooler_config/astroport/single_side_lp_limits/asset_a_limit",
+                "value": "100",
+            })]
+        );
+    }
+
+    #[test]
+    fn ambiguous_and_missing_fields_are_skipped() {
+        let instantiation = json!({
+            "party_a_config": { "native_denom": "untrn" },
+            "party_b_config": { "native_denom": "ibc/ABC" },
+        });
+        let ambiguous = fix("splits", "native_denom", "native_denom", json!("x"));
+        let missing = fix(
+            "party_a_config",
+            "fallback_address",
+            "fallback_address",
+            json!("x"),
+        );
+        assert!(json_patch(&instantiation, &[&ambiguous, &missing]).is_empty());
+    }
+
+    #[test]
+    fn pointer_tokens_are_escaped() {
+        let instantiation = json!({ "splits": { "factory/a~b": { "receivers": {} } } });
+        let fix = fix("splits", "receivers", "receivers", json!({}));
+        let patch = json_patch(&instantiation, &[&fix]);
+        assert_eq!(patch[0]["path"], "/splits/factory~1a~0b/receivers");
+    }
+}
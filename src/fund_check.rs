@@ -0,0 +1,123 @@
+use anyhow::Error;
+use chrono::DateTime;
+use serde_json::{json, Value};
+
+use crate::context::CliContext;
+use crate::types::Party;
+use crate::utils::chain::{get_chain_info, get_registry_chains, get_rpc_url};
+use crate::utils::cosmwasm::{get_contract_history, INIT_OPERATION};
+use crate::utils::covenant::get_covenant_child_address;
+
+/// Print what a party has to deposit, where and before when, from the message the
+/// covenant was instantiated with
+pub async fn run(
+    ctx: &CliContext,
+    host_chain_name: &str,
+    covenant_addr: &str,
+    party: Party,
+) -> Result<(), Error> {
+    let history = get_contract_history(ctx, host_chain_name, covenant_addr).await?;
+    let msg = history
+        .into_iter()
+        .find(|entry| entry.operation == INIT_OPERATION)
+        .map(|entry| entry.msg)
+        .ok_or_else(|| anyhow::anyhow!("instantiation not found for {}", covenant_addr))?;
+    let party_config = msg.get(format!("{}_config", party.key())).ok_or_else(|| {
+        anyhow::anyhow!(
+            "no {} config: fund-check supports two-party POL and swap covenants",
+            party.key()
+        )
+    })?;
+
+    let deposit_addr = get_covenant_child_address(
+        ctx,
+        host_chain_name,
+        covenant_addr,
+        "party_deposit_address",
+        json!({ "party": party.key() }),
+    )
+    .await?;
+    // Interchain parties deposit to an ICA on their chain, native parties on the host chain
+    let (config, interchain) = match (party_config.get("interchain"), party_config.get("native")) {
+        (Some(config), _) => (config, true),
+        (None, Some(config)) => (config, false),
+        _ => anyhow::bail!("unknown {} config: {}", party.key(), party_config),
+    };
+    let denom = config["contribution"]["denom"].as_str().unwrap_or_default();
+    let amount = config["contribution"]["amount"]
+        .as_str()
+        .unwrap_or_default();
+    let (chain_name, chain_id, daemon) = deposit_chain(ctx, &deposit_addr).await?;
+    // Swap covenants take deposits until their lockup
+    let deadline = msg
+        .get("deposit_deadline")
+        .or_else(|| msg.get("lockup_config"))
+        .map(format_expiration)
+        .unwrap_or_else(|| "unknown".to_owned());
+
+    println!("Deposit of {} to covenant {}", party.key(), covenant_addr);
+    println!("  chain: {} ({})", chain_name, chain_id);
+    println!("  deposit address: {}", deposit_addr);
+    println!("  denom: {}", denom);
+    if interchain {
+        println!(
+            "         arrives on {} as {} (transfer/{}/{})",
+            host_chain_name,
+            config["native_denom"].as_str().unwrap_or_default(),
+            config["host_to_party_chain_channel_id"]
+                .as_str()
+                .unwrap_or_default(),
+            denom
+        );
+    }
+    println!("  amount: {}", amount);
+    println!("  deadline: {}", deadline);
+    println!("\nTransfer command:");
+    println!(
+        "  {} tx bank send <your-key> {} {}{} --chain-id {} --node {}",
+        daemon,
+        deposit_addr,
+        amount,
+        denom,
+        chain_id,
+        get_rpc_url(&chain_name)
+    );
+    Ok(())
+}
+
+/// Name, chain id and daemon of the chain of an address, from its bech32 prefix
+async fn deposit_chain(ctx: &CliContext, addr: &str) -> Result<(String, String, String), Error> {
+    let (hrp, _) = bech32::decode(addr)?;
+    let chain = get_registry_chains(ctx)
+        .await?
+        .into_iter()
+        .filter(|chain| chain.bech32_prefix == hrp.as_str())
+        // Testnets share the prefix of their mainnet
+        .min_by_key(|chain| chain.network_type != "mainnet")
+        .ok_or_else(|| anyhow::anyhow!("no registry chain with prefix {}", hrp.as_str()))?;
+    let daemon = get_chain_info(ctx, &chain.name)
+        .await
+        .map(|info| info.daemon_name)
+        .unwrap_or_default();
+    let daemon = if daemon.is_empty() {
+        format!("{}d", chain.name)
+    } else {
+        daemon
+    };
+    Ok((chain.name, chain.chain_id, daemon))
+}
+
+fn format_expiration(expiration: &Value) -> String {
+    if let Some(nanos) = expiration["at_time"]
+        .as_str()
+        .and_then(|nanos| nanos.parse::<i64>().ok())
+    {
+        return DateTime::from_timestamp(nanos / 1_000_000_000, 0)
+            .map(|time| time.format("%Y-%m-%dT%H:%MZ").to_string())
+            .unwrap_or_else(|| nanos.to_string());
+    }
+    if let Some(height) = expiration["at_height"].as_u64() {
+        return format!("block {}", height);
+    }
+    expiration.to_string()
+}
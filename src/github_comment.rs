@@ -0,0 +1,132 @@
+use itertools::Itertools;
+use std::collections::HashMap;
+
+use crate::display::ReportDisplay;
+use crate::validations::CovenantValidationContext;
+
+// GitHub rejects comments longer than 65536 characters, keep some room for the bot
+const MAX_COMMENT_LEN: usize = 60_000;
+
+struct Row {
+    field: String,
+    message: String,
+    check_id: String,
+    status: &'static str,
+}
+
+/// Render the validation report as a GitHub comment with a collapsible section per key
+pub fn render_github_comment(ctx: &CovenantValidationContext, display: &ReportDisplay) -> String {
+    let sections = collect_sections(ctx, display);
+    let comment = render_sections(ctx, display, &sections, false);
+    if comment.len() <= MAX_COMMENT_LEN {
+        return comment;
+    }
+
+    // Too long: only keep the warnings and errors
+    let comment = render_sections(ctx, display, &sections, true);
+    if comment.len() <= MAX_COMMENT_LEN {
+        return comment;
+    }
+
+    // Still too long: cut at the last complete line
+    let cut = comment.as_bytes()[..MAX_COMMENT_LEN]
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .unwrap_or_default();
+    format!(
+        "{}\n\n</details>\n\n> Report truncated to {} characters, run `covenant-cli validate` for the full report.\n",
+        &comment[..cut],
+        MAX_COMMENT_LEN
+    )
+}
+
+fn collect_sections<'a>(
+    ctx: &'a CovenantValidationContext,
+    display: &ReportDisplay,
+) -> Vec<(&'a str, Vec<Row>)> {
+    let mut sections: HashMap<&str, Vec<Row>> = HashMap::new();
+    for (entries, status) in [
+        (ctx.checks(), "✅"),
+        (ctx.warnings(), "⚠️"),
+        (ctx.errors(), "⛔️"),
+    ] {
+        for (key, messages) in entries {
+            for message in messages {
+                let (field, message) = message.split_once(": ").unwrap_or(("", message));
+                sections.entry(*key).or_default().push(Row {
+                    field: field.to_owned(),
+                    message: display.message(message).replace('|', "&#124;"),
+                    check_id: ctx.check_id(key, field).unwrap_or_default(),
+                    status,
+                });
+            }
+        }
+    }
+    sections
+        .into_iter()
+        .sorted_by_key(|(key, _)| *key)
+        .collect()
+}
+
+fn render_sections(
+    ctx: &CovenantValidationContext,
+    display: &ReportDisplay,
+    sections: &[(&str, Vec<Row>)],
+    findings_only: bool,
+) -> String {
+    let count = |status: &str| {
+        sections
+            .iter()
+            .flat_map(|(_, rows)| rows)
+            .filter(|row| row.status == status)
+            .count()
+    };
+    let mut comment = format!(
+        "### {} `{}` ({})\n\nMessage SHA-256 `{}`\n\n✅ {} passed · ⚠️ {} warnings · ⛔️ {} errors\n\n",
+        if ctx.has_errors() { "⛔️" } else { "✅" },
+        ctx.covenant_label(),
+        ctx.covenant_contract(),
+        ctx.message_hash(),
+        count("✅"),
+        count("⚠️"),
+        count("⛔️"),
+    );
+
+    let mut omitted = 0;
+    for (key, rows) in sections {
+        let (shown, hidden): (Vec<&Row>, Vec<&Row>) = rows
+            .iter()
+            .partition(|row| !findings_only || row.status != "✅");
+        omitted += hidden.len();
+        if shown.is_empty() {
+            continue;
+        }
+        let status = ["⛔️", "⚠️"]
+            .into_iter()
+            .find(|status| rows.iter().any(|row| row.status == *status))
+            .unwrap_or("✅");
+        // Sections with errors are expanded
+        comment.push_str(&format!(
+            "<details{}><summary>{} <code>{}</code> ({})</summary>\n\n",
+            if status == "⛔️" { " open" } else { "" },
+            status,
+            display.key(key),
+            rows.len()
+        ));
+        comment.push_str("| Field | Message | Check | Status |\n| :--- | :--- | :--- | :---: |\n");
+        for row in shown {
+            comment.push_str(&format!(
+                "| {} | {} | {} | {} |\n",
+                row.field, row.message, row.check_id, row.status
+            ));
+        }
+        comment.push_str("\n</details>\n\n");
+    }
+    if omitted > 0 {
+        comment.push_str(&format!(
+            "> {} passing checks omitted to stay under the comment size limit.\n",
+            omitted
+        ));
+    }
+    comment
+}
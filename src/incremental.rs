@@ -0,0 +1,239 @@
+use anyhow::{Context, Error};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::acceptances::ACCEPT_FILE_NAME;
+use crate::allowlist::ALLOWLIST_FILE_NAME;
+use crate::canonical::message_hash;
+use crate::checks::section_key;
+use crate::validations::{CovenantValidationContext, SuggestedFix};
+
+/// Name of the incremental validation cache, written next to the metadata file
+pub const CACHE_FILE_NAME: &str = ".covenant-cache.json";
+// Pool prices and balances move, cached results older than this are validated again
+const MAX_AGE_SECS: i64 = 10 * 60;
+
+// Message members each report section reads, the other sections depend on the whole message
+const SECTION_MEMBERS: &[(&str, &[&str])] = &[
+    ("contract_codes", &["contract_codes"]),
+    (
+        "fees",
+        &[
+            "timeouts",
+            "preset_ibc_fee",
+            "deposit_deadline",
+            "lockup_config",
+            "lockup_period",
+        ],
+    ),
+    ("covenant_party_config", &["covenant_party_config"]),
+    ("party_a_config", &["party_a_config"]),
+    ("party_b_config", &["party_b_config"]),
+    (
+        "ls_forwarder_config",
+        &[
+            "ls_forwarder_config",
+            "covenant_party_config",
+            "remote_chain_splitter_config",
+        ],
+    ),
+    (
+        "lp_forwarder_config",
+        &[
+            "lp_forwarder_config",
+            "covenant_party_config",
+            "remote_chain_splitter_config",
+        ],
+    ),
+    ("ls_info", &["ls_info", "covenant_party_config"]),
+    (
+        "remote_chain_splitter_config",
+        &["remote_chain_splitter_config", "covenant_party_config"],
+    ),
+    ("pool_migration", &["pool_migration"]),
+];
+
+/// Results of a report section and the hash of the message members they were computed from
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CachedSection {
+    pub hash: String,
+    pub created_at: i64,
+    pub checks: Vec<String>,
+    pub warnings: Vec<String>,
+    pub errors: Vec<String>,
+    pub fixes: Vec<SuggestedFix>,
+    pub checked_fields: Vec<String>,
+}
+
+/// Per-section results of the last run, valid for the same data sources snapshot
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IncrementalCache {
+    snapshot: String,
+    sections: BTreeMap<String, CachedSection>,
+}
+
+impl IncrementalCache {
+    pub fn path(metadata_file: &str) -> PathBuf {
+        Path::new(metadata_file)
+            .parent()
+            .unwrap_or(Path::new("."))
+            .join(CACHE_FILE_NAME)
+    }
+
+    /// Cache of the last run, empty when missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Sections whose message members did not change since the cached run of the same
+    /// snapshot, keyed by their static report key
+    pub fn reusable(
+        &self,
+        snapshot: &str,
+        instantiation: &Value,
+    ) -> Vec<(&'static str, &CachedSection)> {
+        if self.snapshot != snapshot {
+            return vec![];
+        }
+        let now = Utc::now().timestamp();
+        self.sections
+            .iter()
+            .filter(|(key, section)| {
+                section.hash == section_hash(key, instantiation)
+                    && now - section.created_at < MAX_AGE_SECS
+            })
+            .filter_map(|(key, section)| Some((section_key(key)?, section)))
+            .collect()
+    }
+
+    /// Whether every cached section is reusable, the validation can then be skipped
+    pub fn is_complete(&self, reusable: &[(&'static str, &CachedSection)]) -> bool {
+        !self.sections.is_empty() && reusable.len() == self.sections.len()
+    }
+
+    /// Save the results of the run, the reused sections keep their creation time
+    pub fn save(
+        &self,
+        path: &Path,
+        snapshot: &str,
+        ctx: &CovenantValidationContext,
+        instantiation: &Value,
+    ) -> Result<(), Error> {
+        let now = Utc::now().timestamp();
+        let mut sections = BTreeMap::<String, CachedSection>::new();
+        let messages_of: [fn(&mut CachedSection) -> &mut Vec<String>; 3] = [
+            |section| &mut section.checks,
+            |section| &mut section.warnings,
+            |section| &mut section.errors,
+        ];
+        for (entries, messages_of) in [ctx.checks(), ctx.warnings(), ctx.errors()]
+            .into_iter()
+            .zip(messages_of)
+        {
+            for (key, messages) in entries {
+                let section = sections.entry(key.to_string()).or_insert_with(|| {
+                    let created_at = match self.sections.get(*key) {
+                        Some(cached) if ctx.reused_keys().contains(key) => cached.created_at,
+                        _ => now,
+                    };
+                    CachedSection {
+                        hash: section_hash(key, instantiation),
+                        created_at,
+                        ..Default::default()
+                    }
+                });
+                *messages_of(section) = messages.clone();
+            }
+        }
+        for fix in ctx.fixes() {
+            if let Some(section) = sections.get_mut(&fix.key) {
+                section.fixes.push(fix.clone());
+            }
+        }
+        // Reused sections keep the fields checked by the run they were cached from
+        for (key, section) in sections.iter_mut() {
+            let mut checked_fields = match self.sections.get(key) {
+                Some(cached) if ctx.reused_keys().contains(&key.as_str()) => {
+                    cached.checked_fields.clone()
+                }
+                _ => vec![],
+            };
+            for field in ctx.checked_fields_of(key) {
+                if !checked_fields.iter().any(|f| f == field) {
+                    checked_fields.push(field.to_owned());
+                }
+            }
+            section.checked_fields = checked_fields;
+        }
+        let cache = IncrementalCache {
+            snapshot: snapshot.to_owned(),
+            sections,
+        };
+        std::fs::write(path, serde_json::to_string_pretty(&cache)?)
+            .with_context(|| format!("failed writing {}", path.display()))
+    }
+}
+
+/// Hash of the inputs the results depend on: tool, chain registry commit, metadata, contents
+/// of the files read next to it, validation settings and remote data sources; `None` when
+/// the registry commit is unknown or a local registry checkout is read
+pub fn snapshot(
+    ctx: &CovenantValidationContext,
+    metadata: &toml::Table,
+    metadata_file: &str,
+) -> Option<String> {
+    let cli_context = ctx.cli_context();
+    if cli_context.local_registry().is_some() {
+        return None;
+    }
+    let metadata_dir = Path::new(metadata_file).parent().unwrap_or(Path::new("."));
+    let allowlist_file = match ctx.allowlist_file() {
+        Some(allowlist_file) => PathBuf::from(allowlist_file),
+        None => metadata_dir.join(ALLOWLIST_FILE_NAME),
+    };
+    let snapshot = serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "registry_commit": ctx.registry_commit()?,
+        "metadata": serde_json::to_value(metadata).ok()?,
+        "code_ids_file": ctx.code_ids_file().and_then(|file| file_digest(Path::new(&file))),
+        "allowlist_file": file_digest(&allowlist_file),
+        "accept_file": file_digest(&metadata_dir.join(ACCEPT_FILE_NAME)),
+        "plugins": ctx.plugins(),
+        "skipped_keys": ctx.skipped_keys(),
+        "verify_onchain": ctx.verify_onchain(),
+        "data_sources": cli_context.data_sources(),
+    });
+    Some(message_hash(&snapshot))
+}
+
+/// SHA-256 of a file content, `None` when the file is missing
+fn file_digest(path: &Path) -> Option<String> {
+    let content = std::fs::read(path).ok()?;
+    Some(base16ct::lower::encode_string(&Sha256::digest(content)))
+}
+
+/// Hash of the message members a section depends on
+fn section_hash(key: &str, instantiation: &Value) -> String {
+    match SECTION_MEMBERS.iter().find(|(k, _)| *k == key) {
+        Some((_, members)) => {
+            let subtree = members
+                .iter()
+                .map(|member| {
+                    let value = instantiation.get(member).cloned().unwrap_or_default();
+                    (member.to_string(), value)
+                })
+                .collect::<Map<_, _>>();
+            message_hash(&Value::Object(subtree))
+        }
+        None => message_hash(instantiation),
+    }
+}
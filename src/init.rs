@@ -0,0 +1,167 @@
+use anyhow::{Context, Error};
+use itertools::Itertools;
+use log::{info, warn};
+use std::io::{BufRead, Write};
+use toml::{Table, Value};
+
+use crate::context::CliContext;
+use crate::utils::chain::get_chain_names;
+use crate::validations::NEUTRON_CHAIN_NAME;
+
+const CONTRACTS: &[&str] = &[
+    "valence-covenant-single-party-pol",
+    "valence-covenant-two-party-pol",
+    "valence-covenant-swap",
+];
+const LS_PROVIDERS: &[&str] = &["stride", "persistence"];
+const DEFAULT_SINGLE_SIDE_LP_LIMIT_PCT: i64 = 10;
+/// Maximum number of completion candidates listed for a partial chain name
+const MAX_SUGGESTIONS: usize = 10;
+
+/// Interactively build a metadata file and write it to `output_file`
+pub async fn run(ctx: &CliContext, output_file: &str) -> Result<(), Error> {
+    let chain_names = get_chain_names(ctx).await.unwrap_or_else(|e| {
+        warn!("Chain names autocompletion unavailable: {}", e);
+        vec![]
+    });
+    let mut prompt = Prompt::new(std::io::stdin().lock(), chain_names);
+
+    let mut covenant = Table::new();
+    let contract = prompt.choice("Covenant contract", CONTRACTS)?;
+    covenant.insert("contract".to_owned(), contract.clone().into());
+
+    let host_chain_name = prompt.chain_name("Host chain name", Some(NEUTRON_CHAIN_NAME))?;
+    if host_chain_name != NEUTRON_CHAIN_NAME {
+        covenant.insert("host_chain_name".to_owned(), host_chain_name.into());
+    }
+    let party_a_chain_name = prompt.chain_name("Party A chain name", None)?;
+    covenant.insert("party_a_chain_name".to_owned(), party_a_chain_name.into());
+    if contract != "valence-covenant-single-party-pol" {
+        let party_b_chain_name = prompt.chain_name("Party B chain name", None)?;
+        covenant.insert("party_b_chain_name".to_owned(), party_b_chain_name.into());
+    }
+    if prompt.confirm("Does the party A channel use a wasm port?")? {
+        covenant.insert("party_a_channel_uses_wasm_port".to_owned(), true.into());
+    }
+
+    if contract == "valence-covenant-single-party-pol" {
+        let ls_provider = prompt.choice("Liquid staking provider", LS_PROVIDERS)?;
+        covenant.insert("ls_provider".to_owned(), ls_provider.into());
+    }
+    if contract != "valence-covenant-swap" {
+        let pct = prompt.integer(
+            "Single side LP limit (% of the pool)",
+            DEFAULT_SINGLE_SIDE_LP_LIMIT_PCT,
+        )?;
+        covenant.insert("single_side_lp_limit_pct".to_owned(), pct.into());
+    }
+
+    let mut metadata = Table::new();
+    metadata.insert("covenant".to_owned(), Value::Table(covenant));
+    std::fs::write(output_file, toml::to_string(&metadata)?)
+        .with_context(|| format!("failed writing {}", output_file))?;
+    info!("Wrote {}", output_file);
+    Ok(())
+}
+
+struct Prompt<R> {
+    input: R,
+    chain_names: Vec<String>,
+}
+
+impl<R: BufRead> Prompt<R> {
+    fn new(input: R, chain_names: Vec<String>) -> Self {
+        Prompt { input, chain_names }
+    }
+
+    fn ask(&mut self, question: &str, default: Option<&str>) -> Result<String, Error> {
+        match default {
+            Some(default) => print!("{} [{}]: ", question, default),
+            None => print!("{}: ", question),
+        }
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            anyhow::bail!("input closed");
+        }
+        let answer = line.trim();
+        Ok(match default {
+            Some(default) if answer.is_empty() => default.to_owned(),
+            _ => answer.to_owned(),
+        })
+    }
+
+    fn choice(&mut self, question: &str, options: &[&str]) -> Result<String, Error> {
+        for (i, option) in options.iter().enumerate() {
+            println!("  {}) {}", i + 1, option);
+        }
+        loop {
+            let answer = self.ask(question, Some("1"))?;
+            let selected = match answer.parse::<usize>() {
+                Ok(i) => options.get(i.wrapping_sub(1)),
+                Err(_) => options.iter().find(|option| **option == answer),
+            };
+            match selected {
+                Some(option) => return Ok(option.to_string()),
+                None => println!("Invalid choice {}", answer),
+            }
+        }
+    }
+
+    /// Ask for a chain name, completing a unique prefix from the chain registry
+    fn chain_name(&mut self, question: &str, default: Option<&str>) -> Result<String, Error> {
+        loop {
+            let answer = self.ask(question, default)?;
+            if answer.is_empty() {
+                continue;
+            }
+            if self.chain_names.is_empty() || self.chain_names.contains(&answer) {
+                return Ok(answer);
+            }
+            let matches = self
+                .chain_names
+                .iter()
+                .filter(|name| name.starts_with(&answer))
+                .collect_vec();
+            match matches.as_slice() {
+                [name] => {
+                    println!("  -> {}", name);
+                    return Ok(name.to_string());
+                }
+                [] => println!("Unknown chain {}", answer),
+                _ => println!(
+                    "Matching chains: {}{}",
+                    matches.iter().take(MAX_SUGGESTIONS).join(", "),
+                    if matches.len() > MAX_SUGGESTIONS {
+                        ", ..."
+                    } else {
+                        ""
+                    }
+                ),
+            }
+        }
+    }
+
+    fn confirm(&mut self, question: &str) -> Result<bool, Error> {
+        loop {
+            match self
+                .ask(&format!("{} (y/n)", question), Some("n"))?
+                .as_str()
+            {
+                "y" | "yes" => return Ok(true),
+                "n" | "no" => return Ok(false),
+                answer => println!("Invalid answer {}", answer),
+            }
+        }
+    }
+
+    fn integer(&mut self, question: &str, default: i64) -> Result<i64, Error> {
+        loop {
+            let answer = self.ask(question, Some(&default.to_string()))?;
+            match answer.parse() {
+                Ok(value) => return Ok(value),
+                Err(_) => println!("Invalid number {}", answer),
+            }
+        }
+    }
+}
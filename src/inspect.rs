@@ -0,0 +1,93 @@
+use anyhow::Error;
+use serde_json::Value;
+
+use crate::context::CliContext;
+use crate::utils::cosmwasm::{get_contract_info, ContractInfo};
+use crate::utils::covenant::{get_contract_config, get_covenant_children, get_holder_state};
+
+/// Print the contract tree of a deployed covenant
+pub async fn run(
+    ctx: &CliContext,
+    host_chain_name: &str,
+    covenant_addr: &str,
+) -> Result<(), Error> {
+    let covenant_info = get_contract_info(ctx, host_chain_name, covenant_addr).await?;
+    println!("covenant {}", describe(covenant_addr, &covenant_info));
+
+    let children = get_covenant_children(ctx, host_chain_name, covenant_addr).await;
+
+    for (i, (name, addr)) in children.iter().enumerate() {
+        let branch = if i + 1 == children.len() {
+            "└──"
+        } else {
+            "├──"
+        };
+        let indent = if i + 1 == children.len() {
+            "    "
+        } else {
+            "│   "
+        };
+        match get_contract_info(ctx, host_chain_name, addr).await {
+            Ok(info) => println!("{} {} {}", branch, name, describe(addr, &info)),
+            Err(e) => println!(
+                "{} {} {} (failed fetching contract info: {})",
+                branch, name, addr, e
+            ),
+        }
+        // Most covenant contracts expose their state with the same query as the holder
+        if let Ok(state) = get_holder_state(ctx, host_chain_name, addr).await {
+            println!("{}├── state: {}", indent, state);
+        }
+        match get_contract_config(ctx, host_chain_name, addr).await {
+            Ok(Value::Object(config)) => {
+                println!("{}└── config", indent);
+                for (j, (field, value)) in config.iter().enumerate() {
+                    let branch = if j + 1 == config.len() {
+                        "└──"
+                    } else {
+                        "├──"
+                    };
+                    println!(
+                        "{}    {} {}: {}",
+                        indent,
+                        branch,
+                        field,
+                        describe_value(value)
+                    );
+                }
+            }
+            Ok(config) => println!("{}└── config: {}", indent, describe_value(&config)),
+            Err(e) => println!("{}└── config: failed querying the config ({})", indent, e),
+        }
+    }
+    Ok(())
+}
+
+fn describe(addr: &str, info: &ContractInfo) -> String {
+    format!(
+        "{} (code {}, label \"{}\", admin {})",
+        addr,
+        info.code_id,
+        info.label,
+        if info.admin.is_empty() {
+            "none"
+        } else {
+            &info.admin
+        }
+    )
+}
+
+// Nested config values (e.g. split or pool configs) are shortened to a single line
+const MAX_VALUE_LEN: usize = 120;
+
+fn describe_value(value: &Value) -> String {
+    let value = match value {
+        Value::String(s) => s.clone(),
+        value => value.to_string(),
+    };
+    if value.chars().count() > MAX_VALUE_LEN {
+        format!("{}…", value.chars().take(MAX_VALUE_LEN).collect::<String>())
+    } else {
+        value
+    }
+}
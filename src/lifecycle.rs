@@ -0,0 +1,135 @@
+use serde_json::json;
+use std::fmt;
+use std::time::Duration;
+
+use crate::utils::chain::get_balances;
+use crate::utils::covenant::{get_covenant_child_address, get_holder_state};
+use crate::validations::CovenantValidationContext;
+
+/// Lifecycle phases of a covenant, in order
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LifecyclePhase {
+    /// Instantiated, no funds deposited yet
+    Instantiated,
+    /// Funds deposited, waiting for the other deposits
+    Deposits,
+    /// Deposits forwarded, liquid staking and liquidity provision in progress
+    Executed,
+    /// Liquidity provided, lockup running
+    Active,
+    /// Lockup expired or ragequit, funds to be withdrawn
+    Expired,
+    /// Funds distributed to the parties
+    Distributed,
+}
+
+impl LifecyclePhase {
+    pub const ALL: [LifecyclePhase; 6] = [
+        LifecyclePhase::Instantiated,
+        LifecyclePhase::Deposits,
+        LifecyclePhase::Executed,
+        LifecyclePhase::Active,
+        LifecyclePhase::Expired,
+        LifecyclePhase::Distributed,
+    ];
+
+    /// Map the holder state and whether the holder holds funds to a phase
+    pub fn from_holder_state(state: &str, holds_funds: bool) -> Option<Self> {
+        match (state, holds_funds) {
+            ("instantiated", false) => Some(LifecyclePhase::Instantiated),
+            ("instantiated", true) => Some(LifecyclePhase::Deposits),
+            ("active", true) => Some(LifecyclePhase::Executed),
+            ("active", false) => Some(LifecyclePhase::Active),
+            ("expired" | "ragequit", _) => Some(LifecyclePhase::Expired),
+            ("complete", _) => Some(LifecyclePhase::Distributed),
+            _ => None,
+        }
+    }
+
+    /// Phases only move forward, several phases may be crossed between two polls
+    pub fn can_transition_to(self, next: LifecyclePhase) -> bool {
+        next >= self
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LifecyclePhase::Instantiated => "instantiated",
+            LifecyclePhase::Deposits => "deposits",
+            LifecyclePhase::Executed => "executed",
+            LifecyclePhase::Active => "active",
+            LifecyclePhase::Expired => "expired",
+            LifecyclePhase::Distributed => "distributed",
+        }
+    }
+}
+
+impl fmt::Display for LifecyclePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Maximum time a covenant is expected to stay in a phase
+#[derive(Clone, Debug, Default)]
+pub struct StuckThresholds(Vec<(LifecyclePhase, Duration)>);
+
+impl StuckThresholds {
+    pub fn new(thresholds: &[(String, String)]) -> Result<Self, anyhow::Error> {
+        thresholds
+            .iter()
+            .map(|(phase, seconds)| {
+                let phase = LifecyclePhase::ALL
+                    .into_iter()
+                    .find(|p| p.name() == phase)
+                    .ok_or_else(|| anyhow::anyhow!("unknown lifecycle phase {}", phase))?;
+                let seconds = seconds
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!("invalid threshold {} for {}", seconds, phase))?;
+                Ok((phase, Duration::from_secs(seconds)))
+            })
+            .collect::<Result<_, _>>()
+            .map(StuckThresholds)
+    }
+
+    pub fn get(&self, phase: LifecyclePhase) -> Option<Duration> {
+        self.0.iter().find(|(p, _)| *p == phase).map(|(_, d)| *d)
+    }
+}
+
+/// Record the lifecycle phase of the deployed covenant (deployed-verification mode)
+pub async fn verify_deployed_phase(ctx: &mut CovenantValidationContext<'_>, covenant_addr: &str) {
+    let key = "deployment";
+    let host_chain_name = ctx.host_chain_name();
+    let cli_context = ctx.cli_context().clone();
+    let holder_addr = match get_covenant_child_address(
+        &cli_context,
+        &host_chain_name,
+        covenant_addr,
+        "holder_address",
+        json!({}),
+    )
+    .await
+    {
+        Ok(addr) => addr,
+        Err(e) => {
+            ctx.invalid(key, format!("failed to resolve the holder: {}", e));
+            return;
+        }
+    };
+    let state = get_holder_state(&cli_context, &host_chain_name, &holder_addr).await;
+    let balances = get_balances(&cli_context, &host_chain_name, &holder_addr).await;
+    match (state, balances) {
+        (Ok(state), Ok(balances)) => {
+            match LifecyclePhase::from_holder_state(&state, !balances.is_empty()) {
+                Some(phase) if phase >= LifecyclePhase::Expired => {
+                    ctx.warn(key, format!("lifecycle phase: {} (past its lockup)", phase))
+                }
+                Some(phase) => ctx.valid(key, format!("lifecycle phase: {}", phase)),
+                None => ctx.invalid(key, format!("unknown holder state {}", state)),
+            }
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            ctx.invalid(key, format!("failed to query the holder state: {}", e))
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use anyhow::Error;
+
+use crate::commands::SUPPORTED_COVENANT_CONTRACTS;
+use crate::context::CliContext;
+use crate::utils::chain::get_registry_chains;
+use crate::validations::resolve_chain_name;
+
+/// Value type of a `[covenant]` metadata key
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum MetadataType {
+    String,
+    Boolean,
+    Integer,
+    /// One of the listed strings
+    OneOf(&'static [&'static str]),
+    /// `[min, max]` percents
+    PctBounds,
+    /// Table of denom amounts
+    DenomAmounts,
+}
+
+// Keys of the `[covenant]` metadata table
+pub(crate) const METADATA_KEYS: &[(&str, MetadataType)] = &[
+    (
+        "contract",
+        MetadataType::OneOf(SUPPORTED_COVENANT_CONTRACTS),
+    ),
+    ("contract_codes_version", MetadataType::String),
+    ("contract_codes_sha256", MetadataType::String),
+    ("contract_codes_file", MetadataType::String),
+    ("contract_codes_registry", MetadataType::String),
+    ("host_chain_name", MetadataType::String),
+    ("party_a_chain_name", MetadataType::String),
+    ("party_b_chain_name", MetadataType::String),
+    ("party_a_name", MetadataType::String),
+    ("party_b_name", MetadataType::String),
+    ("party_a_channel_uses_wasm_port", MetadataType::Boolean),
+    ("party_a_outpost_contract", MetadataType::String),
+    ("party_b_outpost_contract", MetadataType::String),
+    ("withdrawal_address", MetadataType::String),
+    ("ls_provider", MetadataType::OneOf(LS_PROVIDERS)),
+    ("single_side_lp_limit_pct", MetadataType::Integer),
+    ("single_side_lp_limit_pct_asset_a", MetadataType::Integer),
+    ("single_side_lp_limit_pct_asset_b", MetadataType::Integer),
+    (
+        "single_side_lp_limit_rounding",
+        MetadataType::OneOf(&["away_from_zero", "floor", "ceil", "nearest"]),
+    ),
+    ("single_side_lp_limit_tolerance", MetadataType::Integer),
+    ("contribution_parity_tolerance_pct", MetadataType::Integer),
+    ("astroport_factory_address", MetadataType::String),
+    ("admin_address", MetadataType::String),
+    ("label_pattern", MetadataType::String),
+    ("slippage_tolerance_bounds_pct", MetadataType::PctBounds),
+    ("max_pool_share_pct", MetadataType::Integer),
+    ("min_contributions", MetadataType::DenomAmounts),
+    ("expected_execution_date", MetadataType::String),
+    ("execution_deadline_buffer_hours", MetadataType::Integer),
+];
+
+const LS_PROVIDERS: &[&str] = &["stride", "persistence"];
+
+/// Check the keys and values of a metadata file, without querying anything but the registry
+pub async fn run(ctx: &CliContext, metadata: &toml::Value) -> Result<(), Error> {
+    let mut errors = 0;
+    let mut report = |result: Result<String, String>| match result {
+        Ok(message) => println!("  ✅ {}", message),
+        Err(message) => {
+            errors += 1;
+            println!("  ⛔️ {}", message);
+        }
+    };
+
+    let Some(covenant_metadata) = metadata.get("covenant").and_then(|c| c.as_table()) else {
+        anyhow::bail!("missing [covenant] table");
+    };
+    for table in metadata.as_table().into_iter().flat_map(|t| t.keys()) {
+        if table != "covenant" {
+            println!("  ⚠️ unknown table [{}]", table);
+        }
+    }
+    for key in covenant_metadata.keys() {
+        if !METADATA_KEYS.iter().any(|(k, _)| k == key) {
+            report(Err(format!("unknown key {}", key)));
+        }
+    }
+
+    let contract = covenant_metadata.get("contract").and_then(|c| c.as_str());
+    report(match contract {
+        Some(contract) if SUPPORTED_COVENANT_CONTRACTS.contains(&contract) => {
+            Ok(format!("contract: {}", contract))
+        }
+        Some(contract) => Err(format!(
+            "contract: unsupported covenant contract {}",
+            contract
+        )),
+        None => Ok("contract: not declared, detected from the instantiation message".to_owned()),
+    });
+
+    let mut chain_keys = vec!["host_chain_name", "party_a_chain_name"];
+    if contract.map_or(covenant_metadata.contains_key("party_b_chain_name"), |c| {
+        c != "valence-covenant-single-party-pol"
+    }) {
+        chain_keys.push("party_b_chain_name");
+    }
+    // Registry lookups are skipped when the registry is unreachable (e.g. plan mode)
+    let chains = get_registry_chains(ctx).await.unwrap_or_default();
+    for key in chain_keys {
+        let name = covenant_metadata.get(key).and_then(|n| n.as_str());
+        report(match (name, key) {
+            (None, "host_chain_name") => Ok(format!("{}: neutron (default)", key)),
+            (None, _) => Err(format!("{}: missing", key)),
+            (Some(name), _) if chains.is_empty() => {
+                Ok(format!("{}: {} (not looked up)", key, name))
+            }
+            (Some(name), _) => match resolve_chain_name(&chains, name) {
+                Some(resolved) if resolved == name => Ok(format!("{}: {}", key, name)),
+                Some(resolved) => Ok(format!("{}: {} resolves to {}", key, name, resolved)),
+                None => Err(format!("{}: {} not found in the chain registry", key, name)),
+            },
+        });
+    }
+
+    for key in [
+        "single_side_lp_limit_pct",
+        "single_side_lp_limit_pct_asset_a",
+        "single_side_lp_limit_pct_asset_b",
+    ] {
+        let Some(setting) = covenant_metadata.get(key) else {
+            continue;
+        };
+        report(match setting.as_integer() {
+            Some(pct) if (0..=100).contains(&pct) => Ok(format!("{}: {}%", key, pct)),
+            _ => Err(format!(
+                "{}: {} should be an integer between 0 and 100",
+                key, setting
+            )),
+        });
+    }
+
+    if let Some(setting) = covenant_metadata.get("ls_provider") {
+        report(match setting.as_str() {
+            Some(provider) if LS_PROVIDERS.contains(&provider) => {
+                Ok(format!("ls_provider: {}", provider))
+            }
+            _ => Err(format!(
+                "ls_provider: {} should be one of {}",
+                setting,
+                LS_PROVIDERS.join(", ")
+            )),
+        });
+    }
+
+    if errors > 0 {
+        anyhow::bail!("{} metadata errors", errors);
+    }
+    Ok(())
+}
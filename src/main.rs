@@ -2,20 +2,100 @@ use anyhow::Error;
 use clap::Parser;
 use context::CliContext;
 use dotenv::dotenv;
+use std::io::IsTerminal;
 use types::*;
 
+mod acceptances;
+mod allowlist;
+mod baseline;
+mod batch_index;
+mod bundle;
+mod canonical;
+mod checks;
 mod commands;
+mod compare;
+mod config;
 mod context;
+mod csv_report;
+mod diagnostics;
+mod display;
+mod doctor;
+mod error;
+mod fixes;
+mod fund_check;
+mod github_comment;
+mod incremental;
+mod init;
+mod inspect;
+mod lifecycle;
+mod lint;
+#[cfg(feature = "mock-server")]
+mod mock_server;
+mod notify;
+mod plugins;
+mod proposal;
+mod review;
+mod saved_result;
+mod schema;
+mod templates;
+mod terminal_report;
 mod types;
 mod utils;
 mod validations;
+mod watch;
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
     dotenv().ok();
-    pretty_env_logger::init();
 
-    let ctx = CliContext::init().await?;
     let cli = Cli::parse();
+    // Configuration file settings apply when neither a flag nor the environment sets them
+    let config = match &cli.config {
+        Some(config_file) => config::CliConfig::load(config_file)?,
+        None => Default::default(),
+    };
+    if cli.quiet {
+        pretty_env_logger::formatted_builder()
+            .filter_level(log::LevelFilter::Error)
+            .init();
+    } else {
+        pretty_env_logger::init();
+    }
+    let ctx = CliContext::init(cli.plan).await?;
+    let ctx = match cli.max_concurrency.or(config.max_concurrency) {
+        Some(max_concurrency) => ctx.with_max_concurrency(max_concurrency),
+        None => ctx,
+    };
+    let ctx = match cli.request_interval_ms.or(config.request_interval_ms) {
+        Some(interval_ms) => {
+            ctx.with_request_interval(std::time::Duration::from_millis(interval_ms))
+        }
+        None => ctx,
+    };
+    let ctx = match cli
+        .notify_webhook
+        .as_ref()
+        .or(config.notify_webhook.as_ref())
+    {
+        Some(webhook_url) => ctx.with_notify_webhook(webhook_url.clone()),
+        None => ctx,
+    };
+    let ctx = match cli.mock_server.as_ref().or(config.mock_server.as_ref()) {
+        Some(base_url) => ctx.with_mock_server(base_url.clone()),
+        None => ctx,
+    };
+    let ctx = match cli
+        .chain_registry_local_first
+        .as_ref()
+        .or(config.chain_registry_local_first.as_ref())
+    {
+        Some(dir) => ctx.with_local_registry(dir.into()),
+        None => ctx,
+    };
+    let color = !cli.no_color
+        && !config.no_color
+        && std::env::var_os("NO_COLOR").is_none()
+        && std::io::stdout().is_terminal();
+    let ctx = ctx.with_color(color).with_config(config);
     commands::execute_cmd(&ctx, &cli.command).await
 }
@@ -0,0 +1,80 @@
+use anyhow::{Context, Error};
+use log::{info, warn};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::bundle::read_bundle;
+use crate::context::RecordedResponse;
+
+/// Serve the responses of the bundles of a fixtures directory, keyed by their original URL
+/// (`GET /https://rest.cosmos.directory/...`), see `--mock-server`
+pub async fn run(fixtures_dir: &str, listen_addr: &str) -> Result<(), Error> {
+    let responses = Arc::new(load_fixtures(Path::new(fixtures_dir))?);
+    let listener = TcpListener::bind(listen_addr).await?;
+    info!(
+        "Serving {} recorded responses on http://{}",
+        responses.len(),
+        listen_addr
+    );
+    loop {
+        let Ok((mut socket, _)) = listener.accept().await else {
+            continue;
+        };
+        let responses = responses.clone();
+        tokio::spawn(async move {
+            let mut request = [0u8; 8192];
+            let Ok(read) = socket.read(&mut request).await else {
+                return;
+            };
+            let request = String::from_utf8_lossy(&request[..read]);
+            let url = request
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or_default()
+                .trim_start_matches('/');
+            let (status, body) = match responses.get(url) {
+                Some(response) => {
+                    info!("{} {}", response.status, url);
+                    (response.status, response.body.as_str())
+                }
+                None => {
+                    warn!("404 {} (not in the fixtures)", url);
+                    (404, "")
+                }
+            };
+            let reason = reqwest::StatusCode::from_u16(status)
+                .ok()
+                .and_then(|status| status.canonical_reason())
+                .unwrap_or_default();
+            let response = format!(
+                "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                reason,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+/// Merge the responses (and recorded statuses) of the bundle archives (`bundle create`) of
+/// the directory
+fn load_fixtures(fixtures_dir: &Path) -> Result<BTreeMap<String, RecordedResponse>, Error> {
+    let mut responses = BTreeMap::new();
+    for entry in std::fs::read_dir(fixtures_dir)
+        .with_context(|| format!("failed reading {}", fixtures_dir.display()))?
+    {
+        let path = entry?.path();
+        if !path.to_string_lossy().ends_with(".tar.gz") {
+            continue;
+        }
+        let bundle = read_bundle(&path)
+            .with_context(|| format!("failed loading fixture {}", path.display()))?;
+        responses.extend(bundle.responses);
+    }
+    Ok(responses)
+}
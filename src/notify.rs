@@ -0,0 +1,56 @@
+use log::warn;
+use serde_json::json;
+use std::path::Path;
+
+use crate::batch_index::BatchEntry;
+use crate::context::CliContext;
+use crate::validations::CovenantValidationContext;
+
+/// Post a message to the notification webhook, if any. Failures are only logged.
+pub async fn notify(ctx: &CliContext, message: &str) {
+    let Some(webhook_url) = ctx.notify_webhook() else {
+        return;
+    };
+    // Slack reads `text` and Discord reads `content`
+    let body = json!({ "text": message, "content": message });
+    if let Err(e) = ctx.api_post(webhook_url, &body).await {
+        warn!("failed notifying webhook: {}", e);
+    }
+}
+
+/// Compact summary of a validation for notifications
+pub fn validation_summary(ctx: &CovenantValidationContext) -> String {
+    let count = |entries: &std::collections::HashMap<&str, Vec<String>>| {
+        entries.values().map(Vec::len).sum::<usize>()
+    };
+    format!(
+        "{} Covenant `{}` ({}): {} passed, {} warnings, {} errors",
+        if ctx.has_errors() { "⛔️" } else { "✅" },
+        ctx.covenant_label(),
+        ctx.covenant_contract(),
+        count(ctx.checks()),
+        count(ctx.warnings()),
+        count(ctx.errors())
+    )
+}
+
+/// Compact summary of a batch validation for notifications, failed covenants listed
+pub fn batch_summary(entries: &[BatchEntry], output_dir: &Path) -> String {
+    let failed = entries
+        .iter()
+        .filter(|e| !e.passed())
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>();
+    let mut summary = format!(
+        "{} Batch of {} covenants: {} passed, {} failed (index {})",
+        if failed.is_empty() { "✅" } else { "⛔️" },
+        entries.len(),
+        entries.len() - failed.len(),
+        failed.len(),
+        output_dir.join("index.md").display()
+    );
+    if !failed.is_empty() {
+        summary.push_str(&format!("\nFailed: {}", failed.join(", ")));
+    }
+    summary
+}
@@ -0,0 +1,91 @@
+use anyhow::{Context, Error};
+use log::info;
+use serde::Deserialize;
+use serde_json::json;
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::validations::CovenantValidationContext;
+
+/// Key the plugin results are reported under
+const PLUGINS_KEY: &str = "plugins";
+// A plugin still running after this long is killed and reported as failed
+const PLUGIN_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PluginOutput {
+    checks: Vec<PluginCheck>,
+}
+
+/// Check returned by a plugin
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct PluginCheck {
+    key: String,
+    field: String,
+    /// `valid`, `warning` or `error`
+    status: String,
+    message: String,
+}
+
+/// Run the plugin executables of the context and merge their checks into the report.
+///
+/// A plugin receives `{"message": <instantiation>, "context": <resolved context>}` on
+/// stdin and prints `{"checks": [{"key", "field", "status", "message"}]}` on stdout.
+pub async fn run_plugins(
+    ctx: &mut CovenantValidationContext<'_>,
+    instantiation: &serde_json::Value,
+) {
+    let input = json!({ "message": instantiation, "context": &*ctx }).to_string();
+    for plugin in ctx.plugins() {
+        info!("Running plugin {}", plugin);
+        match run_plugin(&plugin, &input).await {
+            Ok(output) => {
+                for check in output.checks {
+                    let message = format!(
+                        "{}.{}: {} ({})",
+                        check.key, check.field, check.message, plugin
+                    );
+                    match check.status.as_str() {
+                        "valid" => ctx.valid(PLUGINS_KEY, message),
+                        "warning" => ctx.warn(PLUGINS_KEY, message),
+                        _ => ctx.invalid(PLUGINS_KEY, message),
+                    }
+                }
+            }
+            Err(e) => ctx.invalid(PLUGINS_KEY, format!("{}: failed: {:#}", plugin, e)),
+        }
+    }
+}
+
+async fn run_plugin(plugin: &str, input: &str) -> Result<PluginOutput, Error> {
+    let mut child = Command::new(plugin)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("failed starting {}", plugin))?;
+    // Stdin is written while the output is read and closed once written, a plugin not
+    // reading its input is then also stopped by the timeout
+    let stdin = child.stdin.take();
+    let write_input = async move {
+        if let Some(mut stdin) = stdin {
+            stdin.write_all(input.as_bytes()).await?;
+        }
+        Ok::<(), std::io::Error>(())
+    };
+    let (written, output) = tokio::time::timeout(PLUGIN_TIMEOUT, async {
+        tokio::join!(write_input, child.wait_with_output())
+    })
+    .await
+    .with_context(|| format!("timed out after {}s", PLUGIN_TIMEOUT.as_secs()))?;
+    written.with_context(|| "failed writing the plugin input")?;
+    let output = output?;
+    if !output.status.success() {
+        anyhow::bail!("exited with {}", output.status);
+    }
+    serde_json::from_slice(&output.stdout).with_context(|| "invalid plugin output")
+}
@@ -0,0 +1,129 @@
+use chrono::DateTime;
+use itertools::Itertools;
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::display::ReportDisplay;
+use crate::validations::CovenantValidationContext;
+
+/// Render a governance proposal description of a validated covenant
+pub fn render_proposal(
+    ctx: &CovenantValidationContext,
+    display: &ReportDisplay,
+    instantiation: &Value,
+) -> String {
+    let mut out = format!("# {}\n\n", ctx.covenant_label());
+    out.push_str(&format!(
+        "This proposal instantiates a `{}` covenant ({}) on {}.\n",
+        ctx.covenant_contract(),
+        ctx.contract_codes_version(),
+        ctx.host_chain_name()
+    ));
+
+    out.push_str("\n## Parties\n\n");
+    out.push_str("| Party | Chain | Contribution |\n| :--- | :--- | :--- |\n");
+    for (party, name, chain_name) in [
+        ("party_a", ctx.party_a_name(), ctx.party_a_chain_name()),
+        ("party_b", ctx.party_b_name(), ctx.party_b_chain_name()),
+    ] {
+        if chain_name.is_empty() {
+            continue;
+        }
+        let contribution = party_contribution(instantiation, party)
+            .map(|(denom, amount)| display.message(&format!("{} {}", amount, denom)))
+            .unwrap_or_else(|| "-".to_owned());
+        out.push_str(&format!(
+            "| {} | {} | {} |\n",
+            name.unwrap_or_else(|| party.to_owned()),
+            chain_name,
+            contribution
+        ));
+    }
+
+    if let Some(pool_address) = find_member(instantiation, "pool_address").and_then(Value::as_str) {
+        out.push_str("\n## Liquidity pool\n\n");
+        out.push_str(&format!("Astroport pool `{}`\n", pool_address));
+    }
+
+    let deadlines = ["deposit_deadline", "lockup_config", "lockup_period"]
+        .iter()
+        .filter_map(|field| {
+            let expiration = instantiation.get(field)?;
+            Some(format!(
+                "| {} | {} |\n",
+                field,
+                format_expiration(expiration)
+            ))
+        })
+        .collect::<String>();
+    if !deadlines.is_empty() {
+        out.push_str("\n## Deadlines\n\n| | |\n| :--- | :--- |\n");
+        out.push_str(&deadlines);
+    }
+
+    if let Some(code_ids) = instantiation
+        .get("contract_codes")
+        .and_then(Value::as_object)
+    {
+        out.push_str("\n## Contract code ids\n\n| Contract | Code id |\n| :--- | ---: |\n");
+        for (contract, code_id) in code_ids.iter().sorted_by_key(|x| x.0) {
+            out.push_str(&format!("| {} | {} |\n", contract, code_id));
+        }
+    }
+
+    let count =
+        |entries: &HashMap<&str, Vec<String>>| entries.values().map(Vec::len).sum::<usize>();
+    out.push_str("\n## Verification\n\n");
+    out.push_str(&format!(
+        "- Instantiation message SHA-256: `{}`\n",
+        ctx.message_hash()
+    ));
+    out.push_str(&format!(
+        "- Validated with {} {}: {} checks passed, {} warnings, {} accepted deviations\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        count(ctx.checks()),
+        count(ctx.warnings()),
+        ctx.accepted().values().map(Vec::len).sum::<usize>()
+    ));
+    if let Some(commit) = ctx.registry_commit() {
+        out.push_str(&format!("- Chain registry commit: {}\n", commit));
+    }
+    out
+}
+
+/// Contribution (denom, amount) of an interchain or native party config
+fn party_contribution(instantiation: &Value, party: &str) -> Option<(String, String)> {
+    let party_config = instantiation
+        .get(format!("{}_config", party))?
+        .as_object()?;
+    let contribution = party_config.values().next()?.get("contribution")?;
+    Some((
+        contribution.get("denom")?.as_str()?.to_owned(),
+        contribution.get("amount")?.as_str()?.to_owned(),
+    ))
+}
+
+/// First member of the given name, depth first
+fn find_member<'v>(value: &'v Value, name: &str) -> Option<&'v Value> {
+    let object = value.as_object()?;
+    object
+        .get(name)
+        .or_else(|| object.values().find_map(|child| find_member(child, name)))
+}
+
+fn format_expiration(expiration: &Value) -> String {
+    if let Some(nanos) = expiration
+        .get("at_time")
+        .and_then(Value::as_str)
+        .and_then(|nanos| nanos.parse::<i64>().ok())
+    {
+        return DateTime::from_timestamp(nanos / 1_000_000_000, 0)
+            .map(|time| time.format("%Y-%m-%d %H:%M UTC").to_string())
+            .unwrap_or_else(|| nanos.to_string());
+    }
+    if let Some(height) = expiration.get("at_height") {
+        return format!("block {}", height);
+    }
+    "never".to_owned()
+}
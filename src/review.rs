@@ -0,0 +1,232 @@
+use anyhow::Error;
+use crossterm::{
+    event::{self, Event, KeyCode, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use itertools::Itertools;
+use ratatui::{
+    backend::CrosstermBackend,
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Row, Table, Wrap},
+    Frame, Terminal,
+};
+use std::collections::{BTreeMap, HashMap};
+use std::io::stdout;
+
+use crate::validations::CovenantValidationContext;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Status {
+    Valid,
+    Warning,
+    Invalid,
+}
+
+struct Section {
+    key: String,
+    rows: Vec<(String, String, Status)>,
+}
+
+struct App {
+    sections: Vec<Section>,
+    selected: ListState,
+    raw_json: String,
+    show_raw_json: bool,
+    scroll: u16,
+}
+
+/// Open an interactive review of the validation results
+pub fn run(ctx: &CovenantValidationContext, raw_json: &str) -> Result<(), Error> {
+    let mut app = App {
+        sections: collect_sections(ctx),
+        selected: ListState::default().with_selected(Some(0)),
+        raw_json: raw_json.to_owned(),
+        show_raw_json: false,
+        scroll: 0,
+    };
+
+    enable_raw_mode()?;
+    execute!(stdout(), EnterAlternateScreen)?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let result = event_loop(&mut terminal, &mut app);
+    disable_raw_mode()?;
+    execute!(stdout(), LeaveAlternateScreen)?;
+    result
+}
+
+fn collect_sections(ctx: &CovenantValidationContext) -> Vec<Section> {
+    let mut sections: BTreeMap<String, Vec<(String, String, Status)>> = BTreeMap::new();
+    let mut collect = |entries: &HashMap<&str, Vec<String>>, status: Status| {
+        for (key, messages) in entries.iter().sorted_by_key(|x| x.0) {
+            for message in messages {
+                let (field, message) = message.split_once(": ").unwrap_or(("", message));
+                sections.entry(key.to_string()).or_default().push((
+                    field.to_owned(),
+                    message.to_owned(),
+                    status,
+                ));
+            }
+        }
+    };
+    collect(ctx.checks(), Status::Valid);
+    collect(ctx.warnings(), Status::Warning);
+    collect(ctx.errors(), Status::Invalid);
+
+    sections
+        .into_iter()
+        .map(|(key, rows)| Section { key, rows })
+        .collect()
+}
+
+fn event_loop(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut App,
+) -> Result<(), Error> {
+    loop {
+        terminal.draw(|f| draw(f, app))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let selected = app.selected.selected().unwrap_or_default();
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char('r') => {
+                    app.show_raw_json = !app.show_raw_json;
+                    app.scroll = 0;
+                }
+                KeyCode::Down | KeyCode::Char('j') if app.show_raw_json => {
+                    app.scroll = app.scroll.saturating_add(1);
+                }
+                KeyCode::Up | KeyCode::Char('k') if app.show_raw_json => {
+                    app.scroll = app.scroll.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if selected + 1 < app.sections.len() {
+                        app.selected.select(Some(selected + 1));
+                    }
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    app.selected.select(Some(selected.saturating_sub(1)));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+fn draw(f: &mut Frame, app: &mut App) {
+    let layout = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(25), Constraint::Percentage(75)])
+        .split(f.size());
+
+    let items = app
+        .sections
+        .iter()
+        .map(|section| {
+            let status = section_status(section);
+            ListItem::new(format!("{} {}", status_symbol(status), section.key))
+                .style(Style::default().fg(status_color(status)))
+        })
+        .collect::<Vec<_>>();
+    let sidebar = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title("Sections"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    f.render_stateful_widget(sidebar, layout[0], &mut app.selected);
+
+    if app.show_raw_json {
+        let raw_json = Paragraph::new(app.raw_json.as_str())
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Instantiation message (r: back, q: quit)"),
+            )
+            .scroll((app.scroll, 0));
+        f.render_widget(raw_json, layout[1]);
+        return;
+    }
+
+    let Some(section) = app
+        .selected
+        .selected()
+        .and_then(|selected| app.sections.get(selected))
+    else {
+        return;
+    };
+    let rows = section
+        .rows
+        .iter()
+        .map(|(field, message, status)| {
+            Row::new(vec![
+                field.clone(),
+                message.clone(),
+                status_symbol(*status).to_owned(),
+            ])
+            .style(Style::default().fg(status_color(*status)))
+        })
+        .collect::<Vec<_>>();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(30),
+            Constraint::Percentage(60),
+            Constraint::Percentage(10),
+        ],
+    )
+    .header(
+        Row::new(vec!["Field", "Message", "Status"])
+            .style(Style::default().add_modifier(Modifier::BOLD)),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("{} (r: raw JSON, q: quit)", section.key)),
+    );
+    f.render_widget(table, layout[1]);
+
+    // Long messages (expected vs actual) are easier to read wrapped below the table
+    if let Some((_, message, _)) = section.rows.iter().find(|r| r.2 != Status::Valid) {
+        let detail_area = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(4)])
+            .split(layout[1])[1];
+        let detail = Paragraph::new(message.as_str())
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("First finding"),
+            );
+        f.render_widget(detail, detail_area);
+    }
+}
+
+fn section_status(section: &Section) -> Status {
+    if section.rows.iter().any(|r| r.2 == Status::Invalid) {
+        Status::Invalid
+    } else if section.rows.iter().any(|r| r.2 == Status::Warning) {
+        Status::Warning
+    } else {
+        Status::Valid
+    }
+}
+
+fn status_symbol(status: Status) -> &'static str {
+    match status {
+        Status::Valid => "✅",
+        Status::Warning => "⚠️",
+        Status::Invalid => "⛔️",
+    }
+}
+
+fn status_color(status: Status) -> Color {
+    match status {
+        Status::Valid => Color::Green,
+        Status::Warning => Color::Yellow,
+        Status::Invalid => Color::Red,
+    }
+}
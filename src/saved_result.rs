@@ -0,0 +1,35 @@
+use anyhow::{Context, Error};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::validations::CovenantValidationContext;
+
+/// Structured result of a validation run, rendered again offline with `render`
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SavedResult<'a> {
+    pub version: String,
+    #[serde(borrow)]
+    pub context: CovenantValidationContext<'a>,
+    /// Symbols of the denoms resolved during the run
+    pub symbols: HashMap<String, String>,
+}
+
+pub fn save_result(
+    result_file: &str,
+    ctx: &CovenantValidationContext,
+    symbols: &HashMap<String, String>,
+) -> Result<(), Error> {
+    let result = serde_json::json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "context": ctx,
+        "symbols": symbols,
+    });
+    std::fs::write(result_file, serde_json::to_string_pretty(&result)?)
+        .with_context(|| format!("failed writing {}", result_file))
+}
+
+/// Parse a saved result, its keys borrow from the file content
+pub fn load_result(content: &str) -> Result<SavedResult<'_>, Error> {
+    serde_json::from_str(content).with_context(|| "failed loading result file")
+}
@@ -0,0 +1,62 @@
+use anyhow::Error;
+use schemars::schema_for;
+use serde_json::{json, Map, Value};
+
+use crate::lint::{MetadataType, METADATA_KEYS};
+use crate::types::SchemaType;
+
+/// JSON Schema of an instantiation message or of the metadata file
+pub fn schema(schema_type: SchemaType) -> Result<Value, Error> {
+    let schema = match schema_type {
+        SchemaType::TwoPartyPol => {
+            serde_json::to_value(schema_for!(two_party_pol_covenant::msg::InstantiateMsg))?
+        }
+        SchemaType::SinglePartyPol => {
+            serde_json::to_value(schema_for!(single_party_pol_covenant::msg::InstantiateMsg))?
+        }
+        SchemaType::Swap => serde_json::to_value(schema_for!(swap_covenant::msg::InstantiateMsg))?,
+        SchemaType::Metadata => metadata_schema(),
+    };
+    Ok(schema)
+}
+
+/// Schema of the metadata TOML file, as JSON
+fn metadata_schema() -> Value {
+    let properties = METADATA_KEYS
+        .iter()
+        .map(|(key, metadata_type)| (key.to_string(), property_schema(*metadata_type)))
+        .collect::<Map<_, _>>();
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Covenant metadata",
+        "type": "object",
+        "required": ["covenant"],
+        "properties": {
+            "covenant": {
+                "type": "object",
+                "required": ["party_a_chain_name"],
+                "properties": properties,
+                "additionalProperties": false,
+            },
+        },
+    })
+}
+
+fn property_schema(metadata_type: MetadataType) -> Value {
+    match metadata_type {
+        MetadataType::String => json!({ "type": "string" }),
+        MetadataType::Boolean => json!({ "type": "boolean" }),
+        MetadataType::Integer => json!({ "type": "integer", "minimum": 0 }),
+        MetadataType::OneOf(values) => json!({ "type": "string", "enum": values }),
+        MetadataType::PctBounds => json!({
+            "type": "array",
+            "items": { "type": "number", "minimum": 0, "maximum": 100 },
+            "minItems": 2,
+            "maxItems": 2,
+        }),
+        MetadataType::DenomAmounts => json!({
+            "type": "object",
+            "additionalProperties": { "type": "integer", "minimum": 0 },
+        }),
+    }
+}
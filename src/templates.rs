@@ -0,0 +1,102 @@
+use anyhow::{Context, Error};
+use itertools::Itertools;
+use log::{info, warn};
+use std::path::Path;
+
+/// Parameterized metadata and instantiation files for a common covenant deployment
+pub struct Template {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub metadata: &'static str,
+    pub instantiation: &'static str,
+}
+
+pub const TEMPLATES: &[Template] = &[
+    Template {
+        name: "atom-ntrn-two-party-pol",
+        description: "ATOM-NTRN two-party POL on Astroport",
+        metadata: include_str!("../templates/atom-ntrn-two-party-pol/metadata.toml"),
+        instantiation: include_str!("../templates/atom-ntrn-two-party-pol/instantiation.json"),
+    },
+    Template {
+        name: "statom-single-party-pol",
+        description: "Single-party stATOM POL on Astroport",
+        metadata: include_str!("../templates/statom-single-party-pol/metadata.toml"),
+        instantiation: include_str!("../templates/statom-single-party-pol/instantiation.json"),
+    },
+];
+
+impl Template {
+    /// Names of the `{{parameter}}` placeholders used by the template
+    pub fn parameters(&self) -> Vec<String> {
+        [self.metadata, self.instantiation]
+            .iter()
+            .flat_map(|content| placeholders(content))
+            .unique()
+            .collect()
+    }
+}
+
+pub fn list_templates() {
+    println!("| Template | Description | Parameters |\n| :--- | :--- | :--- |");
+    for template in TEMPLATES {
+        println!(
+            "| {} | {} | {} |",
+            template.name,
+            template.description,
+            template.parameters().join(", ")
+        );
+    }
+}
+
+/// Write the template files into `output_dir`, substituting the given parameters
+pub fn init_template(
+    name: &str,
+    output_dir: &str,
+    params: &[(String, String)],
+) -> Result<(), Error> {
+    let template = TEMPLATES
+        .iter()
+        .find(|t| t.name == name)
+        .with_context(|| format!("unknown template {}", name))?;
+
+    let output_dir = Path::new(output_dir);
+    std::fs::create_dir_all(output_dir)?;
+    for (file_name, content) in [
+        ("metadata.toml", template.metadata),
+        ("instantiation.json", template.instantiation),
+    ] {
+        let mut content = content.to_owned();
+        for (key, value) in params {
+            content = content.replace(&format!("{{{{{}}}}}", key), value);
+        }
+        let unresolved = placeholders(&content);
+        if !unresolved.is_empty() {
+            warn!(
+                "{}: unresolved parameters {}",
+                file_name,
+                unresolved.iter().unique().join(", ")
+            );
+        }
+        let path = output_dir.join(file_name);
+        std::fs::write(&path, content)
+            .with_context(|| format!("failed writing {}", path.display()))?;
+        info!("Wrote {}", path.display());
+    }
+    Ok(())
+}
+
+pub fn parse_param(param: &str) -> Result<(String, String), String> {
+    param
+        .split_once('=')
+        .map(|(key, value)| (key.to_owned(), value.to_owned()))
+        .ok_or_else(|| format!("invalid parameter '{}': expected key=value", param))
+}
+
+fn placeholders(content: &str) -> Vec<String> {
+    content
+        .split("{{")
+        .skip(1)
+        .filter_map(|part| part.split_once("}}").map(|(name, _)| name.to_owned()))
+        .collect()
+}
@@ -0,0 +1,132 @@
+use itertools::Itertools;
+use std::collections::HashMap;
+
+use crate::display::ReportDisplay;
+use crate::validations::CovenantValidationContext;
+
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const RED: &str = "\x1b[31m";
+const CYAN: &str = "\x1b[36m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+struct Row {
+    status: &'static str,
+    color: &'static str,
+    key: String,
+    field: String,
+    check_id: String,
+    message: String,
+}
+
+/// Render the validation report as an aligned table, one color per status
+pub fn render_terminal(
+    ctx: &CovenantValidationContext,
+    display: &ReportDisplay,
+    color: bool,
+) -> String {
+    let paint = |text: &str, code: &str| {
+        if color {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_owned()
+        }
+    };
+
+    let mut rows = vec![];
+    for (entries, status, code) in [
+        (ctx.checks(), "PASS", GREEN),
+        (ctx.warnings(), "WARN", YELLOW),
+        (ctx.errors(), "FAIL", RED),
+    ] {
+        for (key, messages) in entries.iter().sorted_by_key(|x| x.0) {
+            for message in messages {
+                let (field, message) = message.split_once(": ").unwrap_or(("", message));
+                rows.push(row(ctx, display, key, field, message, status, code));
+            }
+        }
+    }
+    for (key, deviations) in ctx.accepted().iter().sorted_by_key(|x| x.0) {
+        for (message, justification) in deviations {
+            let (field, message) = message.split_once(": ").unwrap_or(("", message));
+            let message = format!("{} (accepted: {})", message, justification);
+            rows.push(row(ctx, display, key, field, &message, "ACCEPTED", CYAN));
+        }
+    }
+
+    let width = |column: fn(&Row) -> &str| {
+        rows.iter()
+            .map(|row| column(row).chars().count())
+            .max()
+            .unwrap_or_default()
+    };
+    let status_width = width(|row| row.status);
+    let key_width = width(|row| row.key.as_str());
+    let field_width = width(|row| row.field.as_str());
+    let check_width = width(|row| row.check_id.as_str());
+
+    let mut out = paint(
+        &format!(
+            "Covenant validation report: {} ({} {})",
+            ctx.covenant_label(),
+            ctx.covenant_contract(),
+            ctx.contract_codes_version()
+        ),
+        BOLD,
+    );
+    out.push('\n');
+    for row in &rows {
+        let line = format!(
+            "{:<status_width$}  {:<key_width$}  {:<field_width$}  {:<check_width$}  {}",
+            row.status, row.key, row.field, row.check_id, row.message
+        );
+        out.push_str(&paint(line.trim_end(), row.color));
+        out.push('\n');
+    }
+
+    let count = |entries: &HashMap<&str, Vec<String>>| entries.values().map(Vec::len).sum();
+    let (passed, warnings, errors): (usize, usize, usize) = (
+        count(ctx.checks()),
+        count(ctx.warnings()),
+        count(ctx.errors()),
+    );
+    out.push_str(&format!(
+        "\n{}, {}, {}\n",
+        paint(&format!("{} passed", passed), GREEN),
+        paint(&format!("{} warnings", warnings), YELLOW),
+        paint(&format!("{} errors", errors), RED)
+    ));
+    if !ctx.skipped_keys().is_empty() {
+        out.push_str(&format!(
+            "Skipped sections of the other party: {}\n",
+            ctx.skipped_keys().join(", ")
+        ));
+    }
+    if !ctx.reused_keys().is_empty() {
+        out.push_str(&format!(
+            "Sections unchanged since the last run (cached): {}\n",
+            ctx.reused_keys().join(", ")
+        ));
+    }
+    out
+}
+
+fn row(
+    ctx: &CovenantValidationContext,
+    display: &ReportDisplay,
+    key: &str,
+    field: &str,
+    message: &str,
+    status: &'static str,
+    color: &'static str,
+) -> Row {
+    Row {
+        status,
+        color,
+        key: display.key(key),
+        field: field.to_owned(),
+        check_id: ctx.check_id(key, field).unwrap_or_default(),
+        message: display.message(message),
+    }
+}
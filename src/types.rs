@@ -1,4 +1,7 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Deserialize;
+
+use crate::templates::parse_param;
 
 /// Covenant CLI
 #[derive(Parser)]
@@ -6,15 +9,332 @@ use clap::{Parser, Subcommand};
 pub(crate) struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+    /// List the remote queries a command would perform without performing them
+    #[arg(long, global = true)]
+    pub plan: bool,
+    /// Only log errors
+    #[arg(long, global = true)]
+    pub quiet: bool,
+    /// Maximum number of concurrent remote requests
+    #[arg(long, global = true)]
+    pub max_concurrency: Option<usize>,
+    /// Minimum delay in milliseconds between two remote requests
+    #[arg(long, global = true)]
+    pub request_interval_ms: Option<u64>,
+    /// Slack or Discord webhook notified of validation results and watched state changes
+    #[arg(long, global = true, env = "COVENANT_NOTIFY_WEBHOOK")]
+    pub notify_webhook: Option<String>,
+    /// Base URL of a mock server to send the remote requests to (e.g. http://127.0.0.1:1317)
+    #[arg(long, global = true, env = "COVENANT_MOCK_SERVER")]
+    pub mock_server: Option<String>,
+    /// Local chain registry directory whose files take precedence over the remote registry
+    #[arg(long, global = true, env = "COVENANT_LOCAL_REGISTRY")]
+    pub chain_registry_local_first: Option<String>,
+    /// Disable the colors of the terminal report (also disabled by the NO_COLOR variable)
+    #[arg(long, global = true)]
+    pub no_color: bool,
+    /// TOML configuration file providing defaults for the flags, endpoints and metadata
+    #[arg(long, global = true, env = "COVENANT_CONFIG")]
+    pub config: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum OutputFormat {
+    /// Markdown table
+    Markdown,
+    /// Markdown with a collapsible section per key, sized for a GitHub comment
+    GithubComment,
+    /// One CSV row per check, with a reviewer column for sign-off spreadsheets
+    Csv,
+    /// Aligned table colored by status, the default when stdout is a terminal
+    Terminal,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) enum Locale {
+    /// Numbers as queried (1234567.5)
+    #[default]
+    Raw,
+    /// English separators (1,234,567.5)
+    En,
+    /// German separators (1.234.567,5)
+    De,
+    /// French separators (1 234 567,5)
+    Fr,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum Party {
+    A,
+    B,
+}
+
+impl Party {
+    /// Party identifier of the covenant messages and queries
+    pub fn key(self) -> &'static str {
+        match self {
+            Party::A => "party_a",
+            Party::B => "party_b",
+        }
+    }
+}
+
+/// Structures with a published JSON Schema
+#[derive(Clone, Copy, Debug, PartialEq, ValueEnum)]
+pub(crate) enum SchemaType {
+    /// Two party POL covenant instantiation message
+    TwoPartyPol,
+    /// Single party POL covenant instantiation message
+    SinglePartyPol,
+    /// Swap covenant instantiation message
+    Swap,
+    /// Metadata file
+    Metadata,
+}
+
+/// Sections of a covenant validated for one counterparty, the shared sections are always
+/// validated
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub(crate) enum PartyScope {
+    A,
+    B,
+    #[default]
+    All,
+}
+
+impl PartyScope {
+    /// Report keys of the other party
+    pub fn skipped_keys(self) -> &'static [&'static str] {
+        match self {
+            PartyScope::A => &["party_b_config"],
+            PartyScope::B => &["party_a_config"],
+            PartyScope::All => &[],
+        }
+    }
 }
 
 #[derive(Subcommand)]
 pub(crate) enum Commands {
     /// Validate a Covenant deployment
     Validate {
+        /// Path to the metadata file
+        metadata_file: String,
+        /// Path to the instantiation file (JSON, YAML or TOML)
+        instantiation_file: String,
+        /// Path to a bundle archive to read remote data from instead of the network
+        #[arg(long)]
+        bundle: Option<String>,
+        /// Address of the deployed covenant to verify the instantiation against
+        #[arg(long)]
+        deployed_address: Option<String>,
+        /// Query the IBC client status of the resolved paths on chain
+        #[arg(long)]
+        verify_onchain: bool,
+        /// Path to a code ids file taking precedence over the release artifact
+        #[arg(long)]
+        code_ids_file: Option<String>,
+        /// Path to the party addresses allowlist (default: `allowlist.toml` next to the metadata file)
+        #[arg(long)]
+        allowlist: Option<String>,
+        /// Executable adding custom checks to the report (repeatable, added to the config file ones)
+        #[arg(long = "plugin")]
+        plugins: Vec<String>,
+        /// Validate every section again instead of reusing the results of the sections
+        /// unchanged since a run of the last 10 minutes, cached in `.covenant-cache.json` next
+        /// to the metadata file (implied by `--json-logs-file`, the audit trail covers the data
+        /// of the whole validation)
+        #[arg(long)]
+        no_incremental: bool,
+        /// Only validate the sections of one party and the shared sections
+        #[arg(long, value_enum, default_value_t = PartyScope::All)]
+        party: PartyScope,
+        /// Format of the validation report (terminal on a TTY, else markdown, unless set in the config file)
+        #[arg(long, alias = "format", value_enum)]
+        output_format: Option<OutputFormat>,
+        /// Number formatting of the validation report (raw unless set in the config file)
+        #[arg(long, value_enum)]
+        locale: Option<Locale>,
+        /// Print a single verdict line instead of the report
+        #[arg(long)]
+        summary_only: bool,
+        /// Path to write the full report to
+        #[arg(long)]
+        report_file: Option<String>,
+        /// Path to write a JSON Patch fixing the computable errors of the instantiation to
+        #[arg(long)]
+        emit_fixes: Option<String>,
+        /// Path to write a governance proposal description to, when the validation passes
+        #[arg(long)]
+        emit_proposal: Option<String>,
+        /// Result saved by a previous run (`--save-result`) to diff the field statuses against
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Path to save the structured result to, for later rendering with `render`
+        #[arg(long)]
+        save_result: Option<String>,
+        /// Path to write the audit trail (URL, response SHA-256 and time of every remote
+        /// request, one JSON object per line) to
+        #[arg(long)]
+        json_logs_file: Option<String>,
+    },
+    /// Validate every covenant of a governance round and write an index of the reports
+    ValidateBatch {
+        /// Directory with a sub-directory (metadata and instantiation files) per covenant
+        covenants_dir: String,
+        /// Directory to write the reports and the `index.md`/`index.json` summaries to
+        #[arg(long, default_value = "reports")]
+        output_dir: String,
+        /// Number formatting of the validation reports (raw unless set in the config file)
+        #[arg(long, value_enum)]
+        locale: Option<Locale>,
+        /// Number of covenants validated at once, sharing the run cache (remote requests
+        /// stay bounded by `--max-concurrency`)
+        #[arg(long, default_value_t = 4)]
+        jobs: usize,
+    },
+    /// Render the report of a saved validation result in another format
+    Render {
+        /// Path to the result file (`validate --save-result`)
+        result_file: String,
+        /// Format of the validation report (terminal on a TTY, else markdown, unless set in the config file)
+        #[arg(long, alias = "format", value_enum)]
+        output_format: Option<OutputFormat>,
+        /// Number formatting of the validation report (raw unless set in the config file)
+        #[arg(long, value_enum)]
+        locale: Option<Locale>,
+    },
+    /// Explain a validation check given its identifier (e.g. TPP-LP-003)
+    ExplainCheck {
+        /// Check identifier
+        id: String,
+    },
+    /// Check the reachability of the endpoints and print the effective configuration
+    Doctor,
+    /// Check a metadata file before running the full validation
+    LintMetadata {
+        /// Path to the metadata file
+        metadata_file: String,
+    },
+    /// Print the JSON Schema of an instantiation message or of the metadata file
+    Schema {
+        /// Message or file described by the schema
+        #[arg(long = "type", value_enum)]
+        schema_type: SchemaType,
+    },
+    /// Print the SHA-256 of the canonical JSON of an instantiation file
+    Hash {
+        /// Path to the instantiation file
+        file: String,
+    },
+    /// Interactively build a metadata file
+    Init {
+        /// Path to the metadata file to write
+        #[arg(long, default_value = "metadata.toml")]
+        output_file: String,
+    },
+    /// Validate a Covenant deployment and review the results interactively
+    Review {
+        /// Path to the metadata file
+        metadata_file: String,
+        /// Path to the instantiation file
+        instantiation_file: String,
+    },
+    /// Watch a deployed Covenant
+    Watch {
+        /// Address of the covenant contract
+        covenant_address: String,
+        /// Name of the chain hosting the covenant
+        #[arg(long, default_value = "neutron")]
+        host_chain_name: String,
+        /// Polling interval in seconds
+        #[arg(long, default_value_t = 60)]
+        interval: u64,
+        /// Address to publish Prometheus metrics on (e.g. 0.0.0.0:9100)
+        #[arg(long)]
+        metrics_listen: Option<String>,
+        /// Time in seconds after which a lifecycle phase is reported as stuck (phase=seconds)
+        #[arg(long = "stuck-threshold", value_parser = parse_param)]
+        stuck_thresholds: Vec<(String, String)>,
+    },
+    /// Compare an instantiation message with the one a deployed covenant was instantiated with
+    Compare {
+        /// Path to the instantiation file
+        instantiation_file: String,
+        /// Address of the deployed covenant contract
+        covenant_address: String,
+        /// Name of the chain hosting the covenant
+        #[arg(long, default_value = "neutron")]
+        host_chain_name: String,
+    },
+    /// Print the deposit address, denom, amount and deadline of a party before it deposits
+    FundCheck {
+        /// Address of the covenant contract
+        covenant_address: String,
+        /// Depositing party
+        #[arg(long, value_enum)]
+        party: Party,
+        /// Name of the chain hosting the covenant
+        #[arg(long, default_value = "neutron")]
+        host_chain_name: String,
+    },
+    /// Print the contract tree of a deployed covenant
+    Inspect {
+        /// Address of the covenant contract
+        covenant_address: String,
+        /// Name of the chain hosting the covenant
+        #[arg(long, default_value = "neutron")]
+        host_chain_name: String,
+    },
+    /// Manage offline bundles of the remote data used by a validation
+    Bundle {
+        #[command(subcommand)]
+        command: BundleCommands,
+    },
+    /// Manage covenant templates for common deployments
+    Template {
+        #[command(subcommand)]
+        command: TemplateCommands,
+    },
+    /// Serve recorded registry and chain responses on localhost (development only)
+    #[cfg(feature = "mock-server")]
+    MockServer {
+        /// Directory of the bundle archives to serve responses from
+        #[arg(long)]
+        fixtures: String,
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:1317")]
+        listen: String,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum TemplateCommands {
+    /// List the available templates
+    List,
+    /// Write a template's metadata and instantiation files
+    Init {
+        /// Name of the template
+        name: String,
+        /// Directory to write the files to
+        #[arg(long, default_value = ".")]
+        output_dir: String,
+        /// Template parameter to substitute (key=value)
+        #[arg(long = "set", value_parser = parse_param)]
+        params: Vec<(String, String)>,
+    },
+}
+
+#[derive(Subcommand)]
+pub(crate) enum BundleCommands {
+    /// Fetch and archive all remote data needed to validate a Covenant deployment
+    Create {
         /// Path to the metadata file
         metadata_file: String,
         /// Path to the instantiation file
         instantiation_file: String,
+        /// Path to the bundle archive to write (gzipped tarball)
+        bundle_file: String,
     },
 }
@@ -2,6 +2,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::context::CliContext;
+use crate::error::CovenantCliError;
 
 // Inspired by https://github.com/PeggyJV/chain-registry
 
@@ -21,6 +22,22 @@ pub struct AssetInfo {
     pub logo_uris: LogoURIs,
 }
 
+impl AssetInfo {
+    /// Exponent of the display denom unit, the registry often omits `decimals`
+    fn display_exponent(&self) -> Option<u8> {
+        self.denom_units
+            .iter()
+            .find(|unit| {
+                unit.denom == self.display
+                    || unit
+                        .aliases
+                        .as_ref()
+                        .is_some_and(|aliases| aliases.contains(&self.display))
+            })
+            .and_then(|unit| u8::try_from(unit.exponent).ok())
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 pub struct DenomUnit {
@@ -36,30 +53,60 @@ pub struct LogoURIs {
     pub svg: String,
 }
 
-pub async fn get_chain_asset_info(
+pub async fn get_chain_assets(
     ctx: &CliContext,
     chain_id: &str,
-    asset_name: &str,
-) -> Result<AssetInfo, anyhow::Error> {
+) -> Result<Vec<AssetInfo>, CovenantCliError> {
     let mut json: Value = ctx
         .api_get(&format!(
             "https://chains.cosmos.directory/{}/assetlist",
             chain_id
         ))
-        .await?;
+        .await
+        .map_err(CovenantCliError::registry)?;
     let assets_obj = json["assets"].take();
     let assets: Vec<AssetInfo> = serde_json::from_value(assets_obj).unwrap_or_default();
+    Ok(assets
+        .into_iter()
+        .filter(|asset| !(asset.name.contains("(old)") || asset.symbol.contains("(old)")))
+        .collect())
+}
+
+pub async fn get_chain_asset_info(
+    ctx: &CliContext,
+    chain_id: &str,
+    asset_name: &str,
+) -> Result<AssetInfo, CovenantCliError> {
+    let assets = get_chain_assets(ctx, chain_id).await?;
     let asset = assets.into_iter().find(|asset| {
-        (asset.name == asset_name
+        asset.name == asset_name
             || asset.symbol == asset_name
             || asset.denom == asset_name
             || asset.display == asset_name
-            || asset.base == asset_name)
-            && !(asset.name.contains("(old)") || asset.symbol.contains("(old)"))
+            || asset.base == asset_name
     });
-    if let Some(asset) = asset {
+    if let Some(mut asset) = asset {
+        if asset.decimals == 0 {
+            asset.decimals = asset.display_exponent().unwrap_or_default();
+        }
         Ok(asset)
     } else {
-        Err(anyhow::anyhow!("Asset not found"))
+        Err(CovenantCliError::RegistryError(format!(
+            "asset {} not found on {}",
+            asset_name, chain_id
+        )))
     }
 }
+
+/// Symbols of the chain assets, by base denom
+pub async fn get_chain_denom_symbols(
+    ctx: &CliContext,
+    chain_id: &str,
+) -> Result<Vec<(String, String)>, CovenantCliError> {
+    Ok(get_chain_assets(ctx, chain_id)
+        .await?
+        .into_iter()
+        .filter(|asset| !asset.symbol.is_empty())
+        .map(|asset| (asset.base, asset.symbol))
+        .collect())
+}
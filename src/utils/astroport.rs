@@ -1,14 +1,21 @@
-use base64::{engine::general_purpose::URL_SAFE, Engine as _};
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE},
+    Engine as _,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use crate::context::CliContext;
-
-const NEUTRON_RPC_URL: &str = "https://rest-kralum.neutron-1.neutron.org";
-// const COIN_REGISTRY_CONTRACT_ADDRESS: &str =
-//     "neutron1jzzv6r5uckwd64n6qan3suzker0kct5w565f6529zjyumfcx96kqtcswn3";
-// const FACTORY_CONTRACT_CODE_ID: &str =
-//     "neutron1hptk0k5kng7hjy35vmh009qd5m6l33609nypgf2yc6nqnewduqasxplt4e";
+use crate::error::CovenantCliError;
+use crate::utils::chain::get_rest_url;
+use crate::utils::cosmwasm::query_contract;
+
+/// Astroport native coin registry on Neutron
+pub const NEUTRON_COIN_REGISTRY_CONTRACT_ADDRESS: &str =
+    "neutron1jzzv6r5uckwd64n6qan3suzker0kct5w565f6529zjyumfcx96kqtcswn3";
+/// Official Astroport factory on Neutron
+pub const NEUTRON_FACTORY_CONTRACT_ADDRESS: &str =
+    "neutron1hptk0k5kng7hjy35vmh009qd5m6l33609nypgf2yc6nqnewduqasxplt4e";
 const COSMWASM_CONTRACT_API: &str = "cosmwasm/wasm/v1/contract";
 const COSMWASM_SMART_QUERY: &str = "smart";
 // const RESULT_LIMIT: usize = 30;
@@ -47,6 +54,21 @@ pub struct PairType {
     pub custom: Option<CustomPair>,
 }
 
+/// Custom pair type of the Astroport PCL (concentrated liquidity) pools
+pub const PCL_PAIR_TYPE: &str = "concentrated";
+
+/// Parameters of a PCL pool, decimals are serialized as strings
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct PclPoolParams {
+    pub amp: String,
+    pub gamma: String,
+    pub mid_fee: String,
+    pub out_fee: String,
+    /// Price of the second asset in units of the first one
+    pub price_scale: String,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 pub struct XykPair {}
@@ -89,17 +111,22 @@ pub struct Token {
 
 pub async fn get_astroport_pair_info(
     ctx: &CliContext,
+    chain_name: &str,
     pool_addr: &str,
-) -> Result<PairInfo, anyhow::Error> {
+) -> Result<PairInfo, CovenantCliError> {
     let base_url = format!(
         "{}/{}/{}/{}",
-        NEUTRON_RPC_URL, COSMWASM_CONTRACT_API, pool_addr, COSMWASM_SMART_QUERY,
+        get_rest_url(chain_name),
+        COSMWASM_CONTRACT_API,
+        pool_addr,
+        COSMWASM_SMART_QUERY,
     );
 
     let smart_query = URL_SAFE.encode(json!({ "pair": {} }).to_string());
     let mut json: Value = ctx
         .api_get(&format!("{}/{}", base_url, smart_query))
-        .await?;
+        .await
+        .map_err(CovenantCliError::lcd)?;
     let pair_obj = json["data"].take();
 
     let pair: PairInfo = serde_json::from_value(pair_obj).unwrap_or_default();
@@ -108,19 +135,98 @@ pub async fn get_astroport_pair_info(
 
 pub async fn get_astroport_pool_info(
     ctx: &CliContext,
+    chain_name: &str,
     pool_addr: &str,
-) -> Result<PoolInfo, anyhow::Error> {
+) -> Result<PoolInfo, CovenantCliError> {
     let base_url = format!(
         "{}/{}/{}/{}",
-        NEUTRON_RPC_URL, COSMWASM_CONTRACT_API, pool_addr, COSMWASM_SMART_QUERY,
+        get_rest_url(chain_name),
+        COSMWASM_CONTRACT_API,
+        pool_addr,
+        COSMWASM_SMART_QUERY,
     );
 
     let smart_query = URL_SAFE.encode(json!({ "pool": {} }).to_string());
     let mut json: Value = ctx
         .api_get(&format!("{}/{}", base_url, smart_query))
-        .await?;
+        .await
+        .map_err(CovenantCliError::lcd)?;
     let pool_obj = json["data"].take();
 
     let pool: PoolInfo = serde_json::from_value(pool_obj).unwrap_or_default();
     Ok(pool)
 }
+
+/// Native token registered in the Astroport coin registry, an error when unregistered
+pub async fn get_astroport_native_token(
+    ctx: &CliContext,
+    chain_name: &str,
+    registry_addr: &str,
+    denom: &str,
+) -> Result<NativeTokentInfo, CovenantCliError> {
+    let base_url = format!(
+        "{}/{}/{}/{}",
+        get_rest_url(chain_name),
+        COSMWASM_CONTRACT_API,
+        registry_addr,
+        COSMWASM_SMART_QUERY,
+    );
+
+    let smart_query = URL_SAFE.encode(json!({ "native_token": { "denom": denom } }).to_string());
+    let mut json: Value = ctx
+        .api_get(&format!("{}/{}", base_url, smart_query))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    serde_json::from_value(json["data"].take())
+        .map_err(|_| CovenantCliError::RegistryError(format!("{} not in the coin registry", denom)))
+}
+
+/// Pairs registered in the factory for the given assets, any pair type
+pub async fn get_astroport_factory_pairs(
+    ctx: &CliContext,
+    chain_name: &str,
+    factory_addr: &str,
+    asset_infos: &[AssetInfo],
+) -> Result<Vec<PairInfo>, CovenantCliError> {
+    // Factories listing several pair types per assets answer `pairs_by_asset_infos`,
+    // older ones only the `pair` query
+    if let Ok(pairs) = query_contract(
+        ctx,
+        chain_name,
+        factory_addr,
+        json!({ "pairs_by_asset_infos": { "asset_infos": asset_infos } }),
+    )
+    .await
+    {
+        if let Ok(pairs) = serde_json::from_value::<Vec<PairInfo>>(pairs) {
+            return Ok(pairs);
+        }
+    }
+    let pair = query_contract(
+        ctx,
+        chain_name,
+        factory_addr,
+        json!({ "pair": { "asset_infos": asset_infos } }),
+    )
+    .await?;
+    Ok(serde_json::from_value::<PairInfo>(pair)
+        .into_iter()
+        .filter(|pair| !pair.contract_addr.is_empty())
+        .collect())
+}
+
+/// Query the parameters of a PCL pool, published base64 encoded in its config
+pub async fn get_astroport_pcl_params(
+    ctx: &CliContext,
+    chain_name: &str,
+    pool_addr: &str,
+) -> Result<PclPoolParams, CovenantCliError> {
+    let config = query_contract(ctx, chain_name, pool_addr, json!({ "config": {} })).await?;
+    let params = config["params"]
+        .as_str()
+        .ok_or_else(|| CovenantCliError::LcdError(format!("no params in {} config", pool_addr)))?;
+    let params = STANDARD
+        .decode(params)
+        .map_err(|e| CovenantCliError::parse("PCL params", e))?;
+    serde_json::from_slice(&params).map_err(|e| CovenantCliError::parse("PCL params", e))
+}
@@ -1,8 +1,16 @@
-use anyhow::Error;
+use log::warn;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::context::CliContext;
+use crate::error::CovenantCliError;
+use crate::utils::assets::get_chain_asset_info;
+use crate::utils::path::get_registry_chain_info;
+
+const NEUTRON_REST_URL: &str = "https://rest-kralum.neutron-1.neutron.org";
+const NEUTRON_RPC_URL: &str = "https://neutron-tw-rpc.polkachu.com:443";
+const REST_PROXY_URL: &str = "https://rest.cosmos.directory";
+const RPC_PROXY_URL: &str = "https://rpc.cosmos.directory";
 
 // Inspired by https://github.com/PeggyJV/chain-registry
 
@@ -42,6 +50,49 @@ pub struct ChainInfo {
     pub best_apis: Apis,
 }
 
+impl ChainInfo {
+    /// Whether the details the validations rely on are all set
+    fn is_complete(&self) -> bool {
+        !self.chain_id.is_empty()
+            && !self.bech32_prefix.is_empty()
+            && !self.fees.fee_tokens.is_empty()
+            && !self.staking.staking_tokens.is_empty()
+            && !self.apis.rest.is_empty()
+            && !self.apis.rpc.is_empty()
+    }
+
+    /// Fill the missing details with the registry ones
+    fn complete_with(&mut self, registry_chain: ChainInfo) {
+        let fill = |value: &mut String, registry_value: String| {
+            if value.is_empty() {
+                *value = registry_value;
+            }
+        };
+        fill(&mut self.chain_name, registry_chain.chain_name);
+        fill(&mut self.chain_id, registry_chain.chain_id);
+        fill(&mut self.bech32_prefix, registry_chain.bech32_prefix);
+        fill(&mut self.daemon_name, registry_chain.daemon_name);
+        if self.slip44 == 0 {
+            self.slip44 = registry_chain.slip44;
+        }
+        if self.fees.fee_tokens.is_empty() {
+            self.fees = registry_chain.fees;
+        }
+        if self.staking.staking_tokens.is_empty() {
+            self.staking = registry_chain.staking;
+        }
+        if self.apis.rest.is_empty() {
+            self.apis.rest = registry_chain.apis.rest;
+        }
+        if self.apis.rpc.is_empty() {
+            self.apis.rpc = registry_chain.apis.rpc;
+        }
+        if self.apis.grpc.is_empty() {
+            self.apis.grpc = registry_chain.apis.grpc;
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 pub struct Genesis {
@@ -148,6 +199,35 @@ pub struct FeeToken {
     pub high_gas_price: f32,
 }
 
+impl FeeToken {
+    /// Check that the gas prices are positive and ordered, `fixed_min <= low <= average <= high`
+    pub fn check_gas_prices(&self) -> Result<(), String> {
+        if self.average_gas_price <= 0.0 {
+            return Err(format!(
+                "no average gas price for the {} fee token",
+                self.denom
+            ));
+        }
+        let prices = [
+            ("fixed_min_gas_price", self.fixed_min_gas_price),
+            ("low_gas_price", self.low_gas_price),
+            ("average_gas_price", self.average_gas_price),
+            ("high_gas_price", self.high_gas_price),
+        ];
+        for pair in prices.windows(2) {
+            let ((lower_name, lower), (higher_name, higher)) = (pair[0], pair[1]);
+            // Unset prices are skipped
+            if lower > 0.0 && higher > 0.0 && lower > higher {
+                return Err(format!(
+                    "{} {} {} exceeds {} {}",
+                    self.denom, lower_name, lower, higher_name, higher
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
 pub struct Staking {
@@ -170,11 +250,157 @@ pub struct Explorer {
     pub account_page: String,
 }
 
-pub async fn get_chain_info(ctx: &CliContext, chain_id: &str) -> Result<ChainInfo, Error> {
-    let mut json: Value = ctx
-        .api_get(&format!("https://chains.cosmos.directory/{}", chain_id))
-        .await?;
-    let achain_obj = json["chain"].take();
-    let chain: ChainInfo = serde_json::from_value(achain_obj).unwrap_or_default();
+/// Returns the LCD endpoint used to query the given chain
+pub fn get_rest_url(chain_name: &str) -> String {
+    match chain_name {
+        "neutron" => NEUTRON_REST_URL.to_owned(),
+        _ => format!("{}/{}", REST_PROXY_URL, chain_name),
+    }
+}
+
+/// Returns the RPC endpoint used to query the given chain
+pub fn get_rpc_url(chain_name: &str) -> String {
+    match chain_name {
+        "neutron" => NEUTRON_RPC_URL.to_owned(),
+        _ => format!("{}/{}", RPC_PROXY_URL, chain_name),
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct Balance {
+    pub denom: String,
+    pub amount: String,
+}
+
+/// Chain details of the cosmos.directory API, completed by the registry `chain.json` when
+/// the API is unavailable or omits some of them
+pub async fn get_chain_info(
+    ctx: &CliContext,
+    chain_id: &str,
+) -> Result<ChainInfo, CovenantCliError> {
+    let mut chain: ChainInfo = match ctx
+        .api_get::<Value>(&format!("https://chains.cosmos.directory/{}", chain_id))
+        .await
+    {
+        Ok(mut json) => serde_json::from_value(json["chain"].take()).unwrap_or_default(),
+        Err(e) => {
+            warn!("chain directory unavailable for {}: {}", chain_id, e);
+            get_registry_chain_info(ctx, chain_id).await?
+        }
+    };
+    if !chain.is_complete() {
+        if let Ok(registry_chain) = get_registry_chain_info(ctx, chain_id).await {
+            chain.complete_with(registry_chain);
+        }
+    }
+    // Fall back to the denom units of the native asset
+    if chain.decimals == 0 && !chain.denom.is_empty() {
+        if let Ok(asset_info) = get_chain_asset_info(ctx, chain_id, &chain.denom).await {
+            chain.decimals = asset_info.decimals;
+        }
+    }
     Ok(chain)
 }
+
+/// Fee tokens accepted by the chain, with their gas prices
+pub async fn get_fee_tokens(
+    ctx: &CliContext,
+    chain_name: &str,
+) -> Result<Vec<FeeToken>, CovenantCliError> {
+    Ok(get_chain_info(ctx, chain_name).await?.fees.fee_tokens)
+}
+
+pub async fn get_balances(
+    ctx: &CliContext,
+    chain_name: &str,
+    address: &str,
+) -> Result<Vec<Balance>, CovenantCliError> {
+    let mut json: Value = ctx
+        .api_get(&format!(
+            "{}/cosmos/bank/v1beta1/balances/{}",
+            get_rest_url(chain_name),
+            address
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    let balances_obj = json["balances"].take();
+    let balances: Vec<Balance> = serde_json::from_value(balances_obj).unwrap_or_default();
+    Ok(balances)
+}
+
+/// Path (`transfer/channel-0`) and base denom of an IBC denom
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct DenomTrace {
+    pub path: String,
+    pub base_denom: String,
+}
+
+/// Trace of an IBC denom (`ibc/<hash>`) on the chain
+pub async fn get_denom_trace(
+    ctx: &CliContext,
+    chain_name: &str,
+    ibc_denom: &str,
+) -> Result<DenomTrace, CovenantCliError> {
+    let hash = ibc_denom.strip_prefix("ibc/").unwrap_or(ibc_denom);
+    let mut json: Value = ctx
+        .api_get(&format!(
+            "{}/ibc/apps/transfer/v1/denom_traces/{}",
+            get_rest_url(chain_name),
+            hash
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    serde_json::from_value(json["denom_trace"].take())
+        .map_err(|e| CovenantCliError::parse("denom trace", e))
+}
+
+/// Chain listed in the chain registry
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct RegistryChain {
+    pub name: String,
+    pub chain_id: String,
+    pub pretty_name: String,
+    pub bech32_prefix: String,
+    pub network_type: String,
+}
+
+/// Chains listed in the chain registry
+pub async fn get_registry_chains(ctx: &CliContext) -> Result<Vec<RegistryChain>, CovenantCliError> {
+    let mut json: Value = ctx
+        .api_get("https://chains.cosmos.directory/")
+        .await
+        .map_err(CovenantCliError::registry)?;
+    Ok(serde_json::from_value(json["chains"].take()).unwrap_or_default())
+}
+
+/// Names of the chains listed in the chain registry
+pub async fn get_chain_names(ctx: &CliContext) -> Result<Vec<String>, CovenantCliError> {
+    Ok(get_registry_chains(ctx)
+        .await?
+        .into_iter()
+        .map(|chain| chain.name)
+        .collect())
+}
+
+/// Status (`Active`, `Frozen`, `Expired`, ...) of an IBC light client
+pub async fn get_client_status(
+    ctx: &CliContext,
+    chain_name: &str,
+    client_id: &str,
+) -> Result<String, CovenantCliError> {
+    let json: Value = ctx
+        .api_get(&format!(
+            "{}/ibc/core/client/v1/client_status/{}",
+            get_rest_url(chain_name),
+            client_id
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    json["status"]
+        .as_str()
+        .map(str::to_owned)
+        .ok_or_else(|| CovenantCliError::LcdError(format!("unexpected client status: {}", json)))
+}
@@ -0,0 +1,261 @@
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE},
+    Engine as _,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::context::CliContext;
+use crate::error::CovenantCliError;
+use crate::utils::chain::get_rest_url;
+
+const COSMWASM_CONTRACT_API: &str = "cosmwasm/wasm/v1/contract";
+const COSMWASM_CODE_API: &str = "cosmwasm/wasm/v1/code";
+const COSMWASM_SMART_QUERY: &str = "smart";
+const COSMWASM_RAW_QUERY: &str = "raw";
+const TX_SEARCH_API: &str = "cosmos/tx/v1beta1/txs";
+// Latest executions of a contract searched for an execute message
+const EXECUTE_SEARCH_LIMIT: u32 = 20;
+// Storage key of the cw2 contract name and version
+const CW2_CONTRACT_INFO_KEY: &str = "contract_info";
+pub(crate) const INIT_OPERATION: &str = "CONTRACT_CODE_HISTORY_OPERATION_TYPE_INIT";
+
+/// Run a smart query against a contract and return the response data
+pub async fn query_contract(
+    ctx: &CliContext,
+    chain_name: &str,
+    contract_addr: &str,
+    query: Value,
+) -> Result<Value, CovenantCliError> {
+    let smart_query = URL_SAFE.encode(query.to_string());
+    let mut json: Value = ctx
+        .api_get(&format!(
+            "{}/{}/{}/{}/{}",
+            get_rest_url(chain_name),
+            COSMWASM_CONTRACT_API,
+            contract_addr,
+            COSMWASM_SMART_QUERY,
+            smart_query
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    Ok(json["data"].take())
+}
+
+/// Balance of an address in a cw20 token contract (e.g. an Astroport liquidity token)
+pub async fn get_cw20_balance(
+    ctx: &CliContext,
+    chain_name: &str,
+    token_addr: &str,
+    address: &str,
+) -> Result<u128, CovenantCliError> {
+    let data = query_contract(
+        ctx,
+        chain_name,
+        token_addr,
+        json!({ "balance": { "address": address } }),
+    )
+    .await?;
+    data["balance"]
+        .as_str()
+        .and_then(|balance| balance.parse().ok())
+        .ok_or_else(|| {
+            CovenantCliError::DeserializeError(format!("unexpected cw20 balance: {}", data))
+        })
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ContractInfo {
+    pub code_id: String,
+    pub creator: String,
+    pub admin: String,
+    pub label: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ContractInfoResponse {
+    contract_info: ContractInfo,
+}
+
+/// Query the code id, admin and label of a contract
+pub async fn get_contract_info(
+    ctx: &CliContext,
+    chain_name: &str,
+    contract_addr: &str,
+) -> Result<ContractInfo, CovenantCliError> {
+    let response: ContractInfoResponse = ctx
+        .api_get(&format!(
+            "{}/{}/{}",
+            get_rest_url(chain_name),
+            COSMWASM_CONTRACT_API,
+            contract_addr
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    Ok(response.contract_info)
+}
+
+/// Contract name and version stored by cw2 at instantiation and migration
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ContractVersion {
+    pub contract: String,
+    pub version: String,
+}
+
+/// Query the cw2 contract name and version of a contract (raw storage query)
+pub async fn get_contract_version(
+    ctx: &CliContext,
+    chain_name: &str,
+    contract_addr: &str,
+) -> Result<ContractVersion, CovenantCliError> {
+    let json: Value = ctx
+        .api_get(&format!(
+            "{}/{}/{}/{}/{}",
+            get_rest_url(chain_name),
+            COSMWASM_CONTRACT_API,
+            contract_addr,
+            COSMWASM_RAW_QUERY,
+            URL_SAFE.encode(CW2_CONTRACT_INFO_KEY)
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    let data = json["data"]
+        .as_str()
+        .filter(|data| !data.is_empty())
+        .ok_or_else(|| CovenantCliError::LcdError("no cw2 contract info".to_owned()))?;
+    let data = STANDARD
+        .decode(data)
+        .map_err(|e| CovenantCliError::parse("cw2 contract info", e))?;
+    serde_json::from_slice(&data).map_err(|e| CovenantCliError::parse("cw2 contract info", e))
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ContractCodeHistoryEntry {
+    pub operation: String,
+    pub code_id: String,
+    pub msg: Value,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct ContractHistoryResponse {
+    entries: Vec<ContractCodeHistoryEntry>,
+}
+
+/// Query the instantiate and migrate messages executed on a contract
+pub async fn get_contract_history(
+    ctx: &CliContext,
+    chain_name: &str,
+    contract_addr: &str,
+) -> Result<Vec<ContractCodeHistoryEntry>, CovenantCliError> {
+    let response: ContractHistoryResponse = ctx
+        .api_get(&format!(
+            "{}/{}/{}/history",
+            get_rest_url(chain_name),
+            COSMWASM_CONTRACT_API,
+            contract_addr
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    Ok(response.entries)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct CodeContractsResponse {
+    contracts: Vec<String>,
+}
+
+/// Addresses of the latest `limit` contracts instantiated from a code id
+pub async fn get_code_contracts(
+    ctx: &CliContext,
+    chain_name: &str,
+    code_id: u64,
+    limit: u32,
+) -> Result<Vec<String>, CovenantCliError> {
+    let response: CodeContractsResponse = ctx
+        .api_get(&format!(
+            "{}/{}/{}/contracts?pagination.limit={}&pagination.reverse=true",
+            get_rest_url(chain_name),
+            COSMWASM_CODE_API,
+            code_id,
+            limit
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    Ok(response.contracts)
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TxSearchResponse {
+    txs: Vec<Value>,
+    tx_responses: Vec<TxSearchEntry>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct TxSearchEntry {
+    height: String,
+}
+
+/// Height of the latest transaction executing `execute_msg` (e.g. `tick`) on a contract,
+/// `None` when none of the latest executions of the contract is one
+pub async fn get_last_execute_height(
+    ctx: &CliContext,
+    chain_name: &str,
+    contract_addr: &str,
+    execute_msg: &str,
+) -> Result<Option<u128>, CovenantCliError> {
+    let response: TxSearchResponse = ctx
+        .api_get(&format!(
+            "{}/{}?query=execute._contract_address='{}'&order_by=ORDER_BY_DESC&pagination.limit={}",
+            get_rest_url(chain_name),
+            TX_SEARCH_API,
+            contract_addr,
+            EXECUTE_SEARCH_LIMIT
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    let Some(entry) = response
+        .txs
+        .iter()
+        .zip(&response.tx_responses)
+        .find(|(tx, _)| executes(tx, contract_addr, execute_msg))
+        .map(|(_, entry)| entry)
+    else {
+        return Ok(None);
+    };
+    entry
+        .height
+        .parse::<u128>()
+        .map(Some)
+        .map_err(|e| CovenantCliError::parse("tx height", e))
+}
+
+/// Whether a transaction executes `execute_msg` on a contract, the message is either
+/// rendered as JSON or base64 encoded
+fn executes(tx: &Value, contract_addr: &str, execute_msg: &str) -> bool {
+    let messages = tx["body"]["messages"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default();
+    messages.iter().any(|message| {
+        if message["contract"].as_str() != Some(contract_addr) {
+            return false;
+        }
+        let msg = match &message["msg"] {
+            Value::String(encoded) => STANDARD
+                .decode(encoded)
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+                .unwrap_or_default(),
+            msg => msg.clone(),
+        };
+        msg.get(execute_msg).is_some()
+    })
+}
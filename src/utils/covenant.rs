@@ -0,0 +1,188 @@
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+
+use crate::context::CliContext;
+use crate::error::CovenantCliError;
+use crate::utils::cosmwasm::query_contract;
+
+/// Query the address of a covenant child contract (e.g. `holder_address`)
+pub async fn get_covenant_child_address(
+    ctx: &CliContext,
+    chain_name: &str,
+    covenant_addr: &str,
+    query_name: &str,
+    query_args: Value,
+) -> Result<String, CovenantCliError> {
+    let data = query_contract(
+        ctx,
+        chain_name,
+        covenant_addr,
+        json!({ query_name: query_args }),
+    )
+    .await?;
+    data.as_str().map(|addr| addr.to_owned()).ok_or_else(|| {
+        CovenantCliError::LcdError(format!(
+            "{} not found for covenant {}",
+            query_name, covenant_addr
+        ))
+    })
+}
+
+/// Child contract address queries of the covenants, not all covenants answer all of them
+fn child_queries() -> Vec<(&'static str, &'static str, Value)> {
+    vec![
+        ("clock", "clock_address", json!({})),
+        ("holder", "holder_address", json!({})),
+        ("splitter", "splitter_address", json!({})),
+        ("liquid staker", "liquid_staker_address", json!({})),
+        ("liquid pooler", "liquid_pooler_address", json!({})),
+        (
+            "party A forwarder",
+            "ibc_forwarder_address",
+            json!({ "party": "party_a" }),
+        ),
+        (
+            "party B forwarder",
+            "ibc_forwarder_address",
+            json!({ "party": "party_b" }),
+        ),
+        (
+            "LS forwarder",
+            "ibc_forwarder_address",
+            json!({ "ty": "ls" }),
+        ),
+        (
+            "LP forwarder",
+            "ibc_forwarder_address",
+            json!({ "ty": "lp" }),
+        ),
+        (
+            "party A router",
+            "interchain_router_address",
+            json!({ "party": "party_a" }),
+        ),
+        (
+            "party B router",
+            "interchain_router_address",
+            json!({ "party": "party_b" }),
+        ),
+        ("router", "interchain_router_address", json!({})),
+    ]
+}
+
+/// Query the child contracts of a covenant, named as in `child_queries`
+pub async fn get_covenant_children(
+    ctx: &CliContext,
+    chain_name: &str,
+    covenant_addr: &str,
+) -> Vec<(&'static str, String)> {
+    let mut children = vec![];
+    for (name, query_name, query_args) in child_queries() {
+        if let Ok(addr) =
+            get_covenant_child_address(ctx, chain_name, covenant_addr, query_name, query_args).await
+        {
+            children.push((name, addr));
+        }
+    }
+    children
+}
+
+/// Query the state of a covenant holder contract (e.g. `active`, `expired`)
+pub async fn get_holder_state(
+    ctx: &CliContext,
+    chain_name: &str,
+    holder_addr: &str,
+) -> Result<String, CovenantCliError> {
+    let data = query_contract(
+        ctx,
+        chain_name,
+        holder_addr,
+        json!({ "contract_state": {} }),
+    )
+    .await?;
+    match data {
+        Value::String(state) => Ok(state),
+        Value::Object(state) => state
+            .keys()
+            .next()
+            .cloned()
+            .ok_or_else(|| CovenantCliError::LcdError("empty holder state".to_owned())),
+        _ => Err(CovenantCliError::LcdError(format!(
+            "unexpected holder state: {}",
+            data
+        ))),
+    }
+}
+
+/// Query the configuration of a covenant contract, most of them answer the same query
+pub async fn get_contract_config(
+    ctx: &CliContext,
+    chain_name: &str,
+    contract_addr: &str,
+) -> Result<Value, CovenantCliError> {
+    query_contract(ctx, chain_name, contract_addr, json!({ "config": {} })).await
+}
+
+/// Query the configuration of a two party covenant holder contract (parties, refunds)
+pub async fn get_holder_config(
+    ctx: &CliContext,
+    chain_name: &str,
+    holder_addr: &str,
+) -> Result<Value, CovenantCliError> {
+    get_contract_config(ctx, chain_name, holder_addr).await
+}
+
+/// Query the interchain account address of a covenant contract (e.g. a liquid staker)
+pub async fn get_ica_address(
+    ctx: &CliContext,
+    chain_name: &str,
+    contract_addr: &str,
+) -> Result<String, CovenantCliError> {
+    let data = query_contract(ctx, chain_name, contract_addr, json!({ "ica_address": {} })).await?;
+    data.as_str().map(|addr| addr.to_owned()).ok_or_else(|| {
+        CovenantCliError::LcdError(format!(
+            "ica address not found for contract {}",
+            contract_addr
+        ))
+    })
+}
+
+/// Query the receivers of the splits held by a covenant contract (holder or splitter)
+pub async fn get_split_receivers(
+    ctx: &CliContext,
+    chain_name: &str,
+    contract_addr: &str,
+    query: Value,
+) -> Result<BTreeSet<String>, CovenantCliError> {
+    let data = query_contract(ctx, chain_name, contract_addr, query).await?;
+    let mut receivers = BTreeSet::new();
+    collect_receivers(&data, &mut receivers);
+    Ok(receivers)
+}
+
+/// Query the denoms a router contract distributes
+pub async fn get_router_target_denoms(
+    ctx: &CliContext,
+    chain_name: &str,
+    router_addr: &str,
+) -> Result<BTreeSet<String>, CovenantCliError> {
+    let data = query_contract(ctx, chain_name, router_addr, json!({ "target_denoms": {} })).await?;
+    serde_json::from_value(data).map_err(|e| CovenantCliError::parse("covenant query response", e))
+}
+
+// Split configs are nested differently depending on the contract (explicit and
+// fallback splits, per denom lists), only their `receivers` maps matter
+fn collect_receivers(value: &Value, receivers: &mut BTreeSet<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                match (key.as_str(), value) {
+                    ("receivers", Value::Object(split)) => receivers.extend(split.keys().cloned()),
+                    _ => collect_receivers(value, receivers),
+                }
+            }
+        }
+        Value::Array(values) => values.iter().for_each(|v| collect_receivers(v, receivers)),
+        _ => {}
+    }
+}
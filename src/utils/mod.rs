@@ -1,5 +1,8 @@
 pub mod assets;
 pub mod astroport;
 pub mod chain;
+pub mod cosmwasm;
+pub mod covenant;
 pub mod neutron;
 pub mod path;
+pub mod persistence;
@@ -2,6 +2,9 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::context::CliContext;
+use crate::error::CovenantCliError;
+use crate::utils::chain::{get_rest_url, get_rpc_url};
+use crate::validations::NEUTRON_CHAIN_NAME;
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(default)]
@@ -11,15 +14,55 @@ pub struct BlockHeader {
     pub time: String,
 }
 
-pub async fn get_latest_block(ctx: &CliContext) -> Result<u128, anyhow::Error> {
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct FeeRefunderParams {
+    pub min_fee: MinFee,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct MinFee {
+    pub recv_fee: Vec<FeeCoin>,
+    pub ack_fee: Vec<FeeCoin>,
+    pub timeout_fee: Vec<FeeCoin>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct FeeCoin {
+    pub denom: String,
+    pub amount: String,
+}
+
+pub async fn get_latest_block(
+    ctx: &CliContext,
+    chain_name: &str,
+) -> Result<u128, CovenantCliError> {
     let mut json: Value = ctx
-        .api_get("https://neutron-tw-rpc.polkachu.com:443/block")
-        .await?;
+        .api_get(&format!("{}/block", get_rpc_url(chain_name)))
+        .await
+        .map_err(CovenantCliError::lcd)?;
     let header_obj = json["result"]["block"]["header"].take();
     let header: BlockHeader = serde_json::from_value(header_obj).unwrap_or_default();
 
     header
         .height
         .parse::<u128>()
-        .map_err(|e| anyhow::anyhow!("Error parsing block height: {:?}", e))
+        .map_err(|e| CovenantCliError::parse("block height", e))
+}
+
+pub async fn get_fee_refunder_params(
+    ctx: &CliContext,
+) -> Result<FeeRefunderParams, CovenantCliError> {
+    let mut json: Value = ctx
+        .api_get(&format!(
+            "{}/neutron-org/neutron/feerefunder/params",
+            get_rest_url(NEUTRON_CHAIN_NAME)
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    let params_obj = json["params"].take();
+    serde_json::from_value(params_obj)
+        .map_err(|e| CovenantCliError::parse("fee refunder params", e))
 }
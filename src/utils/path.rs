@@ -1,10 +1,17 @@
-use anyhow::Error;
 use serde::{Deserialize, Serialize};
 
 use crate::context::CliContext;
+use crate::error::CovenantCliError;
+use crate::utils::chain;
 
 const GIT_REF: &str = "HEAD";
-const RAW_FILE_REPO_URL: &str = "https://raw.githubusercontent.com/cosmos/chain-registry";
+pub(crate) const RAW_FILE_REPO_URL: &str =
+    "https://raw.githubusercontent.com/cosmos/chain-registry";
+pub(crate) const REPO_COMMITS_URL: &str =
+    "https://api.github.com/repos/cosmos/chain-registry/commits";
+const TRANSFER_CHANNEL_ORDERING: &str = "unordered";
+// cw20-ics20 contracts bound to `wasm.` ports negotiate the same ICS20 version
+const TRANSFER_CHANNEL_VERSION: &str = "ics20-1";
 
 // Inspired by https://github.com/PeggyJV/chain-registry
 
@@ -34,6 +41,38 @@ pub struct ChannelInfo {
     pub tags: Tags,
 }
 
+impl IBCPath {
+    /// Find the channel identified by `channel_id` on the `chain_name` end
+    pub fn find_channel(&self, chain_name: &str, channel_id: &str) -> Option<&ChannelInfo> {
+        self.channels.iter().find(|c| {
+            if self.chain_1.chain_name == chain_name {
+                c.chain_1.channel_id == channel_id
+            } else {
+                c.chain_2.channel_id == channel_id
+            }
+        })
+    }
+}
+
+impl ChannelInfo {
+    /// Check that the registry entry describes an unordered ICS20 transfer channel
+    pub fn check_transfer_channel(&self) -> Result<(), String> {
+        if self.ordering != TRANSFER_CHANNEL_ORDERING {
+            return Err(format!(
+                "invalid channel ordering: expected {} | actual {}",
+                TRANSFER_CHANNEL_ORDERING, self.ordering
+            ));
+        }
+        if self.version != TRANSFER_CHANNEL_VERSION {
+            return Err(format!(
+                "invalid channel version: expected {} | actual {}",
+                TRANSFER_CHANNEL_VERSION, self.version
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default, rename_all = "snake_case")]
 pub struct ChannelPort {
@@ -71,25 +110,69 @@ pub struct Content {
     pub type_field: String,
 }
 
+#[derive(Debug, Deserialize)]
+struct Commit {
+    sha: String,
+}
+
+/// Resolve the chain registry commit the files are read from
+pub(crate) async fn get_registry_commit(ctx: &CliContext) -> Result<String, CovenantCliError> {
+    let commit: Commit = ctx
+        .api_get(&format!("{}/{}", REPO_COMMITS_URL, GIT_REF))
+        .await
+        .map_err(CovenantCliError::registry)?;
+    Ok(commit.sha)
+}
+
 pub(crate) async fn get_path_info(
-    _ctx: &CliContext,
+    ctx: &CliContext,
     chain_a: &str,
     chain_b: &str,
-) -> Result<IBCPath, Error> {
+) -> Result<IBCPath, CovenantCliError> {
     let path = format!(
         "_IBC/{}-{}.json",
         chain_a.min(chain_b),
         chain_a.max(chain_b)
     );
 
-    let data = get_file_content(GIT_REF, &path).await?;
+    let data = get_file_content(ctx, GIT_REF, &path).await?;
     let path: IBCPath = serde_json::from_str(&data).unwrap_or_default();
 
     Ok(path)
 }
 
-async fn get_file_content(r#ref: &str, path: &str) -> Result<String, Error> {
+/// Registry `chain.json` of the chain
+pub(crate) async fn get_registry_chain_info(
+    ctx: &CliContext,
+    chain_name: &str,
+) -> Result<chain::ChainInfo, CovenantCliError> {
+    let data = get_file_content(ctx, GIT_REF, &format!("{}/chain.json", chain_name)).await?;
+    serde_json::from_str(&data).map_err(CovenantCliError::registry)
+}
+
+async fn get_file_content(
+    ctx: &CliContext,
+    r#ref: &str,
+    path: &str,
+) -> Result<String, CovenantCliError> {
+    // Local patches (e.g. a new channel not merged upstream yet) take precedence
+    if let Some(local_registry) = ctx.local_registry() {
+        let local_file = local_registry.join(path);
+        if local_file.is_file() {
+            let content =
+                std::fs::read_to_string(&local_file).map_err(CovenantCliError::registry)?;
+            ctx.record_registry_source(path, "local");
+            ctx.audit(&local_file.display().to_string(), &content, "local");
+            return Ok(content);
+        }
+    }
     let url = format!("{}/{}/{}", RAW_FILE_REPO_URL, r#ref, path);
-    let response = reqwest::get(url).await?; //.text().await?
-    response.text().await.map_err(Error::from)
+    let content = ctx
+        .api_get_text(&url)
+        .await
+        .map_err(CovenantCliError::registry)?;
+    if ctx.local_registry().is_some() {
+        ctx.record_registry_source(path, "remote");
+    }
+    Ok(content)
 }
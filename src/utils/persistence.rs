@@ -0,0 +1,112 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::str::FromStr;
+
+use crate::context::CliContext;
+use crate::error::CovenantCliError;
+use crate::utils::chain::get_rest_url;
+use crate::validations::PERSISTENCE_CHAIN_NAME;
+
+/// Prefix of the liquid staking denoms minted by pSTAKE
+pub const STK_DENOM_PREFIX: &str = "stk/";
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct HostChain {
+    pub chain_id: String,
+    pub connection_id: String,
+    pub host_denom: String,
+    pub channel_id: String,
+    pub port_id: String,
+    /// stkTokens minted per staked token
+    pub c_value: String,
+    /// Number of undelegation epochs a redemption waits for
+    pub unbonding_factor: String,
+    pub active: bool,
+}
+
+impl HostChain {
+    /// Staked tokens redeemed per stkToken, none for an unset or zero c value
+    pub fn exchange_rate(&self) -> Option<Decimal> {
+        let c_value = Decimal::from_str(&self.c_value).ok()?;
+        Decimal::ONE.checked_div(c_value)
+    }
+}
+
+/// Bounds of the c value of the host chains, outside of which the module pauses them
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+pub struct LiquidStakeParams {
+    pub upper_c_value_limit: String,
+    pub lower_c_value_limit: String,
+    pub module_paused: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(default)]
+struct StakingParams {
+    unbonding_time: String,
+}
+
+/// Liquid staking denom minted on Persistence for a host chain base denom
+pub fn stk_denom(host_denom: &str) -> String {
+    format!("{}{}", STK_DENOM_PREFIX, host_denom)
+}
+
+/// Host chain registered in the liquidstakeibc module
+pub async fn get_host_chain(
+    ctx: &CliContext,
+    chain_id: &str,
+) -> Result<HostChain, CovenantCliError> {
+    let mut json: Value = ctx
+        .api_get(&format!(
+            "{}/pstake/liquidstakeibc/v1beta1/host_chain/{}",
+            get_rest_url(PERSISTENCE_CHAIN_NAME),
+            chain_id
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    let host_chain_obj = json["host_chain"].take();
+    let host_chain: HostChain = serde_json::from_value(host_chain_obj).unwrap_or_default();
+    Ok(host_chain)
+}
+
+/// Parameters of the liquidstakeibc module
+pub async fn get_liquid_stake_params(
+    ctx: &CliContext,
+) -> Result<LiquidStakeParams, CovenantCliError> {
+    let mut json: Value = ctx
+        .api_get(&format!(
+            "{}/pstake/liquidstakeibc/v1beta1/params",
+            get_rest_url(PERSISTENCE_CHAIN_NAME)
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    let params_obj = json["params"].take();
+    let params: LiquidStakeParams = serde_json::from_value(params_obj).unwrap_or_default();
+    Ok(params)
+}
+
+/// Unbonding period of a host chain in seconds, redemptions wait at least as long
+pub async fn get_unbonding_period(
+    ctx: &CliContext,
+    chain_name: &str,
+) -> Result<u64, CovenantCliError> {
+    let mut json: Value = ctx
+        .api_get(&format!(
+            "{}/cosmos/staking/v1beta1/params",
+            get_rest_url(chain_name)
+        ))
+        .await
+        .map_err(CovenantCliError::lcd)?;
+    let params_obj = json["params"].take();
+    let params: StakingParams = serde_json::from_value(params_obj).unwrap_or_default();
+    // Durations are serialized as seconds with an `s` suffix (e.g. `1814400s`)
+    params
+        .unbonding_time
+        .trim_end_matches('s')
+        .parse::<f64>()
+        .map(|seconds| seconds as u64)
+        .map_err(|e| CovenantCliError::parse("unbonding time", e))
+}
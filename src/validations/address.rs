@@ -0,0 +1,326 @@
+use cosmwasm_std::Decimal;
+use itertools::Itertools;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::CovenantValidationContext;
+use crate::utils::covenant::{
+    get_covenant_child_address, get_holder_config, get_router_target_denoms, get_split_receivers,
+};
+
+/// Check that an address is bech32 encoded with the expected human readable part
+pub fn check_address(addr: &str, expected_prefix: &str) -> Result<(), String> {
+    let (hrp, _) = bech32::decode(addr).map_err(|e| format!("invalid address: {}", e))?;
+    if hrp.as_str() != expected_prefix {
+        return Err(format!(
+            "invalid address prefix: expected {} | actual {}",
+            expected_prefix,
+            hrp.as_str()
+        ));
+    }
+    Ok(())
+}
+
+pub fn verify_address<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    addr: &str,
+    expected_prefix: &str,
+) -> bool {
+    match check_address(addr, expected_prefix) {
+        Ok(()) => {
+            ctx.valid_field(key, field, "verified".to_owned());
+            true
+        }
+        Err(e) => {
+            ctx.invalid_field(key, field, e);
+            false
+        }
+    }
+}
+
+/// Check that the receivers of a denom split are distinct party receivers with a share
+pub fn verify_split_entries<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    denom: &str,
+    receivers: &BTreeMap<String, Decimal>,
+    party_receivers: &[String],
+) {
+    let mut errors = vec![];
+    for (receiver, share) in receivers {
+        if share.is_zero() {
+            errors.push(format!("{}: zero share", receiver));
+        }
+        if !party_receivers.contains(receiver) {
+            errors.push(format!("{}: not a party receiver", receiver));
+        }
+    }
+    // Bech32 addresses are case insensitive, the map keys are not
+    for (receiver, count) in receivers.keys().map(|r| r.to_lowercase()).counts() {
+        if count > 1 {
+            errors.push(format!("{}: duplicated receiver", receiver));
+        }
+    }
+    if errors.is_empty() {
+        ctx.valid(
+            key,
+            format!("{}: {} distinct receivers", denom, receivers.len()),
+        );
+    } else {
+        ctx.invalid(key, format!("{}: {}", denom, errors.join(", ")));
+    }
+}
+
+/// Check that the splits of a deployed covenant only pay out to its own router contracts
+///
+/// `splits_contract` is the child contract holding the splits and the query returning them.
+pub async fn verify_deployed_split_receivers<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    covenant_addr: &str,
+    splits_contract: (&str, Value),
+) {
+    let host_chain_name = ctx.host_chain_name();
+    let mut routers = vec![];
+    for party in ["party_a", "party_b"] {
+        match get_covenant_child_address(
+            &ctx.cli_context,
+            &host_chain_name,
+            covenant_addr,
+            "interchain_router_address",
+            json!({ "party": party }),
+        )
+        .await
+        {
+            Ok(router) => routers.push(router),
+            Err(e) => {
+                ctx.invalid(
+                    key,
+                    format!("failed to resolve the {} router: {}", party, e),
+                );
+                return;
+            }
+        }
+    }
+
+    let (query_name, query) = splits_contract;
+    let receivers = match get_covenant_child_address(
+        &ctx.cli_context,
+        &host_chain_name,
+        covenant_addr,
+        query_name,
+        json!({}),
+    )
+    .await
+    {
+        Ok(addr) => get_split_receivers(&ctx.cli_context, &host_chain_name, &addr, query).await,
+        Err(e) => Err(e),
+    };
+    match receivers {
+        Ok(receivers) => {
+            for receiver in receivers {
+                if routers.contains(&receiver) {
+                    ctx.valid(
+                        key,
+                        format!("deployed receiver {}: covenant router", receiver),
+                    );
+                } else {
+                    ctx.invalid(
+                        key,
+                        format!(
+                            "deployed receiver {}: not a router of the covenant ({})",
+                            receiver,
+                            routers.join(", ")
+                        ),
+                    );
+                }
+            }
+        }
+        Err(e) => ctx.invalid(key, format!("failed to query the deployed splits: {}", e)),
+    }
+}
+
+/// Check that the routers of a deployed covenant distribute exactly the covenant denoms, a
+/// denom missing from a router stays stranded in it
+pub async fn verify_deployed_router_denoms<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    covenant_addr: &str,
+    expected_denoms: &BTreeSet<String>,
+) {
+    let host_chain_name = ctx.host_chain_name();
+    for party in ["party_a", "party_b"] {
+        let router = match get_covenant_child_address(
+            &ctx.cli_context,
+            &host_chain_name,
+            covenant_addr,
+            "interchain_router_address",
+            json!({ "party": party }),
+        )
+        .await
+        {
+            Ok(router) => router,
+            Err(e) => {
+                ctx.invalid(
+                    key,
+                    format!("failed to resolve the {} router: {}", party, e),
+                );
+                continue;
+            }
+        };
+        let denoms =
+            match get_router_target_denoms(&ctx.cli_context, &host_chain_name, &router).await {
+                Ok(denoms) => denoms,
+                Err(e) => {
+                    ctx.invalid(
+                        key,
+                        format!("failed to query the {} router denoms: {}", party, e),
+                    );
+                    continue;
+                }
+            };
+        let missing = expected_denoms.difference(&denoms).join(", ");
+        let extra = denoms.difference(expected_denoms).join(", ");
+        match (missing.is_empty(), extra.is_empty()) {
+            (true, true) => ctx.valid(
+                key,
+                format!(
+                    "{} router {} denoms: {}",
+                    party,
+                    router,
+                    denoms.iter().join(", ")
+                ),
+            ),
+            (false, _) => ctx.invalid(
+                key,
+                format!(
+                    "{} router {} misses denoms {}: their funds would be stranded",
+                    party, router, missing
+                ),
+            ),
+            (true, false) => ctx.invalid(
+                key,
+                format!(
+                    "{} router {} routes unexpected denoms {}",
+                    party, router, extra
+                ),
+            ),
+        }
+    }
+}
+
+/// Route the deposit of a party is expected to be refunded over
+pub struct ExpectedRefund {
+    pub receiver: String,
+    /// Host to party chain channel of an interchain party, none for a native party
+    pub channel_id: Option<String>,
+    /// Denom of the party deposit on the host chain
+    pub denom: String,
+}
+
+/// Check the refund configs of the deployed holder send partially arrived deposits back to
+/// the party receivers, over the party channels and in the party denoms
+pub async fn verify_deployed_refund_configs<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    covenant_addr: &str,
+    parties: [(&'a str, ExpectedRefund); 2],
+) {
+    let host_chain_name = ctx.host_chain_name();
+    let config = match get_covenant_child_address(
+        &ctx.cli_context,
+        &host_chain_name,
+        covenant_addr,
+        "holder_address",
+        json!({}),
+    )
+    .await
+    {
+        Ok(holder) => get_holder_config(&ctx.cli_context, &host_chain_name, &holder).await,
+        Err(e) => Err(e),
+    };
+    let config = match config {
+        Ok(config) => config,
+        Err(e) => {
+            for (key, _) in parties {
+                ctx.invalid_field(
+                    key,
+                    "refund_config",
+                    format!("failed to query the holder config: {}", e),
+                );
+            }
+            return;
+        }
+    };
+
+    for (key, expected) in parties {
+        let party = &config[key.trim_end_matches("_config")];
+        let denom = party["native_denom"]
+            .as_str()
+            .or(party["contribution"]["denom"].as_str())
+            .unwrap_or_default();
+        if denom == expected.denom {
+            ctx.valid_field(key, "refund_config", format!("refund denom {}", denom));
+        } else {
+            ctx.invalid_field(
+                key,
+                "refund_config",
+                format!(
+                    "invalid refund denom: expected {} | actual {}",
+                    expected.denom, denom
+                ),
+            );
+        }
+
+        let (receiver, channel_id) = match &party["refund_config"] {
+            Value::Object(refund) if refund.contains_key("native") => {
+                (refund["native"].as_str(), None)
+            }
+            Value::Object(refund) if refund.contains_key("ibc") => (
+                refund["ibc"]["destination_receiver_addr"].as_str(),
+                refund["ibc"]["local_to_destination_chain_channel_id"].as_str(),
+            ),
+            refund_config => {
+                ctx.invalid_field(
+                    key,
+                    "refund_config",
+                    format!("unexpected refund config: {}", refund_config),
+                );
+                continue;
+            }
+        };
+        let receiver = receiver.unwrap_or_default();
+        if receiver == expected.receiver {
+            ctx.valid_field(
+                key,
+                "refund_config",
+                format!("refund receiver {}", receiver),
+            );
+        } else {
+            ctx.invalid_field(
+                key,
+                "refund_config",
+                format!(
+                    "invalid refund receiver: expected {} | actual {}",
+                    expected.receiver, receiver
+                ),
+            );
+        }
+        match (expected.channel_id.as_deref(), channel_id) {
+            (None, None) => {}
+            (Some(expected_channel), Some(channel)) if expected_channel == channel => {
+                ctx.valid_field(key, "refund_config", format!("refund channel {}", channel));
+            }
+            (expected_channel, channel) => ctx.invalid_field(
+                key,
+                "refund_config",
+                format!(
+                    "invalid refund channel: expected {} | actual {}",
+                    expected_channel.unwrap_or("none (native party)"),
+                    channel.unwrap_or("none (native refund)")
+                ),
+            ),
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use serde_json::Value;
+
+use super::CovenantValidationContext;
+use crate::allowlist::{Allowlist, SignatureStatus};
+
+/// Check the receiver and fallback addresses of each party match its allowlist entry and
+/// the entry is signed by the trusted key of the party
+pub fn verify_allowlist(
+    ctx: &mut CovenantValidationContext<'_>,
+    allowlist: &Allowlist,
+    instantiation: &Value,
+) {
+    for (key, party_name) in [
+        ("covenant_party_config", ctx.party_a_name()),
+        ("party_a_config", ctx.party_a_name()),
+        ("party_b_config", ctx.party_b_name()),
+    ] {
+        let Some(party_config) = instantiation.get(key) else {
+            continue;
+        };
+        // Interchain and native parties are wrapped in their variant
+        let party_config = match party_config.get("party_receiver_addr") {
+            Some(_) => party_config,
+            None => match party_config.as_object().and_then(|o| o.values().next()) {
+                Some(party_config) => party_config,
+                None => continue,
+            },
+        };
+        let Some(party_name) = party_name else {
+            ctx.warn(
+                key,
+                "party without a name in the metadata: allowlist skipped".to_owned(),
+            );
+            continue;
+        };
+        let Some(entry) = allowlist.get(&party_name) else {
+            ctx.invalid_field(
+                key,
+                "party_receiver_addr",
+                format!("party {} missing from the allowlist", party_name),
+            );
+            continue;
+        };
+
+        for (field, expected) in [
+            ("party_receiver_addr", Some(&entry.receiver_address)),
+            ("fallback_address", entry.fallback_address.as_ref()),
+        ] {
+            let Some(expected) = expected else {
+                continue;
+            };
+            let actual = party_config
+                .get(field)
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if actual == expected {
+                ctx.valid_field(
+                    key,
+                    field,
+                    format!("{} allowlisted by {}", actual, party_name),
+                );
+            } else {
+                ctx.invalid_field(
+                    key,
+                    field,
+                    format!(
+                        "address not allowlisted: expected {} | actual {}",
+                        expected, actual
+                    ),
+                );
+            }
+        }
+
+        let trusted_key = ctx
+            .cli_context()
+            .config()
+            .party_keys
+            .get(&party_name)
+            .cloned();
+        match entry.verify_signature(&party_name, trusted_key.as_deref()) {
+            SignatureStatus::Verified => ctx.valid(
+                key,
+                format!(
+                    "allowlist entry signed by the trusted key of {}",
+                    party_name
+                ),
+            ),
+            SignatureStatus::Unverified => ctx.warn(
+                key,
+                format!(
+                    "unverified allowlist signature of {}: no trusted key in the config file",
+                    party_name
+                ),
+            ),
+            SignatureStatus::Invalid(e) => ctx.invalid(
+                key,
+                format!("invalid allowlist signature of {}: {}", party_name, e),
+            ),
+            SignatureStatus::Unsigned => {
+                ctx.warn(key, format!("unsigned allowlist entry of {}", party_name))
+            }
+        }
+    }
+}
@@ -1,17 +1,16 @@
-use crate::{
-    utils::astroport::{
-        get_astroport_pair_info, get_astroport_pool_info, CustomPair, StablePair, XykPair,
-    },
-    verify_equals,
+use crate::error::CovenantCliError;
+use crate::utils::astroport::{
+    get_astroport_factory_pairs, get_astroport_pair_info, get_astroport_pcl_params,
+    get_astroport_pool_info, CustomPair, PairInfo, StablePair, XykPair,
+    NEUTRON_FACTORY_CONTRACT_ADDRESS, PCL_PAIR_TYPE,
 };
-use anyhow::Error;
 use astroport_liquid_pooler::msg::AstroportLiquidPoolerConfig;
 use covenant_utils::PoolPriceConfig;
 use log::debug;
-use rust_decimal::{Decimal, RoundingStrategy};
+use rust_decimal::Decimal;
 use std::ops::Range;
 
-use super::CovenantValidationContext;
+use super::{CovenantValidationContext, NEUTRON_CHAIN_NAME};
 
 #[allow(clippy::too_many_arguments)]
 pub async fn verify_astroport_liquid_pooler_config<'a>(
@@ -23,13 +22,20 @@ pub async fn verify_astroport_liquid_pooler_config<'a>(
     asset_b_contribution: Decimal,
     lp_cfg: &AstroportLiquidPoolerConfig,
     pool_price_cfg: &PoolPriceConfig,
-    single_side_lp_limit_pct: u32,
-) -> Result<(), Error> {
+    (asset_a_limit_pct, asset_b_limit_pct): (u32, u32),
+) -> Result<(), CovenantCliError> {
     let mut key = key;
     let mut field = "pool_address";
-    let pair_info = get_astroport_pair_info(&ctx.cli_context, &lp_cfg.pool_address).await?;
+    let host_chain_name = ctx.host_chain_name();
+    let pair_info =
+        get_astroport_pair_info(&ctx.cli_context, &host_chain_name, &lp_cfg.pool_address).await?;
     debug!("astroport pair info: {:?}", pair_info);
-    ctx.valid_field(key, field, "verified".to_owned());
+    verify_factory_registration(ctx, key, field, &lp_cfg.pool_address, &pair_info).await;
+    let is_pcl = pair_info
+        .pair_type
+        .custom
+        .as_ref()
+        .is_some_and(|CustomPair(custom_type)| custom_type == PCL_PAIR_TYPE);
 
     field = "pool_pair_type";
     debug!(
@@ -58,7 +64,7 @@ pub async fn verify_astroport_liquid_pooler_config<'a>(
     }
 
     field = "asset_a_denom";
-    let pair_asset_a = pair_info.asset_infos.first().unwrap();
+    let pair_asset_a = pair_info.asset_infos.first().cloned().unwrap_or_default();
     let pair_asset_a_denom = pair_asset_a
         .native_token
         .as_ref()
@@ -89,7 +95,7 @@ pub async fn verify_astroport_liquid_pooler_config<'a>(
     }
 
     field = "asset_b_denom";
-    let asset_b = pair_info.asset_infos.last().unwrap();
+    let asset_b = pair_info.asset_infos.last().cloned().unwrap_or_default();
     let asset_b_denom = asset_b
         .native_token
         .as_ref()
@@ -119,26 +125,33 @@ pub async fn verify_astroport_liquid_pooler_config<'a>(
 
     // Pool price config
     key = "pool_price_config";
-    let pool_info = get_astroport_pool_info(&ctx.cli_context, &lp_cfg.pool_address).await?;
+    let pool_info =
+        get_astroport_pool_info(&ctx.cli_context, &host_chain_name, &lp_cfg.pool_address).await?;
     debug!("astroport pool info: {:?}", pool_info);
 
     let asset_a_pool_amount = pool_info
         .assets
         .first()
-        .unwrap()
-        .amount
-        .parse::<u128>()
-        .unwrap();
+        .and_then(|a| a.amount.parse::<u128>().ok())
+        .unwrap_or_default();
     let asset_b_pool_amount = pool_info
         .assets
         .last()
-        .unwrap()
-        .amount
-        .parse::<u128>()
-        .unwrap();
-    let current_pool_price = Decimal::from(asset_a_pool_amount)
-        .checked_div(Decimal::from(asset_b_pool_amount))
+        .and_then(|a| a.amount.parse::<u128>().ok())
         .unwrap_or_default();
+    let current_pool_price = if is_pcl {
+        // PCL pools concentrate the liquidity around their price scale, the reserves
+        // ratio is not the pool price
+        let pcl_params =
+            get_astroport_pcl_params(&ctx.cli_context, &host_chain_name, &lp_cfg.pool_address)
+                .await?;
+        debug!("astroport pcl params: {:?}", pcl_params);
+        pcl_params.price_scale.parse::<Decimal>()?
+    } else {
+        Decimal::from(asset_a_pool_amount)
+            .checked_div(Decimal::from(asset_b_pool_amount))
+            .unwrap_or_default()
+    };
     debug!(
         "pool_price_config/current pool price: {} / {} = {}",
         asset_a_pool_amount, asset_b_pool_amount, current_pool_price
@@ -182,6 +195,14 @@ pub async fn verify_astroport_liquid_pooler_config<'a>(
             ),
         );
     }
+    verify_price_direction(
+        ctx,
+        key,
+        field,
+        expected_spot_price,
+        current_pool_price,
+        (&pair_asset_a_denom, &asset_b_denom),
+    );
 
     field = "acceptable_price_spread";
     // Compute acceptable price spread based on expected spot price
@@ -209,62 +230,422 @@ pub async fn verify_astroport_liquid_pooler_config<'a>(
     );
     ctx.valid_field(key, field, format!("{:.0}%", acceptable_price_spread_pct));
 
-    // Ensure Single Side LP limits are within 10% for party contributions
+    // Contributions in the pool asset order
+    let (pool_asset_a_contribution, pool_asset_b_contribution) =
+        if pair_asset_a_denom == asset_a_denom {
+            (asset_a_contribution, asset_b_contribution)
+        } else {
+            (asset_b_contribution, asset_a_contribution)
+        };
+    verify_pool_share(
+        ctx,
+        key,
+        &pool_info.total_share,
+        (
+            pool_asset_a_contribution,
+            Decimal::from(asset_a_pool_amount),
+        ),
+        (
+            pool_asset_b_contribution,
+            Decimal::from(asset_b_pool_amount),
+        ),
+    );
+
+    // Ensure Single Side LP limits are within the limit (10% by default) of each contribution
     key = "liquid_pooler_config";
-    field = "single_side_lp_limits_asset_a";
-    let asset_a_limit = Decimal::from(lp_cfg.single_side_lp_limits.asset_a_limit.u128());
-    debug!("{}", single_side_lp_limit_pct);
-    debug!("{}", Decimal::new(single_side_lp_limit_pct as i64, 2));
-    let expected_asset_a_limit = asset_a_contribution
-        .checked_sub(
-            asset_a_contribution
-                .checked_mul(Decimal::new(single_side_lp_limit_pct as i64, 2))
-                .unwrap(),
-        )
-        .unwrap()
-        .round_dp_with_strategy(0, RoundingStrategy::AwayFromZero);
-    debug!(
-        "liquid_pooler_config/single_side_lp_limits_asset_a: expected {} | actual {}",
-        expected_asset_a_limit, lp_cfg.single_side_lp_limits.asset_a_limit
+    verify_single_side_lp_limit(
+        ctx,
+        key,
+        "single_side_lp_limits_asset_a",
+        asset_a_contribution,
+        asset_a_limit_pct,
+        Decimal::from(lp_cfg.single_side_lp_limits.asset_a_limit.u128()),
+        is_pcl,
     );
-    
-    verify_equals!(
+    verify_single_side_lp_limit(
         ctx,
         key,
-        field,
-        expected_asset_a_limit,
-        asset_a_limit,
-        "invalid single side lp limit: expected {} | actual {}"
+        "single_side_lp_limits_asset_b",
+        asset_b_contribution,
+        asset_b_limit_pct,
+        Decimal::from(lp_cfg.single_side_lp_limits.asset_b_limit.u128()),
+        is_pcl,
     );
 
-    field = "single_side_lp_limits_asset_b";
-    let asset_b_limit = Decimal::from(lp_cfg.single_side_lp_limits.asset_b_limit.u128());
+    let slippage_tolerance = lp_cfg
+        .slippage_tolerance
+        .map(|tolerance| {
+            Decimal::try_from_i128_with_scale(tolerance.atomics().u128().try_into()?, 18)
+        })
+        .transpose()?;
+    verify_slippage_tolerance(ctx, key, slippage_tolerance, acceptable_price_spread_pct);
+
+    Ok(())
+}
+
+/// Estimate the share of the pool the covenant would own once its liquidity is provided,
+/// from the (contribution, pool reserve) of each asset
+fn verify_pool_share<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    total_share: &str,
+    asset_a: (Decimal, Decimal),
+    asset_b: (Decimal, Decimal),
+) {
+    let field = "pool_share";
+    let (Some(ratio_a), Some(ratio_b)) = (
+        asset_a.0.checked_div(asset_a.1),
+        asset_b.0.checked_div(asset_b.1),
+    ) else {
+        ctx.warn_field(
+            key,
+            field,
+            "empty pool: the covenant would own all of its liquidity".to_owned(),
+        );
+        return;
+    };
+    // LP tokens are minted for the smallest contribution to reserve ratio,
+    // the surplus of the other asset is not provided
+    let minted_ratio = ratio_a.min(ratio_b);
+    let share_pct = minted_ratio / (Decimal::ONE + minted_ratio) * Decimal::ONE_HUNDRED;
     debug!(
-        "{}",
-        asset_b_contribution
-            .checked_mul(Decimal::new(single_side_lp_limit_pct as i64, 2))
-            .unwrap()
+        "pool_price_config/pool_share: {:.2}% of {} total shares",
+        share_pct, total_share
+    );
+    let max_pct = ctx.max_pool_share_pct();
+    let message = format!(
+        "{:.2}% of the pool ({} total shares)",
+        share_pct, total_share
     );
-    let expected_asset_b_limit = asset_b_contribution
+    if share_pct > Decimal::from(max_pct) {
+        ctx.warn_field(
+            key,
+            field,
+            format!("{} -> more than {}% of the pool", message, max_pct),
+        );
+    } else {
+        ctx.valid_field(key, field, message);
+    }
+}
+
+/// Check the slippage tolerance of the liquidity provision is within the accepted range
+/// and does not exceed the acceptable price spread
+fn verify_slippage_tolerance<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    slippage_tolerance: Option<Decimal>,
+    acceptable_price_spread_pct: Decimal,
+) {
+    let field = "slippage_tolerance";
+    let Some(slippage_tolerance_pct) = slippage_tolerance.map(|t| t * Decimal::ONE_HUNDRED) else {
+        ctx.warn_field(
+            key,
+            field,
+            "not set: the liquid pooler default slippage tolerance applies".to_owned(),
+        );
+        return;
+    };
+    debug!(
+        "liquid_pooler_config/slippage_tolerance: {}%",
+        slippage_tolerance_pct
+    );
+    let (min_pct, max_pct) = ctx.slippage_tolerance_bounds_pct();
+    if slippage_tolerance_pct.is_zero() {
+        ctx.warn_field(
+            key,
+            field,
+            "0%: any price movement will fail the liquidity provision".to_owned(),
+        );
+    } else if slippage_tolerance_pct < min_pct || slippage_tolerance_pct > max_pct {
+        ctx.invalid_field(
+            key,
+            field,
+            format!(
+                "invalid slippage tolerance {}%: should be within {}%-{}%",
+                slippage_tolerance_pct.normalize(),
+                min_pct,
+                max_pct
+            ),
+        );
+    } else if slippage_tolerance_pct > acceptable_price_spread_pct {
+        ctx.warn_field(
+            key,
+            field,
+            format!(
+                "{}% exceeds the acceptable price spread ({:.0}%)",
+                slippage_tolerance_pct.normalize(),
+                acceptable_price_spread_pct
+            ),
+        );
+    } else {
+        ctx.valid_field(
+            key,
+            field,
+            format!("{}%", slippage_tolerance_pct.normalize()),
+        );
+    }
+}
+
+/// Check that the pool is a pair registered in the Astroport factory with the same assets
+/// and pair type, and not a contract mimicking the pair query
+async fn verify_factory_registration<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    pool_address: &str,
+    pair_info: &PairInfo,
+) {
+    let host_chain_name = ctx.host_chain_name();
+    let factory_address = match ctx.astroport_factory_address() {
+        Some(address) => address,
+        None if host_chain_name == NEUTRON_CHAIN_NAME => {
+            NEUTRON_FACTORY_CONTRACT_ADDRESS.to_owned()
+        }
+        None => {
+            ctx.warn_field(
+                key,
+                field,
+                format!(
+                    "factory registration not verified: no astroport_factory_address for {}",
+                    host_chain_name
+                ),
+            );
+            return;
+        }
+    };
+    match get_astroport_factory_pairs(
+        &ctx.cli_context,
+        &host_chain_name,
+        &factory_address,
+        &pair_info.asset_infos,
+    )
+    .await
+    {
+        Ok(pairs) => match pairs.iter().find(|pair| pair.contract_addr == pool_address) {
+            Some(pair) if pair.pair_type == pair_info.pair_type => {
+                ctx.valid_field(key, field, "registered in the Astroport factory".to_owned())
+            }
+            Some(_) => ctx.invalid_field(
+                key,
+                field,
+                "invalid pool: pair type differs from the factory registration".to_owned(),
+            ),
+            None => ctx.invalid_field(
+                key,
+                field,
+                format!(
+                    "invalid pool: not a pair registered in the Astroport factory {}",
+                    factory_address
+                ),
+            ),
+        },
+        Err(e) => ctx.invalid_field(
+            key,
+            field,
+            format!("failed to query the Astroport factory: {}", e),
+        ),
+    }
+}
+
+/// Verify a single side LP limit is the contribution minus `limit_pct`%, rounded with the
+/// configured strategy, within the configured absolute tolerance.
+///
+/// PCL pools accept imbalanced deposits for a fee: a limit above the tolerance but within
+/// the contribution is only a warning there.
+fn verify_single_side_lp_limit<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    contribution: Decimal,
+    limit_pct: u32,
+    limit: Decimal,
+    is_pcl: bool,
+) {
+    let expected_limit = contribution
         .checked_sub(
-            asset_b_contribution
-                .checked_mul(Decimal::new(single_side_lp_limit_pct as i64, 2))
+            contribution
+                .checked_mul(Decimal::new(limit_pct as i64, 2))
                 .unwrap(),
         )
         .unwrap()
-        .round_dp_with_strategy(0, RoundingStrategy::AwayFromZero);
+        .round_dp_with_strategy(0, ctx.single_side_lp_limit_rounding().into());
+    let delta = limit - expected_limit;
+    let tolerance = Decimal::from(ctx.single_side_lp_limit_tolerance());
     debug!(
-        "liquid_pooler_config/single_side_lp_limits_asset_b: expected {} | actual {}",
-        expected_asset_b_limit, asset_b_limit
-    );
-    verify_equals!(
-        ctx,
-        key,
-        field,
-        expected_asset_b_limit,
-        asset_b_limit,
-        "invalid single side lp limit: expected {} | actual {}"
+        "{}/{}: expected {} | actual {} | delta {}",
+        key, field, expected_limit, limit, delta
     );
 
+    if delta.is_zero() {
+        ctx.valid_field(key, field, "verified".to_owned());
+    } else if delta.abs() <= tolerance {
+        ctx.valid_field(
+            key,
+            field,
+            format!(
+                "verified (delta {:+} within tolerance {})",
+                delta, tolerance
+            ),
+        );
+    } else if is_pcl && delta > Decimal::ZERO && limit <= contribution {
+        ctx.warn_field(
+            key,
+            field,
+            format!(
+                "PCL pool limit {} above the expected limit {} (delta {:+}, tolerance {}): imbalanced deposits pay a fee",
+                limit, expected_limit, delta, tolerance
+            ),
+        );
+    } else {
+        ctx.invalid_field(
+            key,
+            field,
+            format!(
+                "invalid single side lp limit: expected {} | actual {} (delta {:+})",
+                expected_limit, limit, delta
+            ),
+        );
+        let path = match field.strip_prefix("single_side_lp_limits_") {
+            Some(asset) => format!("single_side_lp_limits/{}_limit", asset),
+            None => field.to_owned(),
+        };
+        ctx.suggest_fix(key, field, &path, expected_limit.to_string().into());
+    }
+}
+
+/// Check that both party contributions are worth the same at the current pool price,
+/// within `tolerance_pct`
+pub async fn verify_contribution_parity<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    pool_address: &str,
+    party_a_denom: &str,
+    party_a_contribution: Decimal,
+    party_b_contribution: Decimal,
+    tolerance_pct: u32,
+) -> Result<(), CovenantCliError> {
+    let host_chain_name = ctx.host_chain_name();
+    let pool_info =
+        get_astroport_pool_info(&ctx.cli_context, &host_chain_name, pool_address).await?;
+    let pool_amount = |i: usize| {
+        pool_info
+            .assets
+            .get(i)
+            .and_then(|a| a.amount.parse::<u128>().ok())
+            .map(Decimal::from)
+            .unwrap_or_default()
+    };
+    // Price of one pool asset B in pool asset A
+    let Some(pool_price) = pool_amount(0).checked_div(pool_amount(1)) else {
+        ctx.invalid(key, "contribution parity: empty pool".to_owned());
+        return Ok(());
+    };
+    let party_a_first = pool_info
+        .assets
+        .first()
+        .and_then(|a| a.info.native_token.as_ref())
+        .is_some_and(|t| t.denom == party_a_denom);
+
+    // Contribution values in pool asset A
+    let (party_a_value, party_b_value) = if party_a_first {
+        (party_a_contribution, party_b_contribution * pool_price)
+    } else {
+        (party_a_contribution * pool_price, party_b_contribution)
+    };
+    let deviation = (party_a_value - party_b_value).abs()
+        / party_a_value.max(party_b_value).max(Decimal::ONE)
+        * Decimal::ONE_HUNDRED;
+    let message = format!(
+        "contribution parity: party A {:.0} | party B {:.0} -> {:.2}% apart",
+        party_a_value, party_b_value, deviation
+    );
+    if deviation <= Decimal::from(tolerance_pct) {
+        ctx.valid(key, message);
+    } else {
+        ctx.invalid(key, format!("{} (tolerance {}%)", message, tolerance_pct));
+    }
     Ok(())
 }
+
+/// The expected spot price is in the pool direction (asset A per asset B) rather than
+/// inverted: an inverted price is outside the tolerance of the pool price but within the
+/// tolerance of its inverse
+fn verify_price_direction<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    expected_spot_price: Decimal,
+    current_pool_price: Decimal,
+    (pool_asset_a_denom, pool_asset_b_denom): (&str, &str),
+) {
+    let Some(inverse_pool_price) = Decimal::ONE.checked_div(current_pool_price) else {
+        return;
+    };
+    let ratios = format!(
+        "{} per {}: {:.4} | {} per {}: {:.4}",
+        pool_asset_a_denom,
+        pool_asset_b_denom,
+        current_pool_price,
+        pool_asset_b_denom,
+        pool_asset_a_denom,
+        inverse_pool_price
+    );
+    if is_inverted_price(expected_spot_price, current_pool_price) {
+        ctx.invalid_field(
+            key,
+            field,
+            format!(
+                "inverted expected spot price {:.4}: should be {} per {} ({})",
+                expected_spot_price, pool_asset_a_denom, pool_asset_b_denom, ratios
+            ),
+        );
+    } else {
+        ctx.valid_field(
+            key,
+            field,
+            format!(
+                "{} per {} direction ({})",
+                pool_asset_a_denom, pool_asset_b_denom, ratios
+            ),
+        );
+    }
+}
+
+// Relative distance of the expected spot price to the pool price checked above
+const PRICE_DIRECTION_TOLERANCE: Decimal = Decimal::from_parts(5, 0, 0, false, 2);
+
+/// Whether an expected price only matches the inverse of the pool price
+fn is_inverted_price(expected_spot_price: Decimal, current_pool_price: Decimal) -> bool {
+    let within =
+        |price: Decimal| (expected_spot_price - price).abs() <= price * PRICE_DIRECTION_TOLERANCE;
+    match Decimal::ONE.checked_div(current_pool_price) {
+        Some(inverse_pool_price) => !within(current_pool_price) && within(inverse_pool_price),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn near_one_prices_within_tolerance_are_not_inverted() {
+        // 0.99 is closer to 1 / 1.02 = 0.980 than to 1.02, but within 5% of 1.02
+        assert!(!is_inverted_price(
+            Decimal::new(99, 2),
+            Decimal::new(102, 2)
+        ));
+    }
+
+    #[test]
+    fn prices_only_matching_the_inverse_are_inverted() {
+        assert!(is_inverted_price(Decimal::new(5, 1), Decimal::new(2, 0)));
+        assert!(is_inverted_price(Decimal::new(101, 3), Decimal::new(10, 0)));
+    }
+
+    #[test]
+    fn prices_matching_neither_direction_are_not_inverted() {
+        assert!(!is_inverted_price(Decimal::new(5, 0), Decimal::new(2, 0)));
+        assert!(!is_inverted_price(Decimal::new(1, 0), Decimal::ZERO));
+    }
+}
@@ -0,0 +1,69 @@
+use super::CovenantValidationContext;
+use crate::error::CovenantCliError;
+use crate::utils::chain::{get_registry_chains, RegistryChain};
+
+// Common names of chains that differ from their registry name
+const CHAIN_ALIASES: &[(&str, &str)] = &[
+    ("cosmos", "cosmoshub"),
+    ("gaia", "cosmoshub"),
+    ("hub", "cosmoshub"),
+    ("ntrn", "neutron"),
+];
+
+/// Resolve the host and party chain names of the metadata to registry chain names
+pub async fn normalize_chain_names(
+    ctx: &mut CovenantValidationContext<'_>,
+) -> Result<(), CovenantCliError> {
+    let chains = get_registry_chains(&ctx.cli_context).await?;
+    // Nothing to resolve against (e.g. plan mode)
+    if chains.is_empty() {
+        return Ok(());
+    }
+
+    let key = "metadata";
+    for field in [
+        "host_chain_name",
+        "party_a_chain_name",
+        "party_b_chain_name",
+    ] {
+        let name = match field {
+            "host_chain_name" => ctx.host_chain_name(),
+            "party_a_chain_name" => ctx.party_a_chain_name(),
+            _ => ctx.party_b_chain_name(),
+        };
+        if name.is_empty() {
+            continue;
+        }
+        match resolve_chain_name(&chains, &name) {
+            Some(resolved) if resolved == name => {}
+            Some(resolved) => {
+                ctx.valid_field(key, field, format!("{} resolved to {}", name, resolved));
+                match field {
+                    "host_chain_name" => ctx.set_host_chain_name(resolved),
+                    "party_a_chain_name" => ctx.set_party_a_chain_name(resolved),
+                    _ => ctx.set_party_b_chain_name(resolved),
+                }
+            }
+            None => ctx.invalid_field(key, field, format!("unknown chain {}", name)),
+        }
+    }
+    Ok(())
+}
+
+/// Registry name of a chain given its name, an alias, its chain id or its pretty name
+pub(crate) fn resolve_chain_name(chains: &[RegistryChain], name: &str) -> Option<String> {
+    let name = CHAIN_ALIASES
+        .iter()
+        .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+        .map_or(name, |(_, chain_name)| *chain_name);
+    chains
+        .iter()
+        .find(|chain| chain.name == name)
+        .or_else(|| chains.iter().find(|chain| chain.chain_id == name))
+        .or_else(|| {
+            chains
+                .iter()
+                .find(|chain| chain.pretty_name.eq_ignore_ascii_case(name))
+        })
+        .map(|chain| chain.name.clone())
+}
@@ -1,37 +1,223 @@
-use anyhow::{Context, Error};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::io::{BufRead, BufReader};
 
-use super::CovenantValidationContext;
+use super::{CovenantValidationContext, NEUTRON_CHAIN_NAME};
+use crate::context::CliContext;
+use crate::error::CovenantCliError;
+use crate::utils::cosmwasm::query_contract;
 
-pub async fn get_covenant_code_ids(version: String) -> Result<HashMap<String, u64>, Error> {
-    let content = reqwest::get(format!(
-        "https://github.com/timewave-computer/covenants/releases/download/{}/contract_code_ids.txt",
-        version
-    ))
-    .await
-    .with_context(|| "failed fetching contract code ids from covenants release")?
-    .text()
-    .await?;
+pub(crate) const RELEASES_URL: &str =
+    "https://github.com/timewave-computer/covenants/releases/download";
+
+/// Expected digest of the contract code ids release artifact
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum CodeIdsChecksum {
+    /// Digest listed in the `SHA256SUMS` file published in the release
+    Release,
+    /// Hex encoded SHA256 digest
+    Sha256(String),
+}
+
+impl From<&str> for CodeIdsChecksum {
+    fn from(value: &str) -> Self {
+        match value {
+            "release" => CodeIdsChecksum::Release,
+            digest => CodeIdsChecksum::Sha256(digest.to_lowercase()),
+        }
+    }
+}
+
+pub async fn get_covenant_code_ids(
+    ctx: &mut CovenantValidationContext<'_>,
+) -> Result<HashMap<String, u64>, CovenantCliError> {
+    // Code ids from an alternative source take precedence over the release artifact
+    let overrides = if let Some(path) = ctx.code_ids_file() {
+        let content =
+            std::fs::read_to_string(&path).map_err(|source| CovenantCliError::ReadError {
+                path: path.clone(),
+                source,
+            })?;
+        ctx.valid("contract_codes", format!("source: file {}", path));
+        Some(parse_code_ids(&content)?)
+    } else if let Some(registry_addr) = ctx.code_ids_registry() {
+        let code_ids =
+            get_registry_code_ids(&ctx.cli_context, &ctx.host_chain_name(), &registry_addr).await?;
+        ctx.valid(
+            "contract_codes",
+            format!("source: registry {}", registry_addr),
+        );
+        Some(code_ids)
+    } else {
+        None
+    };
 
+    let code_ids = match (get_release_code_ids(ctx).await, overrides) {
+        (Ok(mut code_ids), Some(overrides)) => {
+            code_ids.extend(overrides);
+            code_ids
+        }
+        (Ok(code_ids), None) => code_ids,
+        (Err(_), Some(overrides)) => overrides,
+        (Err(e), None) => return Err(e),
+    };
+    // `valence-covenant-two-party-pol` is released as `valence_covenant_two_party_pol.wasm`
+    let covenant_name = contract_name(&ctx.covenant_contract().replace('-', "_"));
+    ctx.covenant_code_id = code_ids.get(&covenant_name).copied();
+    Ok(code_ids)
+}
+
+async fn get_release_code_ids(
+    ctx: &mut CovenantValidationContext<'_>,
+) -> Result<HashMap<String, u64>, CovenantCliError> {
+    let version = ctx.contract_codes_version();
+    // Neutron code ids are published as `contract_code_ids.txt`, other host chains
+    // are expected to be suffixed with the chain name.
+    let file_name = if ctx.host_chain_name() == NEUTRON_CHAIN_NAME {
+        "contract_code_ids.txt".to_owned()
+    } else {
+        format!("contract_code_ids_{}.txt", ctx.host_chain_name())
+    };
+    let content = ctx
+        .cli_context
+        .api_get_text(&format!("{}/{}/{}", RELEASES_URL, version, file_name))
+        .await
+        .map_err(|e| {
+            CovenantCliError::registry(
+                e.context("failed fetching contract code ids from covenants release"),
+            )
+        })?;
+
+    if let Some(checksum) = ctx.contract_codes_checksum() {
+        verify_checksum(ctx, &checksum, &version, &file_name, &content).await;
+    }
+
+    parse_code_ids(&content)
+}
+
+fn parse_code_ids(content: &str) -> Result<HashMap<String, u64>, CovenantCliError> {
     let mut code_ids = HashMap::new();
     let reader = BufReader::new(content.as_bytes());
     for line in reader.lines() {
-        let line = line.with_context(|| "failed reading line from contract_code_ids.txt file")?;
+        let line = line.map_err(|e| CovenantCliError::parse("contract_code_ids.txt line", e))?;
         let parts: Vec<&str> = line.split_ascii_whitespace().collect();
         if parts.len() == 2 {
-            let contract_name = parts[0].trim().replace("valence_", "").replace(".wasm", "");
             let code_id = parts[1].trim();
-            code_ids.insert(contract_name, code_id.parse::<u64>().unwrap());
+            code_ids.insert(
+                contract_name(parts[0].trim()),
+                code_id.parse::<u64>().map_err(|_| {
+                    CovenantCliError::DeserializeError(format!("invalid code id {}", code_id))
+                })?,
+            );
         } else {
-            return Err(anyhow::anyhow!(
-                "invalid line in contract_code_ids.txt file"
+            return Err(CovenantCliError::DeserializeError(
+                "invalid line in contract_code_ids.txt file".to_owned(),
             ));
         }
     }
     Ok(code_ids)
 }
 
+/// Query the code ids pinned in an on-chain registry contract (`{ "code_ids": {} }`)
+async fn get_registry_code_ids(
+    ctx: &CliContext,
+    host_chain_name: &str,
+    registry_addr: &str,
+) -> Result<HashMap<String, u64>, CovenantCliError> {
+    let data = query_contract(
+        ctx,
+        host_chain_name,
+        registry_addr,
+        json!({ "code_ids": {} }),
+    )
+    .await
+    .map_err(|e| {
+        CovenantCliError::registry(anyhow::Error::new(e).context(format!(
+            "failed querying code ids registry {}",
+            registry_addr
+        )))
+    })?;
+    data.as_object()
+        .ok_or_else(|| {
+            CovenantCliError::DeserializeError(format!(
+                "unexpected code ids registry response: {}",
+                data
+            ))
+        })?
+        .iter()
+        .map(|(name, code_id)| {
+            code_id
+                .as_u64()
+                .or_else(|| code_id.as_str().and_then(|id| id.parse().ok()))
+                .map(|code_id| (contract_name(name), code_id))
+                .ok_or_else(|| {
+                    CovenantCliError::DeserializeError(format!(
+                        "invalid code id for {}: {}",
+                        name, code_id
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn contract_name(artifact: &str) -> String {
+    artifact.replace("valence_", "").replace(".wasm", "")
+}
+
+async fn verify_checksum(
+    ctx: &mut CovenantValidationContext<'_>,
+    checksum: &CodeIdsChecksum,
+    version: &str,
+    file_name: &str,
+    content: &str,
+) {
+    let expected = match checksum {
+        CodeIdsChecksum::Sha256(digest) => Ok(digest.clone()),
+        CodeIdsChecksum::Release => {
+            get_release_checksum(&ctx.cli_context, version, file_name).await
+        }
+    };
+    let actual = base16ct::lower::encode_string(&Sha256::digest(content.as_bytes()));
+    match expected {
+        Ok(expected) if expected == actual => {
+            ctx.valid_field("contract_codes", "checksum", "verified".to_owned())
+        }
+        Ok(expected) => ctx.invalid_field(
+            "contract_codes",
+            "checksum",
+            format!(
+                "invalid checksum: expected {} | actual {}",
+                expected, actual
+            ),
+        ),
+        Err(e) => ctx.invalid_field("contract_codes", "checksum", e.to_string()),
+    }
+}
+
+async fn get_release_checksum(
+    ctx: &CliContext,
+    version: &str,
+    file_name: &str,
+) -> Result<String, CovenantCliError> {
+    let sums = ctx
+        .api_get_text(&format!("{}/{}/SHA256SUMS", RELEASES_URL, version))
+        .await
+        .map_err(|e| {
+            CovenantCliError::registry(
+                e.context("failed fetching SHA256SUMS from covenants release"),
+            )
+        })?;
+    sums.lines()
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .find(|(_, name)| name.trim().trim_start_matches('*') == file_name)
+        .map(|(digest, _)| digest.to_lowercase())
+        .ok_or_else(|| {
+            CovenantCliError::RegistryError(format!("{} not listed in SHA256SUMS", file_name))
+        })
+}
+
 pub fn verify_code_id(
     ctx: &mut CovenantValidationContext,
     field: &'static str,
@@ -40,10 +226,12 @@ pub fn verify_code_id(
     code_id: u64,
 ) {
     if code_ids.contains_key(contract_name) {
-        if code_ids.get(contract_name).unwrap() == &code_id {
+        let expected_code_id = code_ids.get(contract_name).unwrap();
+        if expected_code_id == &code_id {
             ctx.valid_field("contract_codes", field, "verified".to_owned());
         } else {
             ctx.invalid_field("contract_codes", field, "invalid code id".to_owned());
+            ctx.suggest_fix("contract_codes", field, field, (*expected_code_id).into());
         }
     } else {
         ctx.invalid(
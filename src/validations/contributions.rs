@@ -0,0 +1,45 @@
+use super::CovenantValidationContext;
+
+// Built-in minimum contributions (base units) below which IBC transfer fees and minimum
+// receive amounts may leave the covenant stuck, overridden by `min_contributions`
+const DEFAULT_MIN_CONTRIBUTIONS: &[(&str, u128)] = &[
+    ("uatom", 100_000),
+    ("untrn", 1_000_000),
+    ("uosmo", 1_000_000),
+    ("ustrd", 1_000_000),
+    ("uxprt", 1_000_000),
+    ("stuatom", 100_000),
+    ("stk/uatom", 100_000),
+];
+
+/// Warn when a contribution is below the minimum of its denom
+pub fn verify_minimum_contribution<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    denom: &str,
+    amount: u128,
+) {
+    let field = "contribution";
+    let minimum = ctx.min_contribution(denom).or_else(|| {
+        DEFAULT_MIN_CONTRIBUTIONS
+            .iter()
+            .find(|(d, _)| *d == denom)
+            .map(|(_, minimum)| *minimum)
+    });
+    match minimum {
+        Some(minimum) if amount < minimum => ctx.warn_field(
+            key,
+            field,
+            format!(
+                "dust contribution: {} {} is below the minimum of {}",
+                amount, denom, minimum
+            ),
+        ),
+        Some(minimum) => ctx.valid_field(
+            key,
+            field,
+            format!("above the minimum of {} {}", minimum, denom),
+        ),
+        None => {}
+    }
+}
@@ -0,0 +1,180 @@
+use crate::error::CovenantCliError;
+
+use super::{CovenantValidationContext, NEUTRON_CHAIN_NAME};
+use crate::utils::chain::get_fee_tokens;
+use crate::utils::neutron::{get_fee_refunder_params, FeeCoin};
+
+const NEUTRON_FEE_DENOM: &str = "untrn";
+// Gas used by a relayer to deliver an acknowledgement or a timeout, the preset fees should
+// cover it at the average gas price
+const RELAY_GAS_ESTIMATE: f32 = 200_000.0;
+const FEE_DENOM_FIELD: &str = "fee_denom";
+// Upper bound (in seconds) above which ICA/IBC transfer timeouts are considered unusual
+const MAX_TIMEOUT_SECS: u64 = 24 * 60 * 60;
+
+pub fn verify_timeout<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    timeout: u64,
+) {
+    if timeout == 0 {
+        ctx.invalid_field(
+            key,
+            field,
+            "invalid timeout: should be greater than 0".to_owned(),
+        );
+    } else if timeout > MAX_TIMEOUT_SECS {
+        ctx.warn_field(
+            key,
+            field,
+            format!(
+                "{}s exceeds the usual upper bound of {}s",
+                timeout, MAX_TIMEOUT_SECS
+            ),
+        );
+    } else {
+        ctx.valid_field(key, field, format!("{}s", timeout));
+    }
+}
+
+/// Verify the preset IBC fees against the Neutron fee refunder minimum fees.
+/// The ICTX fee middleware only accepts NTRN-denominated fees.
+pub async fn verify_ibc_fee<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    ack_fee: u128,
+    timeout_fee: u128,
+) -> Result<(), CovenantCliError> {
+    if ctx.host_chain_name() != NEUTRON_CHAIN_NAME {
+        ctx.warn(
+            key,
+            format!(
+                "IBC fees are only verified on {}: skipped",
+                NEUTRON_CHAIN_NAME
+            ),
+        );
+        return Ok(());
+    }
+
+    let params = get_fee_refunder_params(&ctx.cli_context).await?;
+    verify_fee(ctx, key, "ack_fee", ack_fee, &params.min_fee.ack_fee);
+    verify_fee(
+        ctx,
+        key,
+        "timeout_fee",
+        timeout_fee,
+        &params.min_fee.timeout_fee,
+    );
+    verify_fee_token(
+        ctx,
+        key,
+        &[("ack_fee", ack_fee), ("timeout_fee", timeout_fee)],
+    )
+    .await;
+
+    Ok(())
+}
+
+/// Verify the fee denom is a fee token of the host chain with sane gas prices, and that the
+/// fees cover the relaying gas at its average gas price
+async fn verify_fee_token<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    fees: &[(&'a str, u128)],
+) {
+    let host_chain_name = ctx.host_chain_name();
+    let fee_tokens = match get_fee_tokens(&ctx.cli_context, &host_chain_name).await {
+        Ok(fee_tokens) => fee_tokens,
+        Err(e) => {
+            ctx.warn_field(
+                key,
+                FEE_DENOM_FIELD,
+                format!("failed to query the fee tokens: {}", e),
+            );
+            return;
+        }
+    };
+    let Some(fee_token) = fee_tokens.iter().find(|t| t.denom == NEUTRON_FEE_DENOM) else {
+        ctx.invalid_field(
+            key,
+            FEE_DENOM_FIELD,
+            format!(
+                "invalid fee denom: {} is not a fee token of {} | accepted {}",
+                NEUTRON_FEE_DENOM,
+                host_chain_name,
+                fee_tokens
+                    .iter()
+                    .map(|t| t.denom.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        );
+        return;
+    };
+    if let Err(e) = fee_token.check_gas_prices() {
+        ctx.warn_field(
+            key,
+            FEE_DENOM_FIELD,
+            format!("unusual registry gas prices: {}", e),
+        );
+        return;
+    }
+    ctx.valid_field(
+        key,
+        FEE_DENOM_FIELD,
+        format!(
+            "{} is a fee token of {} (average gas price {})",
+            NEUTRON_FEE_DENOM, host_chain_name, fee_token.average_gas_price
+        ),
+    );
+    for (field, amount) in fees {
+        let covered_gas = *amount as f32 / fee_token.average_gas_price;
+        if covered_gas < RELAY_GAS_ESTIMATE {
+            ctx.warn_field(
+                key,
+                field,
+                format!(
+                    "{} {} covers {:.0} gas at the average gas price, relaying uses about {:.0}",
+                    amount, NEUTRON_FEE_DENOM, covered_gas, RELAY_GAS_ESTIMATE
+                ),
+            );
+        }
+    }
+}
+
+fn verify_fee<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    amount: u128,
+    min_fee: &[FeeCoin],
+) {
+    match min_fee.iter().find(|c| c.denom == NEUTRON_FEE_DENOM) {
+        Some(min_fee) => {
+            let min_amount = min_fee.amount.parse::<u128>().unwrap_or_default();
+            if amount >= min_amount {
+                ctx.valid_field(key, field, format!("{} {}", amount, NEUTRON_FEE_DENOM));
+            } else {
+                ctx.invalid_field(
+                    key,
+                    field,
+                    format!(
+                        "invalid fee: expected at least {} {} | actual {} {}",
+                        min_amount, NEUTRON_FEE_DENOM, amount, NEUTRON_FEE_DENOM
+                    ),
+                );
+            }
+        }
+        None => {
+            ctx.invalid_field(
+                key,
+                field,
+                format!(
+                    "invalid fee denom: minimum fee is not {}-denominated",
+                    NEUTRON_FEE_DENOM
+                ),
+            );
+        }
+    }
+}
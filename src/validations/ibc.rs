@@ -0,0 +1,495 @@
+use sha2::{Digest, Sha256};
+
+use super::{CovenantValidationContext, TRANSFER_PORT_ID};
+use crate::utils::chain::{get_client_status, get_denom_trace, DenomTrace};
+use crate::utils::cosmwasm::get_contract_info;
+use crate::utils::path::IBCPath;
+
+/// Detect from the registry path whether the party chain reaches the host chain
+/// through a `wasm.` port, cross-check it against the metadata flag (if any) and
+/// return the setting that should be used to resolve the party channels.
+pub fn verify_channel_uses_wasm_port<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    path_info: &IBCPath,
+    host_chain_name: &str,
+    declared: Option<bool>,
+) -> bool {
+    let field = "channel_uses_wasm_port";
+    let party_ports = path_info
+        .channels
+        .iter()
+        .filter_map(|c| {
+            if path_info.chain_1.chain_name == host_chain_name {
+                (c.chain_1.port_id == TRANSFER_PORT_ID).then_some(&c.chain_2.port_id)
+            } else {
+                (c.chain_2.port_id == TRANSFER_PORT_ID).then_some(&c.chain_1.port_id)
+            }
+        })
+        .collect::<Vec<_>>();
+    let has_transfer_port = party_ports.iter().any(|p| *p == TRANSFER_PORT_ID);
+    let has_wasm_port = party_ports.iter().any(|p| p.starts_with("wasm."));
+    let detected = has_wasm_port && !has_transfer_port;
+
+    match declared {
+        Some(true) if !has_wasm_port => {
+            ctx.invalid_field(
+                key,
+                field,
+                "invalid metadata flag: no wasm port channel found in the registry".to_owned(),
+            );
+            true
+        }
+        Some(false) if detected => {
+            ctx.invalid_field(
+                key,
+                field,
+                "invalid metadata flag: the registry only lists a wasm port channel".to_owned(),
+            );
+            false
+        }
+        Some(value) => {
+            ctx.valid_field(key, field, "verified".to_owned());
+            value
+        }
+        None => {
+            ctx.valid_field(key, field, format!("detected from registry ({})", detected));
+            detected
+        }
+    }
+}
+
+/// Contracts bound to the `wasm.` ports of the party end of the path, with the party end
+/// channel id
+fn wasm_port_contracts(path_info: &IBCPath, host_chain_name: &str) -> Vec<(String, String)> {
+    path_info
+        .channels
+        .iter()
+        .filter_map(|c| {
+            let (host_end, party_end) = if path_info.chain_1.chain_name == host_chain_name {
+                (&c.chain_1, &c.chain_2)
+            } else {
+                (&c.chain_2, &c.chain_1)
+            };
+            let contract = party_end.port_id.strip_prefix("wasm.")?;
+            (host_end.port_id == TRANSFER_PORT_ID)
+                .then(|| (contract.to_owned(), party_end.channel_id.clone()))
+        })
+        .collect()
+}
+
+/// Resolve the contracts owning the `wasm.` ports of the path on the party chain and check
+/// the outpost contract declared in the metadata (if any) is one of them
+pub async fn verify_wasm_port_contract<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    path_info: &IBCPath,
+    host_chain_name: &str,
+    party_chain_name: &str,
+    expected_outpost: Option<String>,
+) {
+    let field = "outpost_contract";
+    let contracts = wasm_port_contracts(path_info, host_chain_name);
+    if contracts.is_empty() {
+        if let Some(expected_outpost) = expected_outpost {
+            ctx.invalid_field(
+                key,
+                field,
+                format!(
+                    "invalid outpost contract {}: no wasm port channel found in the registry",
+                    expected_outpost
+                ),
+            );
+        }
+        return;
+    }
+
+    let mut port_contracts = vec![];
+    for (contract, channel_id) in &contracts {
+        match get_contract_info(&ctx.cli_context, party_chain_name, contract).await {
+            Ok(info) => {
+                port_contracts.push(format!("{} ({}, {})", contract, info.label, channel_id))
+            }
+            Err(e) => ctx.invalid_field(
+                key,
+                field,
+                format!(
+                    "port contract {} not found on {}: {}",
+                    contract, party_chain_name, e
+                ),
+            ),
+        }
+    }
+    match expected_outpost {
+        Some(expected_outpost) => {
+            match contracts
+                .iter()
+                .find(|(contract, _)| *contract == expected_outpost)
+            {
+                Some((contract, channel_id)) => ctx.valid_field(
+                    key,
+                    field,
+                    format!(
+                        "{} bound to port wasm.{} ({})",
+                        contract, contract, channel_id
+                    ),
+                ),
+                None => ctx.invalid_field(
+                    key,
+                    field,
+                    format!(
+                        "invalid outpost contract: expected {} | wasm ports bound to {}",
+                        expected_outpost,
+                        contracts
+                            .iter()
+                            .map(|(contract, _)| contract.as_str())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                ),
+            }
+        }
+        None if !port_contracts.is_empty() => ctx.warn_field(
+            key,
+            field,
+            format!(
+                "wasm ports bound to {}: declare the expected outpost in the metadata",
+                port_contracts.join(", ")
+            ),
+        ),
+        None => {}
+    }
+}
+
+/// Resolve the connection id and the host to party and party to host channel ids
+/// of the transfer channel between the host chain and the party chain
+pub fn get_path_connection_and_channels(
+    path_info: &IBCPath,
+    host_chain_name: &str,
+    channel_uses_wasm_port: bool,
+) -> (String, String, String) {
+    if path_info.chain_1.chain_name == host_chain_name {
+        path_info
+            .channels
+            .iter()
+            .filter_map(|c| {
+                if c.chain_1.port_id == TRANSFER_PORT_ID
+                    && ((channel_uses_wasm_port && c.chain_2.port_id.starts_with("wasm."))
+                        || (!channel_uses_wasm_port && c.chain_2.port_id == TRANSFER_PORT_ID))
+                {
+                    Some((
+                        path_info.chain_1.connection_id.clone(),
+                        c.chain_1.channel_id.clone(),
+                        c.chain_2.channel_id.clone(),
+                    ))
+                } else {
+                    None
+                }
+            })
+            .next()
+            .unwrap_or_default()
+    } else {
+        path_info
+            .channels
+            .iter()
+            .filter_map(|c| {
+                if c.chain_2.port_id == TRANSFER_PORT_ID
+                    && ((channel_uses_wasm_port && c.chain_1.port_id.starts_with("wasm."))
+                        || c.chain_1.port_id == TRANSFER_PORT_ID)
+                {
+                    Some((
+                        path_info.chain_2.connection_id.clone(),
+                        c.chain_2.channel_id.clone(),
+                        c.chain_1.channel_id.clone(),
+                    ))
+                } else {
+                    None
+                }
+            })
+            .next()
+            .unwrap_or_default()
+    }
+}
+
+/// Record whether the resolved channel is an unordered ICS20 transfer channel
+pub fn verify_transfer_channel<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    path_info: &IBCPath,
+    chain_name: &str,
+    channel_id: &str,
+) {
+    // Unresolved channels are reported by the channel id checks
+    if channel_id.is_empty() {
+        return;
+    }
+    match path_info.find_channel(chain_name, channel_id) {
+        Some(channel) => match channel.check_transfer_channel() {
+            Ok(())
+                if !channel.tags.preferred
+                    && path_info.channels.iter().any(|c| c.tags.preferred) =>
+            {
+                ctx.warn(
+                    key,
+                    format!(
+                        "{} ({}): not the preferred channel of the path",
+                        channel_id, chain_name
+                    ),
+                )
+            }
+            Ok(()) => ctx.valid(
+                key,
+                format!(
+                    "{} ({}): {} {}",
+                    channel_id, chain_name, channel.ordering, channel.version
+                ),
+            ),
+            Err(e) => ctx.invalid(key, format!("{} ({}): {}", channel_id, chain_name, e)),
+        },
+        None => ctx.invalid(
+            key,
+            format!(
+                "{} ({}): channel not found in registry",
+                channel_id, chain_name
+            ),
+        ),
+    }
+}
+
+/// Query the status of the light clients at both ends of the path (`--verify-onchain`)
+pub async fn verify_path_clients<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    path_info: &IBCPath,
+) {
+    if !ctx.verify_onchain() {
+        return;
+    }
+    for end in [&path_info.chain_1, &path_info.chain_2] {
+        match get_client_status(&ctx.cli_context, &end.chain_name, &end.client_id).await {
+            Ok(status) if status == "Active" => ctx.valid(
+                key,
+                format!("{} ({}): client active", end.client_id, end.chain_name),
+            ),
+            Ok(status) => ctx.invalid(
+                key,
+                format!(
+                    "{} ({}): client {}, transfers over {} would fail",
+                    end.client_id,
+                    end.chain_name,
+                    status.to_lowercase(),
+                    end.connection_id
+                ),
+            ),
+            Err(e) => ctx.warn(
+                key,
+                format!(
+                    "{} ({}): failed querying client status: {}",
+                    end.client_id, end.chain_name, e
+                ),
+            ),
+        }
+    }
+}
+
+/// Forwarding mappings are only expected when the party chain has no direct transfer
+/// channel with the host chain, otherwise refunds may be routed through a hop
+pub fn verify_pfm_consistency<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    party_chain_name: &str,
+    direct_channel_id: &str,
+    forwarded_denoms: usize,
+) {
+    let field = "denom_to_pfm_map";
+    match (direct_channel_id.is_empty(), forwarded_denoms) {
+        (false, 0) | (true, 1..) => {
+            ctx.valid_field(key, field, "consistent with the path".to_owned())
+        }
+        (false, _) => ctx.warn_field(
+            key,
+            field,
+            format!(
+                "{} forwarded denoms although {} has a direct transfer channel {}",
+                forwarded_denoms, party_chain_name, direct_channel_id
+            ),
+        ),
+        (true, 0) => ctx.warn_field(
+            key,
+            field,
+            format!(
+                "no forwarded denoms although {} has no direct transfer channel",
+                party_chain_name
+            ),
+        ),
+    }
+}
+
+/// Simulate the refund of a party deposit: sent back from the host chain over the host to
+/// party channel, the deposit must unwind to the party denom instead of arriving on the
+/// party chain as a double-hop IBC voucher
+#[allow(clippy::too_many_arguments)]
+pub async fn verify_refund_path<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    path_info: &IBCPath,
+    party_chain_name: &str,
+    host_to_party_channel_id: &str,
+    native_denom: &str,
+    remote_chain_denom: &str,
+) {
+    let host_chain_name = ctx.host_chain_name();
+    // Reverse route: the channel the refund is sent over
+    let Some(channel) = path_info.find_channel(&host_chain_name, host_to_party_channel_id) else {
+        ctx.invalid(
+            key,
+            format!(
+                "refund path: no channel {} from {} to {}",
+                host_to_party_channel_id, host_chain_name, party_chain_name
+            ),
+        );
+        return;
+    };
+    let (host_port, party_port) = if path_info.chain_1.chain_name == host_chain_name {
+        (&channel.chain_1, &channel.chain_2)
+    } else {
+        (&channel.chain_2, &channel.chain_1)
+    };
+
+    let trace = if native_denom.starts_with("ibc/") {
+        match get_denom_trace(ctx.cli_context(), &host_chain_name, native_denom).await {
+            Ok(trace) => trace,
+            Err(e) => {
+                ctx.invalid(
+                    key,
+                    format!("refund path: unknown trace of {}: {}", native_denom, e),
+                );
+                return;
+            }
+        }
+    } else {
+        DenomTrace {
+            path: String::new(),
+            base_denom: native_denom.to_owned(),
+        }
+    };
+
+    let refund_path = refund_trace_path(
+        &trace.path,
+        &format!("{}/{}", host_port.port_id, host_port.channel_id),
+        &format!("{}/{}", party_port.port_id, party_port.channel_id),
+    );
+    let refund_denom = ibc_denom(&refund_path, &trace.base_denom);
+
+    if refund_denom == remote_chain_denom {
+        ctx.valid(
+            key,
+            format!(
+                "refund path: {} unwinds to {} over {}",
+                native_denom, remote_chain_denom, host_to_party_channel_id
+            ),
+        );
+    } else if refund_path.is_empty() {
+        ctx.invalid(
+            key,
+            format!(
+                "refund path: {} unwinds to {}, not the remote chain denom {}",
+                native_denom, refund_denom, remote_chain_denom
+            ),
+        );
+    } else {
+        ctx.invalid(
+            key,
+            format!(
+                "refund path: {} arrives on {} as the voucher {} ({}/{}), not {}",
+                native_denom,
+                party_chain_name,
+                refund_denom,
+                refund_path,
+                trace.base_denom,
+                remote_chain_denom
+            ),
+        );
+    }
+}
+
+/// Trace path of the tokens on the party chain once sent back over the host hop: sent back
+/// over the channel they arrived from, they lose their last hop, otherwise the party chain
+/// prefixes them with the receiving end of the channel
+fn refund_trace_path(path: &str, host_hop: &str, party_hop: &str) -> String {
+    if path == host_hop {
+        return String::new();
+    }
+    match path
+        .strip_prefix(host_hop)
+        .and_then(|rest| rest.strip_prefix('/'))
+    {
+        Some(rest) => rest.to_owned(),
+        None if path.is_empty() => party_hop.to_owned(),
+        None => format!("{}/{}", party_hop, path),
+    }
+}
+
+/// Denom of the tokens of the given trace path, the base denom when the path is empty
+fn ibc_denom(path: &str, base_denom: &str) -> String {
+    if path.is_empty() {
+        return base_denom.to_owned();
+    }
+    format!(
+        "ibc/{}",
+        base16ct::upper::encode_string(
+            Sha256::digest(format!("{}/{}", path, base_denom).as_bytes()).as_ref()
+        )
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ibc_denom_hashes_the_full_trace() {
+        assert_eq!(ibc_denom("", "untrn"), "untrn");
+        assert_eq!(
+            ibc_denom("transfer/channel-141", "uosmo"),
+            "ibc/14F9BC3E44B8A9C1BE1FB08980FAB87034C9905EF17CF2F5008FC085218811CC"
+        );
+    }
+
+    #[test]
+    fn refund_path_unwinds_the_arrival_hop() {
+        assert_eq!(
+            refund_trace_path(
+                "transfer/channel-1",
+                "transfer/channel-1",
+                "transfer/channel-9"
+            ),
+            ""
+        );
+        assert_eq!(
+            refund_trace_path(
+                "transfer/channel-1/transfer/channel-5",
+                "transfer/channel-1",
+                "transfer/channel-9"
+            ),
+            "transfer/channel-5"
+        );
+    }
+
+    #[test]
+    fn refund_path_matches_whole_segments() {
+        // channel-10 is a different channel than channel-1
+        assert_eq!(
+            refund_trace_path(
+                "transfer/channel-10",
+                "transfer/channel-1",
+                "transfer/channel-9"
+            ),
+            "transfer/channel-9/transfer/channel-10"
+        );
+        assert_eq!(
+            refund_trace_path("", "transfer/channel-1", "transfer/channel-9"),
+            "transfer/channel-9"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use regex::Regex;
+
+use super::contracts::get_covenant_code_ids;
+use super::CovenantValidationContext;
+use crate::utils::cosmwasm::{get_code_contracts, get_contract_info};
+
+// Latest covenants of the same code compared with the label (on-chain mode)
+const LABEL_COLLISION_SCAN_LIMIT: u32 = 100;
+
+/// Check the label is set and matches the naming convention of the metadata, and in
+/// on-chain mode that no covenant of the same code already uses it
+pub async fn verify_label<'a>(ctx: &mut CovenantValidationContext<'a>, key: &'a str, label: &str) {
+    let field = "label";
+    if label.is_empty() {
+        ctx.invalid_field(key, field, "required".to_owned());
+        return;
+    }
+    match ctx.label_pattern().map(|pattern| Regex::new(&pattern)) {
+        Some(Ok(regex)) if !regex.is_match(label) => {
+            ctx.invalid_field(
+                key,
+                field,
+                format!("invalid label '{}': should match {}", label, regex.as_str()),
+            );
+            return;
+        }
+        Some(Err(e)) => {
+            ctx.invalid_field(key, field, format!("invalid label_pattern: {}", e));
+            return;
+        }
+        _ => {}
+    }
+    if !ctx.verify_onchain() {
+        ctx.valid_field(key, field, "valid".to_owned());
+        return;
+    }
+    // Covenants not verifying their code ids (swap) resolve them here, on a discarded
+    // context: the code ids checks are not part of their report
+    let code_id = match ctx.covenant_code_id() {
+        Some(code_id) => Some(code_id),
+        None => {
+            let mut code_ids_ctx = ctx.fork();
+            let _ = get_covenant_code_ids(&mut code_ids_ctx).await;
+            code_ids_ctx.covenant_code_id()
+        }
+    };
+    let Some(code_id) = code_id else {
+        ctx.warn_field(
+            key,
+            field,
+            "uniqueness not verified: unknown covenant code id".to_owned(),
+        );
+        return;
+    };
+    let host_chain_name = ctx.host_chain_name();
+    let contracts = match get_code_contracts(
+        &ctx.cli_context,
+        &host_chain_name,
+        code_id,
+        LABEL_COLLISION_SCAN_LIMIT,
+    )
+    .await
+    {
+        Ok(contracts) => contracts,
+        Err(e) => {
+            ctx.warn_field(key, field, format!("uniqueness not verified: {}", e));
+            return;
+        }
+    };
+    let deployed_address = ctx.deployed_address();
+    let mut collisions = vec![];
+    for contract_addr in contracts
+        .iter()
+        .filter(|addr| Some(*addr) != deployed_address.as_ref())
+    {
+        match get_contract_info(&ctx.cli_context, &host_chain_name, contract_addr).await {
+            Ok(info) if info.label == label => collisions.push(contract_addr.clone()),
+            Ok(_) => {}
+            Err(e) => {
+                ctx.warn_field(key, field, format!("uniqueness not verified: {}", e));
+                return;
+            }
+        }
+    }
+    if collisions.is_empty() {
+        ctx.valid_field(
+            key,
+            field,
+            format!(
+                "valid, unique among the latest {} covenants of code {}",
+                contracts.len(),
+                code_id
+            ),
+        );
+    } else {
+        ctx.warn_field(
+            key,
+            field,
+            format!(
+                "label already used by {} on {}",
+                collisions.join(", "),
+                host_chain_name
+            ),
+        );
+    }
+}
@@ -1,57 +1,359 @@
+use crate::acceptances::Acceptance;
 use crate::context::CliContext;
-use anyhow::Error;
+use crate::error::CovenantCliError;
 use async_trait::async_trait;
-use serde::Serialize;
-use std::collections::HashMap;
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 
+mod address;
+mod allowlist;
 mod astroport;
+mod chain;
 mod contracts;
+mod contributions;
+mod fees;
+mod ibc;
+mod label;
 mod neutron;
+mod pool_migration;
+mod prefetch;
+mod rules;
+mod security;
 mod single_party_pol_covenant;
 mod swap_covenant;
 mod two_party_pol_covenant;
+mod versions;
 
-pub use single_party_pol_covenant::SinglePartyPolCovenantInstMsg;
-pub use swap_covenant::SwapCovenantInstMsg;
-pub use two_party_pol_covenant::TwoPartyPolCovenantInstMsg;
+use contracts::CodeIdsChecksum;
+pub(crate) use contracts::RELEASES_URL;
 
-const NEUTRON_CHAIN_NAME: &str = "neutron";
-const PERSISTENCE_CHAIN_NAME: &str = "persistence";
+pub use allowlist::verify_allowlist;
+pub use chain::normalize_chain_names;
+pub(crate) use chain::resolve_chain_name;
+pub use pool_migration::{
+    verify_pool_migration, PoolMigrationConfig, POOL_MIGRATION_FIELDS, POOL_MIGRATION_KEY,
+};
+pub use prefetch::prefetch_registry;
+pub use security::{verify_admin_surface, verify_clock_privileges, verify_contract_versions};
+pub use versions::{covenant_validator, CovenantVersion};
+
+pub(crate) const NEUTRON_CHAIN_NAME: &str = "neutron";
+pub(crate) const PERSISTENCE_CHAIN_NAME: &str = "persistence";
 const STRIDE_CHAIN_NAME: &str = "stride";
 const TRANSFER_PORT_ID: &str = "transfer";
+/// Key of the admin and migration checks of deployed covenants
+pub(crate) const SECURITY_KEY: &str = "security";
+
+// Fields of the interchain and native covenant party configs shared by all covenants
+const INTERCHAIN_PARTY_FIELDS: &[&str] = &[
+    "party_receiver_addr",
+    "party_chain_connection_id",
+    "ibc_transfer_timeout",
+    "party_to_host_chain_channel_id",
+    "host_to_party_chain_channel_id",
+    "remote_chain_denom",
+    "addr",
+    "native_denom",
+    "contribution",
+    "denom_to_pfm_map",
+    "fallback_address",
+];
+const NATIVE_PARTY_FIELDS: &[&str] = &[
+    "party_receiver_addr",
+    "native_denom",
+    "addr",
+    "contribution",
+];
+const FEES_FIELDS: &[&str] = &[
+    "ica_timeout",
+    "ibc_transfer_timeout",
+    "ack_fee",
+    "timeout_fee",
+];
+const LIQUID_POOLER_FIELDS: &[&str] = &[
+    "pool_address",
+    "pool_pair_type",
+    "asset_a_denom",
+    "asset_b_denom",
+    "single_side_lp_limits_asset_a",
+    "single_side_lp_limits_asset_b",
+    "slippage_tolerance",
+];
+const POOL_PRICE_FIELDS: &[&str] = &["expected_spot_price", "acceptable_price_spread"];
+// Share of the pool liquidity the covenant may own before a warning
+const DEFAULT_MAX_POOL_SHARE_PCT: u32 = 30;
+// Time left to execute the covenant after the expected execution date of the proposal
+const DEFAULT_EXECUTION_DEADLINE_BUFFER_HOURS: u64 = 72;
 
-#[derive(Clone, Debug, Default, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub enum LsProvider {
     #[default]
     Stride,
     Persistence,
 }
 
-impl From<&str> for LsProvider {
-    fn from(value: &str) -> Self {
+impl TryFrom<&str> for LsProvider {
+    type Error = CovenantCliError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "stride" => Ok(LsProvider::Stride),
+            "persistence" => Ok(LsProvider::Persistence),
+            _ => Err(CovenantCliError::InvalidMetadata(format!(
+                "ls_provider: unknown provider {}",
+                value
+            ))),
+        }
+    }
+}
+
+/// Rounding applied to the expected single side LP limits
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub enum LimitRounding {
+    #[default]
+    AwayFromZero,
+    Floor,
+    Ceil,
+    Nearest,
+}
+
+impl TryFrom<&str> for LimitRounding {
+    type Error = CovenantCliError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "away_from_zero" => Ok(LimitRounding::AwayFromZero),
+            "floor" => Ok(LimitRounding::Floor),
+            "ceil" => Ok(LimitRounding::Ceil),
+            "nearest" => Ok(LimitRounding::Nearest),
+            _ => Err(CovenantCliError::InvalidMetadata(format!(
+                "single_side_lp_limit_rounding: unknown rounding {}",
+                value
+            ))),
+        }
+    }
+}
+
+impl From<LimitRounding> for rust_decimal::RoundingStrategy {
+    fn from(value: LimitRounding) -> Self {
         match value {
-            "stride" => LsProvider::Stride,
-            "persistence" => LsProvider::Persistence,
-            _ => panic!("Invalid LsProvider"),
+            LimitRounding::AwayFromZero => rust_decimal::RoundingStrategy::AwayFromZero,
+            LimitRounding::Floor => rust_decimal::RoundingStrategy::ToNegativeInfinity,
+            LimitRounding::Ceil => rust_decimal::RoundingStrategy::ToPositiveInfinity,
+            LimitRounding::Nearest => rust_decimal::RoundingStrategy::MidpointNearestEven,
         }
     }
 }
 
-#[derive(Clone, Debug, Default, Serialize)]
+/// Value a message field should be replaced with to pass a failed check
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SuggestedFix {
+    pub key: String,
+    pub field: String,
+    /// Path of the field below the key (`single_side_lp_limits/asset_a_limit`)
+    pub path: String,
+    pub value: serde_json::Value,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct CovenantValidationContext<'a> {
     #[serde(skip)]
     cli_context: CliContext,
+    covenant_contract: String,
+    host_chain_name: String,
+    contract_codes_version: String,
+    contract_codes_checksum: Option<CodeIdsChecksum>,
+    code_ids_file: Option<String>,
+    allowlist_file: Option<String>,
+    code_ids_registry: Option<String>,
     party_a_chain_name: String,
-    party_a_channel_uses_wasm_port: bool,
+    party_a_channel_uses_wasm_port: Option<bool>,
+    party_a_outpost_contract: Option<String>,
+    party_b_outpost_contract: Option<String>,
     party_b_chain_name: String,
+    party_a_name: Option<String>,
+    party_b_name: Option<String>,
     ls_provider: LsProvider,
     single_side_lp_limit_pct: u32,
+    single_side_lp_limit_pct_asset_a: Option<u32>,
+    single_side_lp_limit_pct_asset_b: Option<u32>,
+    single_side_lp_limit_rounding: LimitRounding,
+    single_side_lp_limit_tolerance: u128,
+    contribution_parity_tolerance_pct: Option<u32>,
+    slippage_tolerance_bounds_pct: Option<(Decimal, Decimal)>,
+    max_pool_share_pct: Option<u32>,
+    min_contributions: HashMap<String, u128>,
+    deployed_address: Option<String>,
+    verify_onchain: bool,
+    covenant_label: String,
+    label_pattern: Option<String>,
+    covenant_code_id: Option<u64>,
+    message_hash: String,
+    registry_commit: Option<String>,
+    audit_log_hash: Option<String>,
+    withdrawal_address: Option<String>,
+    expected_admin: Option<String>,
+    astroport_factory_address: Option<String>,
+    expected_execution_time: Option<u64>,
+    execution_deadline_buffer_hours: Option<u64>,
+    /// Keys of the other party, not validated when scoped to one party
+    skipped_keys: Vec<String>,
+    /// Keys whose results were restored from the incremental cache
+    #[serde(skip)]
+    reused_keys: Vec<&'a str>,
+    /// Fields checked by the cached run of the reused sections
+    #[serde(skip)]
+    reused_fields: Vec<(&'a str, String)>,
+    /// Whether the results of the reused sections are recorded again
+    #[serde(skip)]
+    reuse_ended: bool,
+    #[serde(skip)]
+    incremental: bool,
+    #[serde(skip)]
+    plugins: Vec<String>,
+    #[serde(borrow)]
+    checked_fields: Vec<(&'a str, &'a str)>,
+    /// Messages recorded for a field (`valid_field`...), by key
+    #[serde(skip)]
+    field_messages: HashMap<&'a str, HashSet<String>>,
+    #[serde(borrow)]
+    expected_fields: Vec<(&'a str, &'a str)>,
+    #[serde(borrow)]
     checks: HashMap<&'a str, Vec<String>>,
+    #[serde(borrow)]
+    warnings: HashMap<&'a str, Vec<String>>,
+    #[serde(borrow)]
     errors: HashMap<&'a str, Vec<String>>,
+    #[serde(borrow)]
+    accepted: HashMap<&'a str, Vec<(String, String)>>,
+    fixes: Vec<SuggestedFix>,
 }
 
 impl<'a> CovenantValidationContext<'a> {
+    pub fn new(cli_context: CliContext) -> Self {
+        CovenantValidationContext {
+            cli_context,
+            ..Default::default()
+        }
+    }
+
+    pub fn cli_context(&self) -> &CliContext {
+        &self.cli_context
+    }
+
+    pub fn covenant_contract(&self) -> String {
+        self.covenant_contract.clone()
+    }
+
+    pub fn covenant_label(&self) -> String {
+        self.covenant_label.clone()
+    }
+
+    pub fn set_covenant_label(&mut self, label: String) {
+        self.covenant_label = label;
+    }
+
+    /// Regular expression the covenant label is expected to match
+    pub fn label_pattern(&self) -> Option<String> {
+        self.label_pattern.clone()
+    }
+
+    pub fn set_label_pattern(&mut self, pattern: String) {
+        self.label_pattern = Some(pattern);
+    }
+
+    /// Code id of the covenant contract, once the code ids are resolved
+    pub fn covenant_code_id(&self) -> Option<u64> {
+        self.covenant_code_id
+    }
+
+    /// SHA-256 of the canonical JSON of the validated instantiation message
+    pub fn message_hash(&self) -> String {
+        self.message_hash.clone()
+    }
+
+    pub fn set_message_hash(&mut self, hash: String) {
+        self.message_hash = hash;
+    }
+
+    /// Chain registry commit the registry data was read from
+    pub fn registry_commit(&self) -> Option<String> {
+        self.registry_commit.clone()
+    }
+
+    pub fn set_registry_commit(&mut self, commit: String) {
+        self.registry_commit = Some(commit);
+    }
+
+    /// SHA-256 of the audit trail of the remote data the results were computed from
+    pub fn audit_log_hash(&self) -> Option<String> {
+        self.audit_log_hash.clone()
+    }
+
+    pub fn set_audit_log_hash(&mut self, hash: String) {
+        self.audit_log_hash = Some(hash);
+    }
+
+    pub fn set_covenant_contract(&mut self, contract: String) {
+        self.covenant_contract = contract;
+    }
+
+    /// Stable identifier of the check of a field, if documented
+    pub fn check_id(&self, key: &str, field: &str) -> Option<String> {
+        crate::checks::check_id(&self.covenant_contract, key, field)
+    }
+
+    pub fn host_chain_name(&self) -> String {
+        self.host_chain_name.clone()
+    }
+
+    pub fn set_host_chain_name(&mut self, chain_name: String) {
+        self.host_chain_name = chain_name;
+    }
+
+    pub fn contract_codes_version(&self) -> String {
+        self.contract_codes_version.clone()
+    }
+
+    pub fn set_contract_codes_version(&mut self, version: String) {
+        self.contract_codes_version = version;
+    }
+
+    pub fn contract_codes_checksum(&self) -> Option<CodeIdsChecksum> {
+        self.contract_codes_checksum.clone()
+    }
+
+    pub fn set_contract_codes_checksum(&mut self, checksum: CodeIdsChecksum) {
+        self.contract_codes_checksum = Some(checksum);
+    }
+
+    pub fn code_ids_file(&self) -> Option<String> {
+        self.code_ids_file.clone()
+    }
+
+    pub fn set_code_ids_file(&mut self, path: String) {
+        self.code_ids_file = Some(path);
+    }
+
+    pub fn allowlist_file(&self) -> Option<String> {
+        self.allowlist_file.clone()
+    }
+
+    pub fn set_allowlist_file(&mut self, path: String) {
+        self.allowlist_file = Some(path);
+    }
+
+    pub fn code_ids_registry(&self) -> Option<String> {
+        self.code_ids_registry.clone()
+    }
+
+    pub fn set_code_ids_registry(&mut self, address: String) {
+        self.code_ids_registry = Some(address);
+    }
+
     pub fn party_a_chain_name(&self) -> String {
         self.party_a_chain_name.clone()
     }
@@ -60,13 +362,28 @@ impl<'a> CovenantValidationContext<'a> {
         self.party_a_chain_name = party;
     }
 
-    #[allow(dead_code)]
-    pub fn party_a_channel_uses_wasm_port(&self) -> bool {
+    pub fn party_a_channel_uses_wasm_port(&self) -> Option<bool> {
         self.party_a_channel_uses_wasm_port
     }
 
     pub(crate) fn set_party_a_channel_uses_wasm_port(&mut self, value: bool) {
-        self.party_a_channel_uses_wasm_port = value;
+        self.party_a_channel_uses_wasm_port = Some(value);
+    }
+
+    pub fn party_a_outpost_contract(&self) -> Option<String> {
+        self.party_a_outpost_contract.clone()
+    }
+
+    pub fn set_party_a_outpost_contract(&mut self, address: String) {
+        self.party_a_outpost_contract = Some(address);
+    }
+
+    pub fn party_b_outpost_contract(&self) -> Option<String> {
+        self.party_b_outpost_contract.clone()
+    }
+
+    pub fn set_party_b_outpost_contract(&mut self, address: String) {
+        self.party_b_outpost_contract = Some(address);
     }
 
     pub fn party_b_chain_name(&self) -> String {
@@ -77,6 +394,24 @@ impl<'a> CovenantValidationContext<'a> {
         self.party_b_chain_name = party;
     }
 
+    /// Name of party A shown in the reports (e.g. the counterparty organization)
+    pub fn party_a_name(&self) -> Option<String> {
+        self.party_a_name.clone()
+    }
+
+    pub fn set_party_a_name(&mut self, name: String) {
+        self.party_a_name = Some(name);
+    }
+
+    /// Name of party B shown in the reports
+    pub fn party_b_name(&self) -> Option<String> {
+        self.party_b_name.clone()
+    }
+
+    pub fn set_party_b_name(&mut self, name: String) {
+        self.party_b_name = Some(name);
+    }
+
     pub fn set_ls_provider(&mut self, provider: LsProvider) {
         self.ls_provider = provider;
     }
@@ -85,39 +420,478 @@ impl<'a> CovenantValidationContext<'a> {
         self.single_side_lp_limit_pct = limit_pct;
     }
 
+    /// Single side LP limits (in %) of the pool assets A and B, the shared limit by default
+    pub fn single_side_lp_limit_pcts(&self) -> (u32, u32) {
+        (
+            self.single_side_lp_limit_pct_asset_a
+                .unwrap_or(self.single_side_lp_limit_pct),
+            self.single_side_lp_limit_pct_asset_b
+                .unwrap_or(self.single_side_lp_limit_pct),
+        )
+    }
+
+    pub fn set_single_side_lp_limit_pct_asset_a(&mut self, limit_pct: u32) {
+        self.single_side_lp_limit_pct_asset_a = Some(limit_pct);
+    }
+
+    pub fn set_single_side_lp_limit_pct_asset_b(&mut self, limit_pct: u32) {
+        self.single_side_lp_limit_pct_asset_b = Some(limit_pct);
+    }
+
+    pub fn single_side_lp_limit_rounding(&self) -> LimitRounding {
+        self.single_side_lp_limit_rounding
+    }
+
+    pub fn set_single_side_lp_limit_rounding(&mut self, rounding: LimitRounding) {
+        self.single_side_lp_limit_rounding = rounding;
+    }
+
+    /// Absolute difference (in base units) tolerated on the single side LP limits
+    pub fn single_side_lp_limit_tolerance(&self) -> u128 {
+        self.single_side_lp_limit_tolerance
+    }
+
+    pub fn set_single_side_lp_limit_tolerance(&mut self, tolerance: u128) {
+        self.single_side_lp_limit_tolerance = tolerance;
+    }
+
+    /// Tolerated value difference (in %) between the two party contributions, if checked
+    pub fn contribution_parity_tolerance_pct(&self) -> Option<u32> {
+        self.contribution_parity_tolerance_pct
+    }
+
+    pub fn set_contribution_parity_tolerance_pct(&mut self, pct: u32) {
+        self.contribution_parity_tolerance_pct = Some(pct);
+    }
+
+    /// Accepted range of the liquid pooler slippage tolerance, in percent
+    pub fn slippage_tolerance_bounds_pct(&self) -> (Decimal, Decimal) {
+        self.slippage_tolerance_bounds_pct
+            .unwrap_or((Decimal::new(1, 1), Decimal::new(5, 0)))
+    }
+
+    pub fn set_slippage_tolerance_bounds_pct(&mut self, min_pct: Decimal, max_pct: Decimal) {
+        self.slippage_tolerance_bounds_pct = Some((min_pct, max_pct));
+    }
+
+    /// Share (in %) of the pool liquidity the covenant may own
+    pub fn max_pool_share_pct(&self) -> u32 {
+        self.max_pool_share_pct
+            .unwrap_or(DEFAULT_MAX_POOL_SHARE_PCT)
+    }
+
+    pub fn set_max_pool_share_pct(&mut self, pct: u32) {
+        self.max_pool_share_pct = Some(pct);
+    }
+
+    /// Minimum contribution of a denom set in the metadata, in base units
+    pub fn min_contribution(&self, denom: &str) -> Option<u128> {
+        self.min_contributions.get(denom).copied()
+    }
+
+    pub fn set_min_contribution(&mut self, denom: String, amount: u128) {
+        self.min_contributions.insert(denom, amount);
+    }
+
+    /// Address of the deployed covenant (deployed-verification mode)
+    pub fn deployed_address(&self) -> Option<String> {
+        self.deployed_address.clone()
+    }
+
+    pub fn set_deployed_address(&mut self, address: String) {
+        self.deployed_address = Some(address);
+    }
+
+    /// Whether on-chain state (e.g. IBC client status) is queried in addition to the registry
+    pub fn verify_onchain(&self) -> bool {
+        self.verify_onchain
+    }
+
+    pub fn set_verify_onchain(&mut self, verify_onchain: bool) {
+        self.verify_onchain = verify_onchain;
+    }
+
+    /// Address the party funds are expected to be withdrawn to (from the metadata)
+    pub fn withdrawal_address(&self) -> Option<String> {
+        self.withdrawal_address.clone()
+    }
+
+    pub fn set_withdrawal_address(&mut self, address: String) {
+        self.withdrawal_address = Some(address);
+    }
+
+    /// Admin declared in the metadata (e.g. the DAO) allowed to migrate the contracts
+    pub fn expected_admin(&self) -> Option<String> {
+        self.expected_admin.clone()
+    }
+
+    pub fn set_expected_admin(&mut self, address: String) {
+        self.expected_admin = Some(address);
+    }
+
+    /// Astroport factory the pools must be registered in, the official one by default
+    pub fn astroport_factory_address(&self) -> Option<String> {
+        self.astroport_factory_address.clone()
+    }
+
+    pub fn set_astroport_factory_address(&mut self, address: String) {
+        self.astroport_factory_address = Some(address);
+    }
+
+    /// Expected execution of the proposal (e.g. the end of the DAO vote), in seconds
+    pub fn expected_execution_time(&self) -> Option<u64> {
+        self.expected_execution_time
+    }
+
+    pub fn set_expected_execution_time(&mut self, seconds: u64) {
+        self.expected_execution_time = Some(seconds);
+    }
+
+    /// Hours the deposit deadlines must leave after the expected execution of the proposal
+    pub fn execution_deadline_buffer_hours(&self) -> u64 {
+        self.execution_deadline_buffer_hours
+            .unwrap_or(DEFAULT_EXECUTION_DEADLINE_BUFFER_HOURS)
+    }
+
+    pub fn set_execution_deadline_buffer_hours(&mut self, hours: u64) {
+        self.execution_deadline_buffer_hours = Some(hours);
+    }
+
+    /// Executables run after the validation to add org-specific checks
+    pub fn plugins(&self) -> Vec<String> {
+        self.plugins.clone()
+    }
+
+    pub fn add_plugin(&mut self, plugin: String) {
+        if !self.plugins.contains(&plugin) {
+            self.plugins.push(plugin);
+        }
+    }
+
     pub fn checks(&self) -> &HashMap<&'a str, Vec<String>> {
         &self.checks
     }
 
+    pub fn warnings(&self) -> &HashMap<&'a str, Vec<String>> {
+        &self.warnings
+    }
+
     pub fn errors(&self) -> &HashMap<&'a str, Vec<String>> {
         &self.errors
     }
 
+    /// Accepted deviations with their justification
+    pub fn accepted(&self) -> &HashMap<&'a str, Vec<(String, String)>> {
+        &self.accepted
+    }
+
+    /// Move the warnings and errors matching an acceptance to the accepted deviations
+    pub fn accept_deviations(&mut self, acceptances: &[Acceptance]) {
+        for findings in [&mut self.warnings, &mut self.errors] {
+            for (key, messages) in findings.iter_mut() {
+                messages.retain(|message| {
+                    let field = message.split(": ").next().unwrap_or_default();
+                    let check_id = crate::checks::check_id(&self.covenant_contract, key, field);
+                    match acceptances
+                        .iter()
+                        .find(|a| a.matches(check_id.as_deref(), key, field))
+                    {
+                        Some(acceptance) => {
+                            self.accepted
+                                .entry(*key)
+                                .or_default()
+                                .push((message.clone(), acceptance.justification.clone()));
+                            false
+                        }
+                        None => true,
+                    }
+                });
+            }
+            findings.retain(|_, messages| !messages.is_empty());
+        }
+    }
+
     pub fn has_errors(&self) -> bool {
         !self.errors.is_empty()
     }
 
-    #[allow(unused)]
+    pub fn set_expected_fields(&mut self, fields: Vec<(&'a str, &'a str)>) {
+        self.expected_fields = fields;
+    }
+
+    pub fn skipped_keys(&self) -> &[String] {
+        &self.skipped_keys
+    }
+
+    pub fn set_skipped_keys(&mut self, keys: &[&str]) {
+        self.skipped_keys = keys.iter().map(|key| key.to_string()).collect();
+    }
+
+    pub fn reused_keys(&self) -> &[&'a str] {
+        &self.reused_keys
+    }
+
+    /// Restore the cached results of a section, its new results are not recorded
+    pub fn reuse_section(
+        &mut self,
+        key: &'a str,
+        checks: &[String],
+        warnings: &[String],
+        errors: &[String],
+        fixes: &[SuggestedFix],
+        checked_fields: &[String],
+    ) {
+        for (entries, messages) in [
+            (&mut self.checks, checks),
+            (&mut self.warnings, warnings),
+            (&mut self.errors, errors),
+        ] {
+            if !messages.is_empty() {
+                entries.insert(key, messages.to_vec());
+            }
+        }
+        self.fixes.extend_from_slice(fixes);
+        self.reused_fields
+            .extend(checked_fields.iter().map(|field| (key, field.clone())));
+        self.reused_keys.push(key);
+    }
+
+    /// Record the results of the reused sections again, for the checks of inputs outside the
+    /// message (allowlist, plugins) run on every validation
+    pub fn end_reuse(&mut self) {
+        self.reuse_ended = true;
+    }
+
+    pub fn incremental(&self) -> bool {
+        self.incremental
+    }
+
+    pub fn set_incremental(&mut self, incremental: bool) {
+        self.incremental = incremental;
+    }
+
+    /// Whether the section of a key belongs to the party left out by `--party`, its
+    /// validators (and their remote queries) are not run
+    pub fn is_out_of_scope(&self, key: &str) -> bool {
+        self.skipped_keys.iter().any(|k| k == key)
+    }
+
+    fn is_skipped(&self, key: &str) -> bool {
+        self.is_out_of_scope(key) || (!self.reuse_ended && self.reused_keys.contains(&key))
+    }
+
+    /// Fields of a key checked by this run
+    pub fn checked_fields_of(&self, key: &str) -> Vec<&'a str> {
+        self.checked_fields
+            .iter()
+            .filter(|(k, _)| *k == key)
+            .map(|(_, field)| *field)
+            .collect()
+    }
+
+    /// Fields declared by the validator that were never checked, by this run or by the
+    /// cached run of a reused section
+    pub fn unchecked_fields(&self) -> Vec<(&'a str, &'a str)> {
+        self.expected_fields
+            .iter()
+            .filter(|f| {
+                !self.checked_fields.contains(f)
+                    && !self
+                        .reused_fields
+                        .iter()
+                        .any(|(k, field)| *k == f.0 && field == f.1)
+                    && !self.is_skipped(f.0)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Copy of the context settings with an empty report, for concurrent validations
+    pub fn fork(&self) -> Self {
+        CovenantValidationContext {
+            checked_fields: vec![],
+            field_messages: HashMap::new(),
+            expected_fields: vec![],
+            checks: HashMap::new(),
+            warnings: HashMap::new(),
+            errors: HashMap::new(),
+            fixes: vec![],
+            ..self.clone()
+        }
+    }
+
+    /// Merge the report of a forked context back into this one
+    pub fn merge(&mut self, other: CovenantValidationContext<'a>) {
+        for field in other.checked_fields {
+            self.mark_checked(field.0, field.1);
+        }
+        for (key, messages) in other.field_messages {
+            self.field_messages.entry(key).or_default().extend(messages);
+        }
+        for (key, messages) in other.checks {
+            self.checks.entry(key).or_default().extend(messages);
+        }
+        for (key, messages) in other.warnings {
+            self.warnings.entry(key).or_default().extend(messages);
+        }
+        for (key, messages) in other.errors {
+            self.errors.entry(key).or_default().extend(messages);
+        }
+        self.fixes.extend(other.fixes);
+    }
+
+    /// Collapse the messages recorded several times for a (key, field) into one row: the
+    /// messages of the most severe status are kept, with their number of occurrences.
+    /// Messages recorded without a field are left alone, whatever their text.
+    pub fn dedupe_checks(&mut self) {
+        for (key, field) in self.checked_fields.clone() {
+            let prefix = format!("{}: ", field);
+            let field_messages = self.field_messages.get(key);
+            let is_of_field = |m: &String| {
+                m.starts_with(&prefix) && field_messages.is_some_and(|f| f.contains(m))
+            };
+            let of_field = |entries: &HashMap<&'a str, Vec<String>>| {
+                entries
+                    .get(key)
+                    .map(|messages| {
+                        messages
+                            .iter()
+                            .filter(|m| is_of_field(m))
+                            .cloned()
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default()
+            };
+            let statuses = [
+                of_field(&self.errors),
+                of_field(&self.warnings),
+                of_field(&self.checks),
+            ];
+            if statuses.iter().map(Vec::len).sum::<usize>() <= 1 {
+                continue;
+            }
+            let severest = statuses
+                .iter()
+                .position(|m| !m.is_empty())
+                .unwrap_or_default();
+            let mut merged = format!(
+                "{}{}",
+                prefix,
+                statuses[severest]
+                    .iter()
+                    .map(|m| &m[prefix.len()..])
+                    .unique()
+                    .join("; ")
+            );
+            if statuses[severest].len() > 1 {
+                merged.push_str(&format!(" (×{})", statuses[severest].len()));
+            }
+            for (status, entries) in [&mut self.errors, &mut self.warnings, &mut self.checks]
+                .into_iter()
+                .enumerate()
+            {
+                let Some(messages) = entries.get_mut(key) else {
+                    continue;
+                };
+                let first = messages.iter().position(is_of_field);
+                messages.retain(|m| !is_of_field(m));
+                if let (true, Some(first)) = (status == severest, first) {
+                    messages.insert(first.min(messages.len()), merged.clone());
+                }
+                if messages.is_empty() {
+                    entries.remove(key);
+                }
+            }
+        }
+    }
+
+    fn mark_checked(&mut self, key: &'a str, field: &'a str) {
+        if !self.checked_fields.contains(&(key, field)) {
+            self.checked_fields.push((key, field));
+        }
+    }
+
+    /// Mark the field checked and return its message, prefixed with the field name
+    fn field_message(&mut self, key: &'a str, field: &'a str, message: String) -> String {
+        self.mark_checked(key, field);
+        let message = format!("{}: {}", field, message);
+        self.field_messages
+            .entry(key)
+            .or_default()
+            .insert(message.clone());
+        message
+    }
+
     pub fn valid(&mut self, key: &'a str, message: String) {
+        if self.is_skipped(key) {
+            return;
+        }
         self.checks.entry(key).or_default().push(message);
     }
 
     pub fn valid_field(&mut self, key: &'a str, field: &'a str, message: String) {
-        self.checks
-            .entry(key)
-            .or_default()
-            .push(format!("{}: {}", field, message));
+        if self.is_skipped(key) {
+            return;
+        }
+        let message = self.field_message(key, field, message);
+        self.checks.entry(key).or_default().push(message);
+    }
+
+    pub fn warn(&mut self, key: &'a str, message: String) {
+        if self.is_skipped(key) {
+            return;
+        }
+        self.warnings.entry(key).or_default().push(message);
+    }
+
+    pub fn warn_field(&mut self, key: &'a str, field: &'a str, message: String) {
+        if self.is_skipped(key) {
+            return;
+        }
+        let message = self.field_message(key, field, message);
+        self.warnings.entry(key).or_default().push(message);
     }
 
     pub fn invalid(&mut self, key: &'a str, message: String) {
+        if self.is_skipped(key) {
+            return;
+        }
         self.errors.entry(key).or_default().push(message);
     }
 
     pub fn invalid_field(&mut self, key: &'a str, field: &'a str, message: String) {
-        self.errors
-            .entry(key)
-            .or_default()
-            .push(format!("{}: {}", field, message));
+        if self.is_skipped(key) {
+            return;
+        }
+        let message = self.field_message(key, field, message);
+        self.errors.entry(key).or_default().push(message);
+    }
+
+    /// Record the computed value of a field failing its check
+    pub fn suggest_fix(&mut self, key: &str, field: &str, path: &str, value: serde_json::Value) {
+        if self.is_skipped(key) {
+            return;
+        }
+        self.fixes.push(SuggestedFix {
+            key: key.to_owned(),
+            field: field.to_owned(),
+            path: path.to_owned(),
+            value,
+        });
+    }
+
+    /// Suggested fixes of the fields still reported as errors (accepted deviations excluded)
+    pub fn fixes(&self) -> Vec<&SuggestedFix> {
+        self.fixes
+            .iter()
+            .filter(|fix| {
+                self.errors.get(fix.key.as_str()).is_some_and(|messages| {
+                    messages
+                        .iter()
+                        .any(|m| m.starts_with(&format!("{}: ", fix.field)))
+                })
+            })
+            .collect()
     }
 }
 
@@ -132,18 +906,133 @@ macro_rules! required_or_ignored {
     };
 }
 
-#[macro_export]
-macro_rules! verify_equals {
-    ($ctx:expr, $key:expr, $field:expr, $expected:expr, $actual:expr, $error_fmt:expr) => {
-        if $actual == $expected {
-            $ctx.valid_field($key, $field, "verified".to_owned());
-        } else {
-            $ctx.invalid_field($key, $field, format!($error_fmt, $expected, $actual));
-        }
-    };
-}
-
 #[async_trait]
 pub trait Validate<'a> {
-    async fn validate(&self, ctx: &mut CovenantValidationContext<'a>) -> Result<(), Error>;
+    async fn validate(
+        &self,
+        ctx: &mut CovenantValidationContext<'a>,
+    ) -> Result<(), CovenantCliError>;
+
+    /// Full list of (key, field) pairs of the message the validation is expected to cover
+    fn fields(&self) -> Vec<(&'static str, &'static str)>;
+}
+
+/// Expand a list of message fields into (key, field) pairs
+fn fields_of(key: &'static str, fields: &[&'static str]) -> Vec<(&'static str, &'static str)> {
+    fields.iter().map(|field| (key, *field)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_keeps_the_fixes_of_the_forked_context() {
+        let mut ctx = CovenantValidationContext::new(CliContext::default());
+        let mut party_ctx = ctx.fork();
+        party_ctx.invalid_field(
+            "party_a_config",
+            "host_to_party_chain_channel_id",
+            "invalid channel id: expected channel-1 | actual channel-2".to_owned(),
+        );
+        party_ctx.suggest_fix(
+            "party_a_config",
+            "host_to_party_chain_channel_id",
+            "host_to_party_chain_channel_id",
+            serde_json::json!("channel-1"),
+        );
+        ctx.merge(party_ctx);
+
+        let fixes = ctx.fixes();
+        assert_eq!(fixes.len(), 1);
+        let instantiation = serde_json::json!({
+            "party_a_config": { "interchain": { "host_to_party_chain_channel_id": "channel-2" } }
+        });
+        assert_eq!(
+            crate::fixes::json_patch(&instantiation, &fixes),
+            vec![serde_json::json!({
+                "op": "replace",
+                "path": "/party_a_config/interchain/host_to_party_chain_channel_id",
+                "value": "channel-1",
+            })]
+        );
+    }
+
+    #[test]
+    fn forks_do_not_duplicate_the_fixes_recorded_before_forking() {
+        let mut ctx = CovenantValidationContext::new(CliContext::default());
+        ctx.invalid_field(
+            "contract_codes",
+            "clock_code",
+            "invalid code id: expected 1 | actual 2".to_owned(),
+        );
+        ctx.suggest_fix(
+            "contract_codes",
+            "clock_code",
+            "clock_code",
+            serde_json::json!(1),
+        );
+        let party_a_ctx = ctx.fork();
+        let party_b_ctx = ctx.fork();
+        ctx.merge(party_a_ctx);
+        ctx.merge(party_b_ctx);
+
+        let instantiation = serde_json::json!({ "contract_codes": { "clock_code": 2 } });
+        assert_eq!(
+            crate::fixes::json_patch(&instantiation, &ctx.fixes()),
+            vec![serde_json::json!({
+                "op": "replace",
+                "path": "/contract_codes/clock_code",
+                "value": 1,
+            })]
+        );
+    }
+
+    #[test]
+    fn duplicated_field_messages_keep_the_severest_status() {
+        let mut ctx = CovenantValidationContext::new(CliContext::default());
+        ctx.valid_field("splits", "receivers", "verified".to_owned());
+        ctx.invalid_field("splits", "receivers", "zero share".to_owned());
+        ctx.valid_field("splits", "receivers", "verified".to_owned());
+        ctx.dedupe_checks();
+
+        assert_eq!(ctx.errors()["splits"], vec!["receivers: zero share"]);
+        assert!(!ctx.checks().contains_key("splits"));
+    }
+
+    #[test]
+    fn occurrences_of_the_severest_status_are_counted() {
+        let mut ctx = CovenantValidationContext::new(CliContext::default());
+        ctx.invalid_field("fees", "ica_timeout", "too short".to_owned());
+        ctx.valid_field("fees", "ica_timeout", "verified".to_owned());
+        ctx.invalid_field("fees", "ica_timeout", "too short".to_owned());
+        ctx.invalid_field("fees", "ica_timeout", "too long".to_owned());
+        ctx.dedupe_checks();
+
+        assert_eq!(
+            ctx.errors()["fees"],
+            vec!["ica_timeout: too short; too long (×3)"]
+        );
+        assert!(!ctx.checks().contains_key("fees"));
+    }
+
+    #[test]
+    fn messages_without_a_field_are_not_merged() {
+        let mut ctx = CovenantValidationContext::new(CliContext::default());
+        ctx.invalid(
+            "covenant",
+            "covenant_terms: party_a amount does not match".to_owned(),
+        );
+        ctx.invalid_field("covenant", "covenant_terms", "a".to_owned());
+        ctx.invalid_field("covenant", "covenant_terms", "b".to_owned());
+        ctx.dedupe_checks();
+
+        assert_eq!(
+            ctx.errors()["covenant"],
+            vec![
+                "covenant_terms: party_a amount does not match",
+                "covenant_terms: a; b (×2)"
+            ]
+        );
+    }
 }
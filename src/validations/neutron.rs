@@ -1,4 +1,5 @@
-use anyhow::Error;
+use crate::error::CovenantCliError;
+use chrono::DateTime;
 use cw_utils::Expiration;
 use std::time::{SystemTime, UNIX_EPOCH};
 
@@ -10,29 +11,58 @@ pub async fn verify_expiration<'a>(
     key: &'a str,
     field: &'a str,
     deadline: Expiration,
-) -> Result<(), Error> {
+) -> Result<(), CovenantCliError> {
     match deadline {
         Expiration::AtHeight(height) => {
-            let cur_block = get_latest_block(&ctx.cli_context).await?;
-            if (height as u128) > cur_block {
-                ctx.valid_field(key, field, "verified".to_owned());
-            } else {
+            match get_latest_block(&ctx.cli_context, &ctx.host_chain_name()).await {
+                Ok(cur_block) if (height as u128) > cur_block => {
+                    ctx.valid_field(key, field, "verified".to_owned());
+                }
+                Ok(_) => {
+                    ctx.invalid_field(
+                        key,
+                        field,
+                        "invalid block height: should be in the future".to_owned(),
+                    );
+                }
+                Err(e) => {
+                    ctx.invalid_field(key, field, format!("failed fetching latest block: {}", e));
+                }
+            }
+        }
+        Expiration::AtTime(timestamp) => {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|e| CovenantCliError::ValidationAbort(e.to_string()))?;
+            let nanos = timestamp.nanos();
+            if let Some(unit) = mistaken_unit(nanos) {
                 ctx.invalid_field(
                     key,
                     field,
-                    "invalid block height: should be in the future".to_owned(),
+                    format!(
+                        "invalid timestamp: {} looks like {} (already expired), expected nanoseconds",
+                        nanos, unit
+                    ),
+                );
+            } else if timestamp.seconds() > now.as_secs() {
+                ctx.valid_field(
+                    key,
+                    field,
+                    format!(
+                        "expires {} (in {})",
+                        format_timestamp(timestamp.seconds()),
+                        format_duration(timestamp.seconds() - now.as_secs())
+                    ),
                 );
-            }
-        }
-        Expiration::AtTime(timestamp) => {
-            let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
-            if timestamp.seconds() > now.as_secs() {
-                ctx.valid_field(key, field, "verified".to_owned());
             } else {
                 ctx.invalid_field(
                     key,
                     field,
-                    "invalid timestamp: should be in the future".to_owned(),
+                    format!(
+                        "invalid timestamp: expired {} ({} ago), should be in the future",
+                        format_timestamp(timestamp.seconds()),
+                        format_duration(now.as_secs() - timestamp.seconds())
+                    ),
                 );
             }
         }
@@ -42,3 +72,200 @@ pub async fn verify_expiration<'a>(
     }
     Ok(())
 }
+
+// Estimated block time of the host chain, to convert block height deadlines to durations
+const ESTIMATED_BLOCK_TIME_MS: u64 = 1_100;
+// Combined timeouts should fit this many times in the time left before a deadline
+const MIN_DEADLINE_TIMEOUT_RATIO: u64 = 3;
+
+/// Compare the ICA and IBC transfer timeouts against the time left before a deadline: a
+/// transfer still pending when the deadline passes can deadlock the covenant
+pub async fn verify_timeouts_before_deadline<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    deadline_field: &str,
+    deadline: Expiration,
+    ica_timeout: u64,
+    ibc_transfer_timeout: u64,
+) {
+    let Some(remaining) = remaining_seconds(ctx, deadline).await else {
+        // Unreachable deadlines are reported by the expiration checks
+        return;
+    };
+    let mut exceeded = false;
+    for (field, timeout) in [
+        ("ica_timeout", ica_timeout),
+        ("ibc_transfer_timeout", ibc_transfer_timeout),
+    ] {
+        if timeout >= remaining {
+            exceeded = true;
+            ctx.invalid_field(
+                key,
+                field,
+                format!(
+                    "invalid timeout: {}s exceeds the time left before the {} (~{})",
+                    timeout,
+                    deadline_field,
+                    format_duration(remaining)
+                ),
+            );
+        }
+    }
+    if exceeded {
+        return;
+    }
+    let combined = ica_timeout.saturating_add(ibc_transfer_timeout);
+    if combined.saturating_mul(MIN_DEADLINE_TIMEOUT_RATIO) > remaining {
+        ctx.warn(
+            key,
+            format!(
+                "timeouts: {}s of ICA and transfer timeouts come close to the {} (~{} left)",
+                combined,
+                deadline_field,
+                format_duration(remaining)
+            ),
+        );
+    } else {
+        ctx.valid(
+            key,
+            format!(
+                "timeouts: {}s of ICA and transfer timeouts fit before the {} (~{} left)",
+                combined,
+                deadline_field,
+                format_duration(remaining)
+            ),
+        );
+    }
+}
+
+/// Check a deposit deadline leaves the configured buffer after the expected execution of the
+/// proposal, else the deadline may pass while the DAO vote is still ongoing
+pub async fn verify_deadline_after_execution<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    deadline: Expiration,
+) {
+    let Some(execution_time) = ctx.expected_execution_time() else {
+        return;
+    };
+    let Some(remaining) = remaining_seconds(ctx, deadline).await else {
+        return;
+    };
+    let Ok(now) = SystemTime::now().duration_since(UNIX_EPOCH) else {
+        return;
+    };
+    // Block height deadlines are estimated from the block time
+    let deadline_time = now.as_secs().saturating_add(remaining);
+    let buffer = ctx.execution_deadline_buffer_hours().saturating_mul(3_600);
+    let left = deadline_time.saturating_sub(execution_time);
+    if deadline_time <= execution_time {
+        ctx.invalid_field(
+            key,
+            field,
+            format!(
+                "invalid deadline: ~{} is before the expected execution on {}",
+                format_timestamp(deadline_time),
+                format_timestamp(execution_time)
+            ),
+        );
+    } else if left < buffer {
+        ctx.warn_field(
+            key,
+            field,
+            format!(
+                "deadline ~{} leaves {} after the expected execution on {} (expected at least {})",
+                format_timestamp(deadline_time),
+                format_duration(left),
+                format_timestamp(execution_time),
+                format_duration(buffer)
+            ),
+        );
+    } else {
+        ctx.valid_field(
+            key,
+            field,
+            format!(
+                "deadline leaves {} after the expected execution on {}",
+                format_duration(left),
+                format_timestamp(execution_time)
+            ),
+        );
+    }
+}
+
+/// Estimated seconds left before an expiration, none when it never expires or is unknown
+async fn remaining_seconds(
+    ctx: &CovenantValidationContext<'_>,
+    deadline: Expiration,
+) -> Option<u64> {
+    match deadline {
+        Expiration::AtHeight(height) => {
+            let cur_block = get_latest_block(&ctx.cli_context, &ctx.host_chain_name())
+                .await
+                .ok()?;
+            let blocks = (height as u128).saturating_sub(cur_block);
+            u64::try_from(blocks * ESTIMATED_BLOCK_TIME_MS as u128 / 1_000).ok()
+        }
+        Expiration::AtTime(timestamp) => {
+            if mistaken_unit(timestamp.nanos()).is_some() {
+                return None;
+            }
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?;
+            Some(timestamp.seconds().saturating_sub(now.as_secs()))
+        }
+        Expiration::Never {} => None,
+    }
+}
+
+// Nanosecond timestamps of covenants are after 2001-09-09 (1e18 ns)
+const MIN_TIMESTAMP_NANOS: u64 = 1_000_000_000_000_000_000;
+
+/// Unit a timestamp expected in nanoseconds was likely written in
+fn mistaken_unit(nanos: u64) -> Option<&'static str> {
+    match nanos {
+        n if n >= MIN_TIMESTAMP_NANOS => None,
+        n if n >= MIN_TIMESTAMP_NANOS / 1_000 => Some("microseconds"),
+        n if n >= MIN_TIMESTAMP_NANOS / 1_000_000 => Some("milliseconds"),
+        n if n >= MIN_TIMESTAMP_NANOS / 1_000_000_000 => Some("seconds"),
+        _ => None,
+    }
+}
+
+fn format_timestamp(seconds: u64) -> String {
+    DateTime::from_timestamp(seconds as i64, 0)
+        .map(|time| time.format("%Y-%m-%dT%H:%MZ").to_string())
+        .unwrap_or_else(|| seconds.to_string())
+}
+
+fn format_duration(seconds: u64) -> String {
+    match seconds {
+        s if s >= 2 * 86_400 => format!("{} days", s / 86_400),
+        s if s >= 2 * 3_600 => format!("{} hours", s / 3_600),
+        s => format!("{} minutes", s / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nanosecond_timestamps_are_accepted() {
+        // 2030-01-01T00:00:00Z
+        assert_eq!(mistaken_unit(1_893_456_000_000_000_000), None);
+    }
+
+    #[test]
+    fn timestamps_in_other_units_are_detected() {
+        assert_eq!(mistaken_unit(1_893_456_000_000_000), Some("microseconds"));
+        assert_eq!(mistaken_unit(1_893_456_000_000), Some("milliseconds"));
+        assert_eq!(mistaken_unit(1_893_456_000), Some("seconds"));
+    }
+
+    #[test]
+    fn small_values_are_not_timestamps() {
+        assert_eq!(mistaken_unit(0), None);
+        assert_eq!(mistaken_unit(999_999_999), None);
+    }
+}
@@ -0,0 +1,268 @@
+use cosmwasm_std::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::CovenantValidationContext;
+use crate::utils::astroport::{
+    get_astroport_pair_info, get_astroport_pcl_params, get_astroport_pool_info, CustomPair,
+    PairInfo, PairType, PCL_PAIR_TYPE,
+};
+use crate::utils::chain::get_balances;
+use crate::utils::cosmwasm::get_cw20_balance;
+use crate::utils::covenant::get_covenant_child_address;
+
+/// Message member holding the pools of a pool migration covenant
+pub const POOL_MIGRATION_KEY: &str = "pool_migration";
+pub const POOL_MIGRATION_FIELDS: &[(&str, &str)] = &[
+    (POOL_MIGRATION_KEY, "exit_pool"),
+    (POOL_MIGRATION_KEY, "entry_pool"),
+];
+
+/// Astroport pool the liquidity is withdrawn from or provided to
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MigrationPool {
+    pub pool_address: String,
+    /// `xyk`, `stable` or `custom-<type>`
+    pub pool_pair_type: String,
+    pub asset_a_denom: String,
+    pub asset_b_denom: String,
+    pub expected_spot_price: Decimal,
+    pub acceptable_price_spread: Decimal,
+}
+
+/// Liquidity moved from the exit pool to the entry pool
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PoolMigrationConfig {
+    pub exit_pool: MigrationPool,
+    pub entry_pool: MigrationPool,
+}
+
+/// Verify both pools of a migration and, in deployed mode, that the holder owns the
+/// liquidity tokens of the exit pool
+pub async fn verify_pool_migration(
+    ctx: &mut CovenantValidationContext<'_>,
+    config: &PoolMigrationConfig,
+) {
+    let key = POOL_MIGRATION_KEY;
+    if config.exit_pool.pool_address == config.entry_pool.pool_address {
+        ctx.invalid_field(
+            key,
+            "entry_pool",
+            "invalid entry pool: same pool as the exit pool".to_owned(),
+        );
+        return;
+    }
+    let mut exit_pair_info = None;
+    for (field, pool) in [
+        ("exit_pool", &config.exit_pool),
+        ("entry_pool", &config.entry_pool),
+    ] {
+        let host_chain_name = ctx.host_chain_name();
+        let pair_info =
+            match get_astroport_pair_info(ctx.cli_context(), &host_chain_name, &pool.pool_address)
+                .await
+            {
+                Ok(pair_info) => pair_info,
+                Err(e) => {
+                    ctx.invalid_field(key, field, format!("failed to query the pool: {}", e));
+                    continue;
+                }
+            };
+        if verify_migration_pool(ctx, key, field, pool, &pair_info).await {
+            ctx.valid_field(key, field, format!("{} verified", pool.pool_address));
+        }
+        if field == "exit_pool" {
+            exit_pair_info = Some(pair_info);
+        }
+    }
+
+    let exit_pool = &config.exit_pool;
+    let entry_pool = &config.entry_pool;
+    if (exit_pool.asset_a_denom != entry_pool.asset_a_denom
+        || exit_pool.asset_b_denom != entry_pool.asset_b_denom)
+        && (exit_pool.asset_a_denom != entry_pool.asset_b_denom
+            || exit_pool.asset_b_denom != entry_pool.asset_a_denom)
+    {
+        ctx.warn(
+            key,
+            format!(
+                "entry pool assets {}/{} differ from the exit pool assets {}/{}",
+                entry_pool.asset_a_denom,
+                entry_pool.asset_b_denom,
+                exit_pool.asset_a_denom,
+                exit_pool.asset_b_denom
+            ),
+        );
+    }
+
+    if let (Some(pair_info), Some(covenant_addr)) = (exit_pair_info, ctx.deployed_address()) {
+        verify_holder_position(ctx, key, &covenant_addr, &pair_info.liquidity_token).await;
+    }
+}
+
+/// Check the pair type, assets and price of a pool, record the failures
+async fn verify_migration_pool<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    pool: &MigrationPool,
+    pair_info: &PairInfo,
+) -> bool {
+    let mut verified = true;
+    let pair_type = pair_type_name(&pair_info.pair_type);
+    if pair_type != pool.pool_pair_type {
+        verified = false;
+        ctx.invalid_field(
+            key,
+            field,
+            format!(
+                "invalid pool pair type: expected {} | actual {}",
+                pool.pool_pair_type, pair_type
+            ),
+        );
+    }
+
+    let pair_denoms = pair_info
+        .asset_infos
+        .iter()
+        .map(|asset| {
+            asset
+                .native_token
+                .as_ref()
+                .map(|t| t.denom.clone())
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+    if pair_denoms != [pool.asset_a_denom.clone(), pool.asset_b_denom.clone()] {
+        verified = false;
+        ctx.invalid_field(
+            key,
+            field,
+            format!(
+                "invalid pool assets: expected {}/{} | actual {}",
+                pool.asset_a_denom,
+                pool.asset_b_denom,
+                pair_denoms.join("/")
+            ),
+        );
+    }
+
+    let host_chain_name = ctx.host_chain_name();
+    let current_price = if pair_type == format!("custom-{}", PCL_PAIR_TYPE) {
+        // PCL pools concentrate the liquidity around their price scale
+        get_astroport_pcl_params(ctx.cli_context(), &host_chain_name, &pool.pool_address)
+            .await
+            .ok()
+            .and_then(|params| params.price_scale.parse::<Decimal>().ok())
+    } else {
+        get_astroport_pool_info(ctx.cli_context(), &host_chain_name, &pool.pool_address)
+            .await
+            .ok()
+            .and_then(|pool_info| {
+                let amount = |i: usize| {
+                    pool_info
+                        .assets
+                        .get(i)
+                        .and_then(|a| a.amount.parse::<u128>().ok())
+                };
+                Decimal::checked_from_ratio(amount(0)?, amount(1)?).ok()
+            })
+    };
+    match current_price {
+        Some(price) if price.abs_diff(pool.expected_spot_price) > pool.acceptable_price_spread => {
+            verified = false;
+            ctx.invalid_field(
+                key,
+                field,
+                format!(
+                    "pool price {} outside of the expected spot price {} ± {}",
+                    price, pool.expected_spot_price, pool.acceptable_price_spread
+                ),
+            );
+        }
+        Some(_) => {}
+        None => {
+            verified = false;
+            ctx.invalid_field(key, field, "failed to compute the pool price".to_owned());
+        }
+    }
+    verified
+}
+
+/// The holder of the deployed covenant owns the liquidity tokens withdrawn from the exit pool
+async fn verify_holder_position<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    covenant_addr: &str,
+    liquidity_token: &str,
+) {
+    let host_chain_name = ctx.host_chain_name();
+    let cli_context = ctx.cli_context().clone();
+    let holder_addr = match get_covenant_child_address(
+        &cli_context,
+        &host_chain_name,
+        covenant_addr,
+        "holder_address",
+        json!({}),
+    )
+    .await
+    {
+        Ok(addr) => addr,
+        Err(e) => {
+            ctx.invalid(key, format!("failed to resolve the holder: {}", e));
+            return;
+        }
+    };
+    // Liquidity tokens are cw20 contracts on older pairs, token factory denoms on newer ones
+    let owned = if bech32::decode(liquidity_token).is_ok() {
+        get_cw20_balance(
+            &cli_context,
+            &host_chain_name,
+            liquidity_token,
+            &holder_addr,
+        )
+        .await
+        .map(|balance| balance > 0)
+    } else {
+        get_balances(&cli_context, &host_chain_name, &holder_addr)
+            .await
+            .map(|balances| balances.iter().any(|b| b.denom == liquidity_token))
+    };
+    match owned {
+        Ok(true) => ctx.valid(
+            key,
+            format!(
+                "holder {} owns the exit pool LP token {}",
+                holder_addr, liquidity_token
+            ),
+        ),
+        Ok(false) => ctx.invalid(
+            key,
+            format!(
+                "holder {} owns no exit pool LP token {}",
+                holder_addr, liquidity_token
+            ),
+        ),
+        Err(e) => ctx.invalid(
+            key,
+            format!("failed to query the holder LP token balance: {}", e),
+        ),
+    }
+}
+
+/// Pair type as written in the liquid pooler configs
+fn pair_type_name(pair_type: &PairType) -> String {
+    match pair_type {
+        PairType { xyk: Some(_), .. } => "xyk".to_owned(),
+        PairType {
+            stable: Some(_), ..
+        } => "stable".to_owned(),
+        PairType {
+            custom: Some(CustomPair(custom_type)),
+            ..
+        } => format!("custom-{}", custom_type),
+        _ => String::new(),
+    }
+}
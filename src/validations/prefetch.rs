@@ -0,0 +1,62 @@
+use itertools::Itertools;
+use log::debug;
+use tokio::task::JoinSet;
+
+use super::{CovenantValidationContext, LsProvider, PERSISTENCE_CHAIN_NAME, STRIDE_CHAIN_NAME};
+use crate::utils::assets::get_chain_assets;
+use crate::utils::chain::get_chain_info;
+use crate::utils::path::get_path_info;
+
+/// Fetch concurrently into the run cache the registry files implied by the metadata
+/// (chain and asset lists of every chain, IBC paths from the host chain), so the
+/// sequential checks do not wait on the network
+pub async fn prefetch_registry(ctx: &CovenantValidationContext<'_>) {
+    let host_chain_name = ctx.host_chain_name();
+    let ls_provider_chain = match ctx.ls_provider {
+        LsProvider::Stride => STRIDE_CHAIN_NAME,
+        LsProvider::Persistence => PERSISTENCE_CHAIN_NAME,
+    };
+    let mut chain_names = vec![
+        host_chain_name.clone(),
+        ctx.party_a_chain_name(),
+        ctx.party_b_chain_name(),
+    ];
+    if ctx.covenant_contract.contains("single-party") {
+        chain_names.push(ls_provider_chain.to_owned());
+    }
+    let chain_names = chain_names
+        .into_iter()
+        .filter(|chain_name| !chain_name.is_empty())
+        .unique()
+        .collect::<Vec<_>>();
+
+    let mut tasks = JoinSet::new();
+    for chain_name in &chain_names {
+        let cli_context = ctx.cli_context().clone();
+        let chain_name = chain_name.clone();
+        tasks.spawn(async move { get_chain_info(&cli_context, &chain_name).await.map(|_| ()) });
+        let cli_context = ctx.cli_context().clone();
+        let chain_name = chain_name.clone();
+        tasks.spawn(async move {
+            get_chain_assets(&cli_context, &chain_name)
+                .await
+                .map(|_| ())
+        });
+        if *chain_name != host_chain_name {
+            let cli_context = ctx.cli_context().clone();
+            let host_chain_name = host_chain_name.clone();
+            let chain_name = chain_name.clone();
+            tasks.spawn(async move {
+                get_path_info(&cli_context, &host_chain_name, &chain_name)
+                    .await
+                    .map(|_| ())
+            });
+        }
+    }
+    while let Some(result) = tasks.join_next().await {
+        if let Ok(Err(e)) = result {
+            // The checks needing the file report the error
+            debug!("prefetch failed: {}", e);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use itertools::Itertools;
+
+use super::CovenantValidationContext;
+
+/// How the actual value of a field is compared with its resolved values
+#[derive(Clone, Copy, Debug)]
+pub enum Comparator {
+    /// Actual value equals the single resolved value
+    Equals,
+    /// Actual value is one of the resolved candidates
+    OneOf,
+}
+
+/// Report level of a failed rule
+#[derive(Clone, Copy, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// Declarative check of a message field against values resolved by the validator
+pub struct Rule<'a> {
+    field: &'a str,
+    /// Name of the compared value in the report (e.g. "channel id")
+    subject: &'static str,
+    expected: Vec<String>,
+    actual: String,
+    comparator: Comparator,
+    severity: Severity,
+}
+
+impl<'a> Rule<'a> {
+    /// Field must equal the resolved value
+    pub fn equals(
+        field: &'a str,
+        subject: &'static str,
+        expected: impl Into<String>,
+        actual: impl Into<String>,
+    ) -> Self {
+        Rule {
+            field,
+            subject,
+            expected: vec![expected.into()],
+            actual: actual.into(),
+            comparator: Comparator::Equals,
+            severity: Severity::Error,
+        }
+    }
+
+    /// Field must be one of the resolved candidates
+    pub fn one_of(
+        field: &'a str,
+        subject: &'static str,
+        candidates: Vec<String>,
+        actual: impl Into<String>,
+    ) -> Self {
+        Rule {
+            comparator: Comparator::OneOf,
+            expected: candidates,
+            ..Rule::equals(field, subject, "", actual)
+        }
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    fn matches(&self) -> bool {
+        match self.comparator {
+            Comparator::Equals => self.expected.first() == Some(&self.actual),
+            Comparator::OneOf => self.expected.contains(&self.actual),
+        }
+    }
+}
+
+/// Run the rules declared for a key, recording a result per rule
+pub fn run_rules<'a>(ctx: &mut CovenantValidationContext<'a>, key: &'a str, rules: Vec<Rule<'a>>) {
+    for rule in rules {
+        if rule.matches() {
+            ctx.valid_field(key, rule.field, "verified".to_owned());
+            continue;
+        }
+        let message = format!(
+            "invalid {}: expected {} | actual {}",
+            rule.subject,
+            rule.expected.iter().join(" or "),
+            rule.actual
+        );
+        match rule.severity {
+            Severity::Error => {
+                ctx.invalid_field(key, rule.field, message);
+                // Unresolved values (e.g. no channel on the path) are not a fix
+                if let (Comparator::Equals, Some(expected)) =
+                    (rule.comparator, rule.expected.first())
+                {
+                    if !expected.is_empty() {
+                        ctx.suggest_fix(key, rule.field, rule.field, expected.clone().into());
+                    }
+                }
+            }
+            Severity::Warning => ctx.warn_field(key, rule.field, message),
+        }
+    }
+}
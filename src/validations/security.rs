@@ -0,0 +1,148 @@
+use serde_json::Value;
+
+use super::{CovenantValidationContext, SECURITY_KEY};
+use crate::utils::cosmwasm::{get_contract_info, get_contract_version};
+use crate::utils::covenant::get_covenant_children;
+
+/// Check the admin of the deployed covenant and of its child contracts: children may only
+/// be migrated by the covenant or the declared DAO, the covenant only by the DAO
+pub async fn verify_admin_surface(ctx: &mut CovenantValidationContext<'_>, covenant_addr: &str) {
+    let host_chain_name = ctx.host_chain_name();
+    let expected_admin = ctx.expected_admin();
+    let cli_context = ctx.cli_context().clone();
+
+    let mut contracts = vec![("covenant", covenant_addr.to_owned())];
+    contracts.extend(get_covenant_children(&cli_context, &host_chain_name, covenant_addr).await);
+    for (name, addr) in contracts {
+        let info = match get_contract_info(&cli_context, &host_chain_name, &addr).await {
+            Ok(info) => info,
+            Err(e) => {
+                ctx.invalid(
+                    SECURITY_KEY,
+                    format!("{}: failed to query the admin of {}: {}", name, addr, e),
+                );
+                continue;
+            }
+        };
+        let allowed = if name == "covenant" {
+            info.admin.is_empty() || Some(&info.admin) == expected_admin.as_ref()
+        } else {
+            info.admin.is_empty()
+                || info.admin == covenant_addr
+                || Some(&info.admin) == expected_admin.as_ref()
+        };
+        if info.admin.is_empty() {
+            ctx.valid(SECURITY_KEY, format!("{}: not migratable", name));
+        } else if allowed {
+            ctx.valid(
+                SECURITY_KEY,
+                format!("{}: migratable by {}", name, info.admin),
+            );
+        } else {
+            ctx.invalid(
+                SECURITY_KEY,
+                format!(
+                    "{}: migratable by an unexpected admin: expected {} | actual {}",
+                    name,
+                    if name == "covenant" {
+                        expected_admin.clone().unwrap_or_else(|| "none".to_owned())
+                    } else {
+                        covenant_addr.to_owned()
+                    },
+                    info.admin
+                ),
+            );
+        }
+    }
+}
+
+/// Check the cw2 version of the deployed covenant and of its child contracts matches the
+/// declared release, a deployment may mix contracts of several releases
+pub async fn verify_contract_versions(
+    ctx: &mut CovenantValidationContext<'_>,
+    covenant_addr: &str,
+) {
+    let key = "contract_codes";
+    let field = "contract_version";
+    let host_chain_name = ctx.host_chain_name();
+    // Releases are tagged `v0.1.0`, cw2 stores the crate version `0.1.0`
+    let release_version = ctx.contract_codes_version();
+    let expected_version = release_version.trim_start_matches('v').to_owned();
+    let cli_context = ctx.cli_context().clone();
+
+    let mut contracts = vec![("covenant", covenant_addr.to_owned())];
+    contracts.extend(get_covenant_children(&cli_context, &host_chain_name, covenant_addr).await);
+    for (name, addr) in contracts {
+        match get_contract_version(&cli_context, &host_chain_name, &addr).await {
+            Ok(info) if info.version == expected_version => ctx.valid_field(
+                key,
+                field,
+                format!("{}: {} {}", name, info.contract, info.version),
+            ),
+            Ok(info) => ctx.invalid_field(
+                key,
+                field,
+                format!(
+                    "{}: {} not from release {}: expected {} | actual {}",
+                    name, info.contract, release_version, expected_version, info.version
+                ),
+            ),
+            Err(e) => ctx.warn_field(
+                key,
+                field,
+                format!(
+                    "{}: failed to query the cw2 version of {}: {}",
+                    name, addr, e
+                ),
+            ),
+        }
+    }
+}
+
+// Message fields granting privileges on the clock, not expected at instantiation
+const CLOCK_PRIVILEGE_FIELDS: &[&str] = &["whitelist", "clock_whitelist", "initial_queue"];
+
+/// Check the message does not pre-populate the clock whitelist or queue: the covenant
+/// enqueues its children itself, any other entry is a privileged account
+pub fn verify_clock_privileges(ctx: &mut CovenantValidationContext<'_>, instantiation: &Value) {
+    let key = "covenant";
+    let field = "clock_whitelist";
+    let entries = privileged_entries(instantiation);
+    if entries.is_empty() {
+        ctx.valid_field(
+            key,
+            field,
+            "empty, children are enqueued by the covenant".to_owned(),
+        );
+    } else {
+        ctx.invalid_field(
+            key,
+            field,
+            format!("invalid clock privileges: {}", entries.join(", ")),
+        );
+    }
+}
+
+/// Non-empty clock privilege fields at any depth of the message
+fn privileged_entries(value: &Value) -> Vec<String> {
+    match value {
+        Value::Object(map) => map
+            .iter()
+            .flat_map(|(name, value)| {
+                let is_set = match value {
+                    Value::Null => false,
+                    Value::Array(items) => !items.is_empty(),
+                    Value::Object(map) => !map.is_empty(),
+                    _ => true,
+                };
+                let mut entries = privileged_entries(value);
+                if CLOCK_PRIVILEGE_FIELDS.contains(&name.as_str()) && is_set {
+                    entries.insert(0, format!("{} = {}", name, value));
+                }
+                entries
+            })
+            .collect(),
+        Value::Array(items) => items.iter().flat_map(privileged_entries).collect(),
+        _ => vec![],
+    }
+}
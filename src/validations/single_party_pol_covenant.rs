@@ -1,23 +1,43 @@
-use anyhow::Error;
+use crate::error::CovenantCliError;
 use async_trait::async_trait;
+use itertools::Itertools;
 use log::{debug, info};
 use rust_decimal::prelude::{One, Zero};
 use rust_decimal::Decimal;
+use serde_json::json;
 use sha2::{Digest, Sha256};
 use single_party_pol_covenant::msg as sppc;
 
-use super::{CovenantValidationContext, Validate};
-use crate::utils::assets::get_chain_asset_info;
-use crate::utils::chain::get_chain_info;
+use super::{
+    fields_of, CovenantValidationContext, Validate, FEES_FIELDS, INTERCHAIN_PARTY_FIELDS,
+    LIQUID_POOLER_FIELDS, NATIVE_PARTY_FIELDS, POOL_PRICE_FIELDS,
+};
+use crate::context::CliContext;
+use crate::required_or_ignored;
+use crate::utils::assets::{get_chain_asset_info, get_chain_assets};
+use crate::utils::astroport::{get_astroport_native_token, NEUTRON_COIN_REGISTRY_CONTRACT_ADDRESS};
+use crate::utils::chain::{get_chain_info, ChainInfo};
+use crate::utils::covenant::{get_covenant_child_address, get_ica_address};
 use crate::utils::path::{get_path_info, IBCPath};
-use crate::validations::neutron::verify_expiration;
+use crate::utils::persistence::{
+    get_host_chain, get_liquid_stake_params, get_unbonding_period, stk_denom, HostChain,
+};
+use crate::validations::label::verify_label;
+use crate::validations::neutron::{verify_expiration, verify_timeouts_before_deadline};
 use crate::validations::{
+    address::{check_address, verify_address},
     astroport::verify_astroport_liquid_pooler_config,
     contracts::{get_covenant_code_ids, verify_code_id},
-    NEUTRON_CHAIN_NAME, STRIDE_CHAIN_NAME, TRANSFER_PORT_ID,
+    contributions::verify_minimum_contribution,
+    fees::{verify_ibc_fee, verify_timeout},
+    ibc::{
+        get_path_connection_and_channels, verify_channel_uses_wasm_port, verify_path_clients,
+        verify_pfm_consistency, verify_transfer_channel,
+    },
+    rules::{run_rules, Rule, Severity},
+    STRIDE_CHAIN_NAME, TRANSFER_PORT_ID,
 };
-use crate::validations::{LsProvider, PERSISTENCE_CHAIN_NAME};
-use crate::{required_or_ignored, verify_equals};
+use crate::validations::{LsProvider, NEUTRON_CHAIN_NAME, PERSISTENCE_CHAIN_NAME};
 
 /// Validate the single party POL covenant instantiation message
 pub struct SinglePartyPolCovenantInstMsg(single_party_pol_covenant::msg::InstantiateMsg);
@@ -34,92 +54,134 @@ impl<'a> SinglePartyPolCovenantInstMsg {
 
 #[async_trait]
 impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
-    async fn validate(&self, ctx: &mut CovenantValidationContext) -> Result<(), Error> {
+    async fn validate(&self, ctx: &mut CovenantValidationContext) -> Result<(), CovenantCliError> {
         // Validate the single party POL covenant instantiation message
         let msg = &self.0;
         debug!("valence-covenant-single-party-pol: {:?}", msg);
 
         info!("Processing covenant {:?}", msg.label);
 
-        // Covenant label
-        let mut key = "covenant";
-        let mut field = "label";
-        if msg.label.is_empty() {
-            ctx.invalid_field(key, field, "required".to_owned());
-        } else {
-            ctx.valid_field(key, field, "valid".to_owned());
-        }
-
         // Lockup period
-        field = "lockup_period";
+        let mut key = "covenant";
+        let mut field = "lockup_period";
         verify_expiration(ctx, key, field, msg.lockup_period).await?;
 
         // Contract Codes
         key = "contract_codes";
         verify_single_party_pol_covenant_code_ids(ctx, key, &msg.contract_codes).await?;
 
+        // Covenant label, checked against the covenants of the same code
+        verify_label(ctx, "covenant", &msg.label).await;
+
+        // Fees and timeouts
+        key = "fees";
+        verify_timeout(ctx, key, "ica_timeout", msg.timeouts.ica_timeout.u64());
+        verify_timeout(
+            ctx,
+            key,
+            "ibc_transfer_timeout",
+            msg.timeouts.ibc_transfer_timeout.u64(),
+        );
+        verify_timeouts_before_deadline(
+            ctx,
+            key,
+            "lockup_period",
+            msg.lockup_period,
+            msg.timeouts.ica_timeout.u64(),
+            msg.timeouts.ibc_transfer_timeout.u64(),
+        )
+        .await;
+        verify_ibc_fee(
+            ctx,
+            key,
+            msg.preset_ibc_fee.ack_fee.u128(),
+            msg.preset_ibc_fee.timeout_fee.u128(),
+        )
+        .await?;
+
         // Covenant party config
         key = "covenant_party_config";
+        let host_chain_name = ctx.host_chain_name();
         let party_chain_name = ctx.party_a_chain_name();
         let path_info =
-            get_path_info(&ctx.cli_context, &party_chain_name, NEUTRON_CHAIN_NAME).await?;
-        debug!(
-            "party_a_uses_wasm_port: {}",
-            ctx.party_a_channel_uses_wasm_port
+            get_path_info(&ctx.cli_context, &party_chain_name, &host_chain_name).await?;
+        let party_channel_uses_wasm_port = verify_channel_uses_wasm_port(
+            ctx,
+            key,
+            &path_info,
+            &host_chain_name,
+            ctx.party_a_channel_uses_wasm_port(),
         );
+        debug!("party_a_uses_wasm_port: {}", party_channel_uses_wasm_port);
         let (expected_connection_id, expected_h2p_channel_id, expected_p2h_channel_id) =
-            get_path_connection_and_channels(&path_info, ctx.party_a_channel_uses_wasm_port);
-
-        field = "party_chain_connection_id";
-        let party_chain_connection_id = msg.covenant_party_config.party_chain_connection_id.clone();
-        verify_equals!(
+            get_path_connection_and_channels(
+                &path_info,
+                &host_chain_name,
+                party_channel_uses_wasm_port,
+            );
+        verify_transfer_channel(
             ctx,
             key,
-            field,
-            expected_connection_id,
-            party_chain_connection_id,
-            "invalid connection id: expected {} | actual {}"
+            &path_info,
+            &host_chain_name,
+            &expected_h2p_channel_id,
+        );
+        verify_path_clients(ctx, key, &path_info).await;
+        verify_pfm_consistency(
+            ctx,
+            key,
+            &party_chain_name,
+            &expected_h2p_channel_id,
+            msg.covenant_party_config.denom_to_pfm_map.len(),
         );
 
-        field = "host_to_party_chain_channel_id";
+        let party_chain_connection_id = msg.covenant_party_config.party_chain_connection_id.clone();
         let host_to_party_chain_channel_id = msg
             .covenant_party_config
             .host_to_party_chain_channel_id
             .clone();
-        verify_equals!(
-            ctx,
-            key,
-            field,
-            expected_h2p_channel_id,
-            msg.covenant_party_config.host_to_party_chain_channel_id,
-            "invalid channel id: expected {} | actual {}"
-        );
-
-        field = "party_to_host_chain_channel_id";
         let party_to_host_chain_channel_id = msg
             .covenant_party_config
             .party_to_host_chain_channel_id
             .clone();
-        verify_equals!(
+        run_rules(
             ctx,
             key,
-            field,
-            expected_p2h_channel_id,
-            party_to_host_chain_channel_id,
-            "invalid channel id: expected {} | actual {}"
+            vec![
+                Rule::equals(
+                    "party_chain_connection_id",
+                    "connection id",
+                    expected_connection_id,
+                    &party_chain_connection_id,
+                ),
+                Rule::equals(
+                    "host_to_party_chain_channel_id",
+                    "channel id",
+                    expected_h2p_channel_id,
+                    &host_to_party_chain_channel_id,
+                ),
+                Rule::equals(
+                    "party_to_host_chain_channel_id",
+                    "channel id",
+                    expected_p2h_channel_id,
+                    &party_to_host_chain_channel_id,
+                ),
+            ],
         );
 
         field = "remote_chain_denom";
         let party_chain_info = get_chain_info(&ctx.cli_context, &party_chain_name).await?;
         let expected_remote_chain_denom = party_chain_info.denom.clone();
         let remote_chain_denom = msg.covenant_party_config.remote_chain_denom.clone();
-        verify_equals!(
+        run_rules(
             ctx,
             key,
-            field,
-            expected_remote_chain_denom,
-            remote_chain_denom,
-            "invalid denom: expected {} | actual {}"
+            vec![Rule::equals(
+                field,
+                "denom",
+                &expected_remote_chain_denom,
+                &remote_chain_denom,
+            )],
         );
 
         field = "native_denom";
@@ -137,13 +199,15 @@ impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
             )
         );
         let native_denom = msg.covenant_party_config.native_denom.clone();
-        verify_equals!(
+        run_rules(
             ctx,
             key,
-            field,
-            expected_native_denom,
-            native_denom,
-            "invalid denom: expected {} | actual {}"
+            vec![Rule::equals(
+                field,
+                "denom",
+                expected_native_denom,
+                native_denom,
+            )],
         );
 
         field = "contribution";
@@ -166,12 +230,48 @@ impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
                 field,
                 format!("{:.2} {}", contribution_amount, party_chain_info.display),
             );
+            verify_minimum_contribution(
+                ctx,
+                key,
+                &remote_chain_denom,
+                msg.covenant_party_config.contribution.amount.u128(),
+            );
+        }
+
+        field = "party_receiver_addr";
+        let party_receiver_addr = &msg.covenant_party_config.party_receiver_addr;
+        match (
+            check_address(party_receiver_addr, &party_chain_info.bech32_prefix),
+            ctx.withdrawal_address(),
+        ) {
+            (Err(e), _) => ctx.invalid_field(key, field, e),
+            (Ok(()), Some(withdrawal_address)) if withdrawal_address != *party_receiver_addr => ctx
+                .invalid_field(
+                    key,
+                    field,
+                    format!(
+                        "invalid receiver: expected withdrawal address {} | actual {}",
+                        withdrawal_address, party_receiver_addr
+                    ),
+                ),
+            (Ok(()), Some(_)) => {
+                ctx.valid_field(key, field, "verified (withdrawal address)".to_owned())
+            }
+            (Ok(()), None) => ctx.valid_field(key, field, "verified".to_owned()),
         }
 
+        // The party address is authorized on the host chain
+        field = "addr";
+        let host_chain_info = get_chain_info(&ctx.cli_context, &host_chain_name).await?;
+        verify_address(
+            ctx,
+            key,
+            field,
+            &msg.covenant_party_config.addr,
+            &host_chain_info.bech32_prefix,
+        );
+
         //TODO: Validate the rest of the covenant party config
-        // field = "party_receiver_addr";
-        // field = "addr";
-        // field = "denom_to_pfm_map";
         // field = "fallback_address";
 
         // LS info (Neutron -> Stride)
@@ -181,72 +281,82 @@ impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
             LsProvider::Persistence => PERSISTENCE_CHAIN_NAME,
         };
         let path_info =
-            get_path_info(&ctx.cli_context, NEUTRON_CHAIN_NAME, ls_provider_chain).await?;
-        let (expected_connection_id, expected_channel_id, reverse_channel_id) =
-            if path_info.chain_1.chain_name == NEUTRON_CHAIN_NAME {
-                path_info
-                    .channels
-                    .iter()
-                    .filter_map(|c| {
-                        if c.chain_2.port_id == TRANSFER_PORT_ID
-                            && ((ctx.party_a_channel_uses_wasm_port
-                                && c.chain_1.port_id.starts_with("wasm."))
-                                || c.chain_1.port_id == TRANSFER_PORT_ID)
-                        {
-                            Some((
-                                path_info.chain_1.connection_id.clone(),
-                                c.chain_2.channel_id.clone(),
-                                c.chain_1.channel_id.clone(),
-                            ))
-                        } else {
-                            None
-                        }
-                    })
-                    .next()
-                    .unwrap()
-            } else {
-                path_info
-                    .channels
-                    .iter()
-                    .filter_map(|c| {
-                        if c.chain_1.port_id == TRANSFER_PORT_ID
-                            && ((ctx.party_a_channel_uses_wasm_port
-                                && c.chain_2.port_id.starts_with("wasm."))
-                                || (!ctx.party_a_channel_uses_wasm_port
-                                    && c.chain_2.port_id == TRANSFER_PORT_ID))
-                        {
-                            Some((
-                                path_info.chain_1.connection_id.clone(),
-                                c.chain_1.channel_id.clone(),
-                                c.chain_2.channel_id.clone(),
-                            ))
-                        } else {
-                            None
-                        }
-                    })
-                    .next()
-                    .unwrap()
-            };
-
-        field = "ls_neutron_connection_id";
-        verify_equals!(
+            get_path_info(&ctx.cli_context, &host_chain_name, ls_provider_chain).await?;
+        let expected_connection_id = if path_info.chain_1.chain_name == host_chain_name {
+            path_info.chain_1.connection_id.clone()
+        } else {
+            path_info.chain_2.connection_id.clone()
+        };
+        // ICA channels may share the connection: only ICS-20 channels are candidates and
+        // the preferred one is picked when there are several
+        let candidates = get_ls_transfer_channels(&path_info, &host_chain_name);
+        let (reverse_channel_id, expected_channel_id) = candidates
+            .iter()
+            .find(|c| c.preferred)
+            .or(candidates.first())
+            .map(|c| (c.host_channel_id.clone(), c.ls_channel_id.clone()))
+            .unwrap_or_default();
+        match candidates.len() {
+            0 => ctx.invalid(
+                key,
+                format!(
+                    "no ICS-20 channel found on connection {} to {}",
+                    expected_connection_id, ls_provider_chain
+                ),
+            ),
+            1 => ctx.valid(
+                key,
+                format!(
+                    "expected channel: {} (ICS-20 on connection {})",
+                    expected_channel_id, expected_connection_id
+                ),
+            ),
+            _ => ctx.warn(
+                key,
+                format!(
+                    "expected channel: {} (out of ICS-20 channels {} on connection {})",
+                    expected_channel_id,
+                    candidates.iter().map(|c| &c.ls_channel_id).join(", "),
+                    expected_connection_id
+                ),
+            ),
+        }
+        verify_transfer_channel(
             ctx,
             key,
-            field,
-            expected_connection_id,
-            msg.ls_info.ls_neutron_connection_id,
-            "invalid connection id: expected {} | actual {}"
+            &path_info,
+            ls_provider_chain,
+            &expected_channel_id,
         );
+        verify_path_clients(ctx, key, &path_info).await;
 
-        field = "ls_chain_to_neutron_channel_id";
-        verify_equals!(
-            ctx,
-            key,
-            field,
-            expected_channel_id,
-            msg.ls_info.ls_chain_to_neutron_channel_id,
-            "invalid channel id: expected {} | actual {}"
-        );
+        let mut rules = vec![
+            Rule::equals(
+                "ls_neutron_connection_id",
+                "connection id",
+                expected_connection_id,
+                &msg.ls_info.ls_neutron_connection_id,
+            ),
+            Rule::one_of(
+                "ls_chain_to_neutron_channel_id",
+                "channel id",
+                candidates.iter().map(|c| c.ls_channel_id.clone()).collect(),
+                &msg.ls_info.ls_chain_to_neutron_channel_id,
+            ),
+        ];
+        // Another ICS-20 channel than the preferred one works but deserves a look
+        if candidates.len() > 1 {
+            rules.push(
+                Rule::equals(
+                    "ls_chain_to_neutron_channel_id",
+                    "preferred channel id",
+                    &expected_channel_id,
+                    &msg.ls_info.ls_chain_to_neutron_channel_id,
+                )
+                .with_severity(Severity::Warning),
+            );
+        }
+        run_rules(ctx, key, rules);
 
         field = "ls_denom";
         let ls_denom = msg.ls_info.ls_denom.clone();
@@ -268,6 +378,17 @@ impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
         } else {
             ctx.invalid_field(key, field, format!("unknown denom: {}", ls_denom));
         }
+        if matches!(ctx.ls_provider, LsProvider::Persistence) {
+            verify_persistence_host_chain(
+                ctx,
+                key,
+                &party_chain_name,
+                &party_chain_info.chain_id,
+                &remote_chain_denom,
+                &ls_denom,
+            )
+            .await;
+        }
 
         field = "ls_denom_on_neutron";
         let expected_ls_denom_on_neutron = format!(
@@ -280,55 +401,49 @@ impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
             )
         );
         let ls_denom_on_neutron = msg.ls_info.ls_denom_on_neutron.clone();
-        verify_equals!(
+        run_rules(
             ctx,
             key,
-            field,
-            expected_ls_denom_on_neutron,
-            ls_denom_on_neutron,
-            "invalid denom: expected {} | actual {}"
+            vec![Rule::equals(
+                field,
+                "denom",
+                &expected_ls_denom_on_neutron,
+                &ls_denom_on_neutron,
+            )],
         );
+        verify_denom_registered(ctx, key, field, &expected_ls_denom_on_neutron).await;
 
         // Remote chain splitter
         key = "remote_chain_splitter_config";
-        field = "connection_id";
-        verify_equals!(
-            ctx,
-            key,
-            field,
-            party_chain_connection_id,
-            msg.remote_chain_splitter_config.connection_id,
-            "invalid connection id: expected {} | actual {}"
-        );
-
-        field = "channel_id";
-        verify_equals!(
+        run_rules(
             ctx,
             key,
-            field,
-            host_to_party_chain_channel_id,
-            msg.remote_chain_splitter_config.channel_id,
-            "invalid channel id: expected {} | actual {}"
-        );
-
-        field = "denom";
-        verify_equals!(
-            ctx,
-            key,
-            field,
-            remote_chain_denom,
-            msg.remote_chain_splitter_config.denom,
-            "invalid denom: expected {} | actual {}"
-        );
-
-        field = "amount";
-        verify_equals!(
-            ctx,
-            key,
-            field,
-            msg.covenant_party_config.contribution.amount,
-            msg.remote_chain_splitter_config.amount,
-            "invalid amount: expected {} | actual {}"
+            vec![
+                Rule::equals(
+                    "connection_id",
+                    "connection id",
+                    &party_chain_connection_id,
+                    &msg.remote_chain_splitter_config.connection_id,
+                ),
+                Rule::equals(
+                    "channel_id",
+                    "channel id",
+                    &host_to_party_chain_channel_id,
+                    &msg.remote_chain_splitter_config.channel_id,
+                ),
+                Rule::equals(
+                    "denom",
+                    "denom",
+                    &remote_chain_denom,
+                    &msg.remote_chain_splitter_config.denom,
+                ),
+                Rule::equals(
+                    "amount",
+                    "amount",
+                    msg.covenant_party_config.contribution.amount.to_string(),
+                    msg.remote_chain_splitter_config.amount.to_string(),
+                ),
+            ],
         );
 
         field = "ls_share";
@@ -369,217 +484,335 @@ impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
             );
         }
 
-        // ls_share + native_share should sum up to 1
-        if ls_share + native_share == Decimal::one() {
-            ctx.valid_field(key, "ls_share + native_share", "verified".to_owned());
-        } else {
-            ctx.invalid_field(
+        // ls_share + native_share should sum up to 1, compared on the exact atomics
+        let one_atomics = cosmwasm_std::Decimal::one().atomics();
+        match msg
+            .remote_chain_splitter_config
+            .ls_share
+            .atomics()
+            .checked_add(msg.remote_chain_splitter_config.native_share.atomics())
+        {
+            Ok(sum_atomics) if sum_atomics == one_atomics => {
+                ctx.valid_field(key, "ls_share + native_share", "verified".to_owned());
+            }
+            Ok(sum_atomics) => {
+                // Atomics may exceed i128, the sign of the difference is carried apart
+                let (sign, delta) = match sum_atomics.checked_sub(one_atomics) {
+                    Ok(delta) => ('+', delta),
+                    Err(_) => ('-', one_atomics - sum_atomics),
+                };
+                ctx.invalid_field(
+                    key,
+                    "ls_share + native_share",
+                    format!(
+                        "invalid share: should sum up to 1 | actual {} ({}{} atomics)",
+                        cosmwasm_std::Decimal::new(sum_atomics),
+                        sign,
+                        delta
+                    ),
+                )
+            }
+            Err(e) => ctx.invalid_field(
                 key,
                 "ls_share + native_share",
-                "invalid share: should sum up to 1".to_owned(),
-            );
+                format!("invalid share: {}", e),
+            ),
         }
 
-        // LP forwarder config
-        key = "lp_forwarder_config";
-        if let sppc::CovenantPartyConfig::Interchain(lp_fwd_cfg) = &msg.lp_forwarder_config {
-            field = "party_receiver_addr";
-            required_or_ignored!(ctx, key, field, &lp_fwd_cfg.party_receiver_addr);
-
-            field = "addr";
-            required_or_ignored!(ctx, key, field, &lp_fwd_cfg.addr);
-
-            field = "host_to_party_chain_channel_id";
-            required_or_ignored!(ctx, key, field, &lp_fwd_cfg.host_to_party_chain_channel_id);
-
-            field = "native_denom";
-            required_or_ignored!(ctx, key, field, &lp_fwd_cfg.native_denom);
-
-            field = "remote_chain_denom";
-            verify_equals!(
-                ctx,
+        // Split receivers: the covenant routes `ls_share` of the splitter amount to the
+        // LS forwarder and `native_share` to the LP forwarder. Both shares are floor rounded,
+        // the unit left on odd amounts may go to either forwarder.
+        field = "splits";
+        let splitter_amount = msg.remote_chain_splitter_config.amount;
+        let expected_ls_fwd_amount = splitter_amount * msg.remote_chain_splitter_config.ls_share;
+        let expected_lp_fwd_amount =
+            splitter_amount * msg.remote_chain_splitter_config.native_share;
+        let ls_fwd_amount = get_party_contribution(&msg.ls_forwarder_config);
+        let lp_fwd_amount = get_party_contribution(&msg.lp_forwarder_config);
+        if is_split_amount(ls_fwd_amount, expected_ls_fwd_amount)
+            && is_split_amount(lp_fwd_amount, expected_lp_fwd_amount)
+            && ls_fwd_amount
+                .checked_add(lp_fwd_amount)
+                .is_ok_and(|total| total == splitter_amount)
+        {
+            ctx.valid_field(
                 key,
                 field,
-                remote_chain_denom,
-                lp_fwd_cfg.remote_chain_denom,
-                "invalid denom: expected {} | actual {}"
+                format!(
+                    "ls_forwarder {} | lp_forwarder {}",
+                    ls_fwd_amount, lp_fwd_amount
+                ),
             );
-
-            field = "party_chain_connection_id";
-            verify_equals!(
-                ctx,
+        } else {
+            ctx.invalid_field(
                 key,
                 field,
-                party_chain_connection_id,
-                lp_fwd_cfg.party_chain_connection_id,
-                "invalid connection id: expected {} | actual {}"
+                format!(
+                    "invalid split: expected ls_forwarder {} / lp_forwarder {} | actual {} / {} (total {})",
+                    expected_ls_fwd_amount,
+                    expected_lp_fwd_amount,
+                    ls_fwd_amount,
+                    lp_fwd_amount,
+                    splitter_amount
+                ),
             );
+        }
 
-            field = "party_to_host_chain_channel_id";
-            verify_equals!(
-                ctx,
-                key,
-                field,
-                party_to_host_chain_channel_id,
-                lp_fwd_cfg.party_to_host_chain_channel_id,
-                "invalid channel id: expected {} | actual {}"
-            );
+        // LP forwarder config
+        key = "lp_forwarder_config";
+        match &msg.lp_forwarder_config {
+            sppc::CovenantPartyConfig::Interchain(lp_fwd_cfg) => {
+                field = "party_receiver_addr";
+                required_or_ignored!(ctx, key, field, &lp_fwd_cfg.party_receiver_addr);
 
-            field = "contribution";
-            if lp_fwd_cfg.contribution.denom != remote_chain_denom {
-                ctx.invalid_field(
-                    key,
-                    field,
-                    format!(
-                        "invalid denom: expected {} | actual {}",
-                        remote_chain_denom, lp_fwd_cfg.contribution.denom
-                    ),
-                );
-            } else if Decimal::from(lp_fwd_cfg.contribution.amount.u128())
-                != Decimal::from(msg.covenant_party_config.contribution.amount.u128())
-                    .checked_mul(native_share)
-                    .unwrap()
-            {
-                ctx.invalid_field(
-                    key,
-                    field,
-                    "invalid amount: should be equal to native_share * contribution amount"
-                        .to_owned(),
-                );
-            } else {
-                let contribution_amount = Decimal::from(lp_fwd_cfg.contribution.amount.u128())
-                    .checked_div(Decimal::from(10u128.pow(party_chain_info.decimals.into())))
-                    .unwrap();
-                ctx.valid_field(
+                field = "addr";
+                required_or_ignored!(ctx, key, field, &lp_fwd_cfg.addr);
+
+                field = "host_to_party_chain_channel_id";
+                required_or_ignored!(ctx, key, field, &lp_fwd_cfg.host_to_party_chain_channel_id);
+
+                field = "native_denom";
+                required_or_ignored!(ctx, key, field, &lp_fwd_cfg.native_denom);
+
+                run_rules(
+                    ctx,
                     key,
-                    field,
-                    format!("{:.2} {}", contribution_amount, party_chain_info.display),
+                    vec![
+                        Rule::equals(
+                            "remote_chain_denom",
+                            "denom",
+                            &remote_chain_denom,
+                            &lp_fwd_cfg.remote_chain_denom,
+                        ),
+                        Rule::equals(
+                            "party_chain_connection_id",
+                            "connection id",
+                            &party_chain_connection_id,
+                            &lp_fwd_cfg.party_chain_connection_id,
+                        ),
+                        Rule::equals(
+                            "party_to_host_chain_channel_id",
+                            "channel id",
+                            &party_to_host_chain_channel_id,
+                            &lp_fwd_cfg.party_to_host_chain_channel_id,
+                        ),
+                    ],
                 );
+
+                field = "contribution";
+                if lp_fwd_cfg.contribution.denom != remote_chain_denom {
+                    ctx.invalid_field(
+                        key,
+                        field,
+                        format!(
+                            "invalid denom: expected {} | actual {}",
+                            remote_chain_denom, lp_fwd_cfg.contribution.denom
+                        ),
+                    );
+                } else if !is_split_amount(
+                    lp_fwd_cfg.contribution.amount,
+                    msg.covenant_party_config.contribution.amount
+                        * msg.remote_chain_splitter_config.native_share,
+                ) {
+                    ctx.invalid_field(
+                        key,
+                        field,
+                        "invalid amount: should be equal to native_share * contribution amount"
+                            .to_owned(),
+                    );
+                } else {
+                    let contribution_amount = Decimal::from(lp_fwd_cfg.contribution.amount.u128())
+                        .checked_div(Decimal::from(10u128.pow(party_chain_info.decimals.into())))
+                        .unwrap();
+                    ctx.valid_field(
+                        key,
+                        field,
+                        format!("{:.2} {}", contribution_amount, party_chain_info.display),
+                    );
+                }
             }
-        } else {
-            ctx.invalid(
+            // Funds already on the host chain, nothing to forward over IBC
+            sppc::CovenantPartyConfig::Native(lp_fwd_cfg) => verify_native_forwarder_config(
+                ctx,
                 key,
-                "Invalit covenant party config: should be an Interchain party config.".to_owned(),
-            );
+                &lp_fwd_cfg.party_receiver_addr,
+                &lp_fwd_cfg.addr,
+                &lp_fwd_cfg.native_denom,
+                &lp_fwd_cfg.contribution,
+                &native_denom,
+                msg.covenant_party_config.contribution.amount
+                    * msg.remote_chain_splitter_config.native_share,
+                &host_chain_info.bech32_prefix,
+                &party_chain_info,
+            ),
         }
 
         // LS forwarder config
         key = "ls_forwarder_config";
-        if let sppc::CovenantPartyConfig::Interchain(ls_fwd_cfg) = &msg.ls_forwarder_config {
-            field = "party_receiver_addr";
-            required_or_ignored!(ctx, key, field, &ls_fwd_cfg.party_receiver_addr);
-
-            field = "addr";
-            required_or_ignored!(ctx, key, field, &ls_fwd_cfg.addr);
-
-            field = "host_to_party_chain_channel_id";
-            required_or_ignored!(ctx, key, field, &ls_fwd_cfg.host_to_party_chain_channel_id);
-
-            field = "native_denom";
-            required_or_ignored!(ctx, key, field, &ls_fwd_cfg.native_denom);
+        match &msg.ls_forwarder_config {
+            sppc::CovenantPartyConfig::Interchain(ls_fwd_cfg) => {
+                field = "party_receiver_addr";
+                match ctx.deployed_address() {
+                    Some(covenant_addr) => {
+                        // Funds must be forwarded to the liquid staker ICA on the LS chain
+                        match get_liquid_staker_ica(
+                            &ctx.cli_context,
+                            &host_chain_name,
+                            &covenant_addr,
+                        )
+                        .await
+                        {
+                            Ok(ls_ica_addr) => run_rules(
+                                ctx,
+                                key,
+                                vec![Rule::equals(
+                                    field,
+                                    "receiver (liquid staker ICA)",
+                                    &ls_ica_addr,
+                                    &ls_fwd_cfg.party_receiver_addr,
+                                )],
+                            ),
+                            Err(e) => ctx.invalid_field(
+                                key,
+                                field,
+                                format!("failed to resolve the liquid staker ICA: {}", e),
+                            ),
+                        }
+                    }
+                    None => required_or_ignored!(ctx, key, field, &ls_fwd_cfg.party_receiver_addr),
+                }
 
-            field = "remote_chain_denom";
-            verify_equals!(
-                ctx,
-                key,
-                field,
-                remote_chain_denom,
-                ls_fwd_cfg.remote_chain_denom,
-                "invalid denom: expected {} | actual {}"
-            );
+                field = "addr";
+                required_or_ignored!(ctx, key, field, &ls_fwd_cfg.addr);
 
-            // Neutron -> Cosmos Hub
-            // (same as covenant_party_config.party_chain_connection_id)
-            field = "party_chain_connection_id";
-            verify_equals!(
-                ctx,
-                key,
-                field,
-                party_chain_connection_id,
-                ls_fwd_cfg.party_chain_connection_id,
-                "invalid connection id: expected {} | actual {}"
-            );
+                field = "host_to_party_chain_channel_id";
+                required_or_ignored!(ctx, key, field, &ls_fwd_cfg.host_to_party_chain_channel_id);
 
-            // Cosmos Hub -> Stride|Persistence
-            field = "party_to_host_chain_channel_id";
-            let ls_path_info = get_path_info(
-                &ctx.cli_context,
-                &ctx.party_a_chain_name(),
-                ls_provider_chain,
-            )
-            .await?;
-            let expected_ls_fwdr_p2h_channel_id =
-                if ls_path_info.chain_1.chain_name == ctx.party_a_chain_name() {
-                    ls_path_info
-                        .channels
-                        .iter()
-                        .filter_map(|c| {
-                            if c.chain_1.port_id == TRANSFER_PORT_ID {
-                                Some(c.chain_1.channel_id.clone())
-                            } else {
-                                None
-                            }
-                        })
-                        .next()
-                        .unwrap()
-                } else {
-                    ls_path_info
-                        .channels
-                        .iter()
-                        .filter_map(|c| {
-                            if c.chain_2.port_id == TRANSFER_PORT_ID {
-                                Some(c.chain_2.channel_id.clone())
-                            } else {
-                                None
-                            }
-                        })
-                        .next()
-                        .unwrap()
-                };
-            verify_equals!(
-                ctx,
-                key,
-                field,
-                expected_ls_fwdr_p2h_channel_id,
-                ls_fwd_cfg.party_to_host_chain_channel_id,
-                "invalid channel id: expected {} | actual {}"
-            );
+                field = "native_denom";
+                required_or_ignored!(ctx, key, field, &ls_fwd_cfg.native_denom);
 
-            field = "contribution";
-            if ls_fwd_cfg.contribution.denom != remote_chain_denom {
-                ctx.invalid_field(
+                run_rules(
+                    ctx,
                     key,
-                    field,
-                    format!(
-                        "invalid denom: expected {} | actual {}",
-                        remote_chain_denom, ls_fwd_cfg.contribution.denom
-                    ),
+                    vec![
+                        Rule::equals(
+                            "remote_chain_denom",
+                            "denom",
+                            &remote_chain_denom,
+                            &ls_fwd_cfg.remote_chain_denom,
+                        ),
+                        // Neutron -> Cosmos Hub
+                        // (same as covenant_party_config.party_chain_connection_id)
+                        Rule::equals(
+                            "party_chain_connection_id",
+                            "connection id",
+                            &party_chain_connection_id,
+                            &ls_fwd_cfg.party_chain_connection_id,
+                        ),
+                    ],
                 );
-            } else if Decimal::from(ls_fwd_cfg.contribution.amount.u128())
-                != Decimal::from(msg.covenant_party_config.contribution.amount.u128())
-                    .checked_mul(ls_share)
-                    .unwrap()
-            {
-                ctx.invalid_field(
+
+                // Cosmos Hub -> Stride|Persistence
+                field = "party_to_host_chain_channel_id";
+                let ls_path_info = get_path_info(
+                    &ctx.cli_context,
+                    &ctx.party_a_chain_name(),
+                    ls_provider_chain,
+                )
+                .await?;
+                let expected_ls_fwdr_p2h_channel_id =
+                    if ls_path_info.chain_1.chain_name == ctx.party_a_chain_name() {
+                        ls_path_info
+                            .channels
+                            .iter()
+                            .filter_map(|c| {
+                                if c.chain_1.port_id == TRANSFER_PORT_ID {
+                                    Some(c.chain_1.channel_id.clone())
+                                } else {
+                                    None
+                                }
+                            })
+                            .next()
+                            .unwrap_or_default()
+                    } else {
+                        ls_path_info
+                            .channels
+                            .iter()
+                            .filter_map(|c| {
+                                if c.chain_2.port_id == TRANSFER_PORT_ID {
+                                    Some(c.chain_2.channel_id.clone())
+                                } else {
+                                    None
+                                }
+                            })
+                            .next()
+                            .unwrap_or_default()
+                    };
+                verify_transfer_channel(
+                    ctx,
                     key,
-                    field,
-                    "invalid amount: should be equal to ls_share * contribution amount".to_owned(),
+                    &ls_path_info,
+                    &party_chain_name,
+                    &expected_ls_fwdr_p2h_channel_id,
                 );
-            } else {
-                let contribution_amount = Decimal::from(ls_fwd_cfg.contribution.amount.u128())
-                    .checked_div(Decimal::from(10u128.pow(party_chain_info.decimals.into())))
-                    .unwrap();
-                ctx.valid_field(
+                verify_path_clients(ctx, key, &ls_path_info).await;
+                run_rules(
+                    ctx,
                     key,
-                    field,
-                    format!("{:.2} {}", contribution_amount, party_chain_info.display),
+                    vec![Rule::equals(
+                        field,
+                        "channel id",
+                        &expected_ls_fwdr_p2h_channel_id,
+                        &ls_fwd_cfg.party_to_host_chain_channel_id,
+                    )],
                 );
+
+                field = "contribution";
+                if ls_fwd_cfg.contribution.denom != remote_chain_denom {
+                    ctx.invalid_field(
+                        key,
+                        field,
+                        format!(
+                            "invalid denom: expected {} | actual {}",
+                            remote_chain_denom, ls_fwd_cfg.contribution.denom
+                        ),
+                    );
+                } else if !is_split_amount(
+                    ls_fwd_cfg.contribution.amount,
+                    msg.covenant_party_config.contribution.amount
+                        * msg.remote_chain_splitter_config.ls_share,
+                ) {
+                    ctx.invalid_field(
+                        key,
+                        field,
+                        "invalid amount: should be equal to ls_share * contribution amount"
+                            .to_owned(),
+                    );
+                } else {
+                    let contribution_amount = Decimal::from(ls_fwd_cfg.contribution.amount.u128())
+                        .checked_div(Decimal::from(10u128.pow(party_chain_info.decimals.into())))
+                        .unwrap();
+                    ctx.valid_field(
+                        key,
+                        field,
+                        format!("{:.2} {}", contribution_amount, party_chain_info.display),
+                    );
+                }
             }
-        } else {
-            ctx.invalid(
+            // Funds already on the host chain, nothing to forward over IBC
+            sppc::CovenantPartyConfig::Native(ls_fwd_cfg) => verify_native_forwarder_config(
+                ctx,
                 key,
-                "Invalit covenant party config: should be an Interchain party config.".to_owned(),
-            );
+                &ls_fwd_cfg.party_receiver_addr,
+                &ls_fwd_cfg.addr,
+                &ls_fwd_cfg.native_denom,
+                &ls_fwd_cfg.contribution,
+                &native_denom,
+                msg.covenant_party_config.contribution.amount
+                    * msg.remote_chain_splitter_config.ls_share,
+                &host_chain_info.bech32_prefix,
+                &party_chain_info,
+            ),
         }
 
         // Liquid pooler config
@@ -595,7 +828,7 @@ impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
                     Decimal::from(get_party_contribution(&msg.ls_forwarder_config).u128()),
                     lp_cfg,
                     &msg.pool_price_config,
-                    ctx.single_side_lp_limit_pct,
+                    ctx.single_side_lp_limit_pcts(),
                 )
                 .await?;
             }
@@ -617,52 +850,70 @@ impl<'a> Validate<'a> for SinglePartyPolCovenantInstMsg {
 
         Ok(())
     }
+
+    fn fields(&self) -> Vec<(&'static str, &'static str)> {
+        let msg = &self.0;
+        [
+            fields_of(
+                "covenant",
+                &[
+                    "label",
+                    "lockup_period",
+                    "clock_tick_max_gas",
+                    "emergency_committee",
+                ],
+            ),
+            fields_of("fees", FEES_FIELDS),
+            fields_of(
+                "contract_codes",
+                &[
+                    "ibc_forwarder_code",
+                    "holder_code",
+                    "clock_code",
+                    "remote_chain_splitter_code",
+                    "liquid_pooler_code",
+                    "liquid_staker_code",
+                    "interchain_router_code",
+                ],
+            ),
+            fields_of("covenant_party_config", INTERCHAIN_PARTY_FIELDS),
+            fields_of(
+                "ls_info",
+                &[
+                    "ls_denom",
+                    "ls_denom_on_neutron",
+                    "ls_chain_to_neutron_channel_id",
+                    "ls_neutron_connection_id",
+                ],
+            ),
+            fields_of(
+                "remote_chain_splitter_config",
+                &[
+                    "channel_id",
+                    "connection_id",
+                    "denom",
+                    "amount",
+                    "ls_share",
+                    "native_share",
+                    "fallback_address",
+                ],
+            ),
+            party_config_fields("lp_forwarder_config", &msg.lp_forwarder_config),
+            party_config_fields("ls_forwarder_config", &msg.ls_forwarder_config),
+            fields_of("liquid_pooler_config", LIQUID_POOLER_FIELDS),
+            fields_of("pool_price_config", POOL_PRICE_FIELDS),
+        ]
+        .concat()
+    }
 }
 
-fn get_path_connection_and_channels(
-    path_info: &IBCPath,
-    channel_uses_wasm_port: bool,
-) -> (String, String, String) {
-    if path_info.chain_1.chain_name == NEUTRON_CHAIN_NAME {
-        path_info
-            .channels
-            .iter()
-            .filter_map(|c| {
-                if c.chain_1.port_id == TRANSFER_PORT_ID
-                    && ((channel_uses_wasm_port && c.chain_2.port_id.starts_with("wasm."))
-                        || (!channel_uses_wasm_port && c.chain_2.port_id == TRANSFER_PORT_ID))
-                {
-                    Some((
-                        path_info.chain_1.connection_id.clone(),
-                        c.chain_1.channel_id.clone(),
-                        c.chain_2.channel_id.clone(),
-                    ))
-                } else {
-                    None
-                }
-            })
-            .next()
-            .unwrap()
-    } else {
-        path_info
-            .channels
-            .iter()
-            .filter_map(|c| {
-                if c.chain_2.port_id == TRANSFER_PORT_ID
-                    && ((channel_uses_wasm_port && c.chain_1.port_id.starts_with("wasm."))
-                        || c.chain_1.port_id == TRANSFER_PORT_ID)
-                {
-                    Some((
-                        path_info.chain_2.connection_id.clone(),
-                        c.chain_2.channel_id.clone(),
-                        c.chain_1.channel_id.clone(),
-                    ))
-                } else {
-                    None
-                }
-            })
-            .next()
-            .unwrap()
+fn party_config_fields(
+    key: &'static str,
+    party_config: &sppc::CovenantPartyConfig,
+) -> Vec<(&'static str, &'static str)> {
+    match party_config {
+        sppc::CovenantPartyConfig::Interchain(_) => fields_of(key, INTERCHAIN_PARTY_FIELDS),
+        sppc::CovenantPartyConfig::Native(_) => fields_of(key, NATIVE_PARTY_FIELDS),
     }
 }
 
@@ -670,8 +921,8 @@ async fn verify_single_party_pol_covenant_code_ids<'a>(
     ctx: &mut CovenantValidationContext<'a>,
     key: &'a str,
     contract_code_ids: &sppc::CovenantContractCodeIds,
-) -> Result<(), Error> {
-    match get_covenant_code_ids("v0.1.0".to_owned()).await {
+) -> Result<(), CovenantCliError> {
+    match get_covenant_code_ids(ctx).await {
         Ok(code_ids) => {
             verify_code_id(
                 ctx,
@@ -730,9 +981,324 @@ async fn verify_single_party_pol_covenant_code_ids<'a>(
     Ok(())
 }
 
+#[allow(clippy::too_many_arguments)]
+fn verify_native_forwarder_config<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    party_receiver_addr: &str,
+    addr: &str,
+    native_denom: &str,
+    contribution: &cosmwasm_std::Coin,
+    expected_native_denom: &str,
+    expected_amount: cosmwasm_std::Uint128,
+    host_prefix: &str,
+    party_chain_info: &ChainInfo,
+) {
+    verify_address(
+        ctx,
+        key,
+        "party_receiver_addr",
+        party_receiver_addr,
+        host_prefix,
+    );
+    verify_address(ctx, key, "addr", addr, host_prefix);
+
+    let field = "native_denom";
+    run_rules(
+        ctx,
+        key,
+        vec![Rule::equals(
+            field,
+            "denom",
+            expected_native_denom,
+            native_denom,
+        )],
+    );
+
+    let field = "contribution";
+    if contribution.denom != expected_native_denom {
+        ctx.invalid_field(
+            key,
+            field,
+            format!(
+                "invalid denom: expected {} | actual {}",
+                expected_native_denom, contribution.denom
+            ),
+        );
+    } else if !is_split_amount(contribution.amount, expected_amount) {
+        ctx.invalid_field(
+            key,
+            field,
+            format!(
+                "invalid amount: expected {} | actual {}",
+                expected_amount, contribution.amount
+            ),
+        );
+    } else {
+        let contribution_amount = Decimal::from(contribution.amount.u128())
+            .checked_div(Decimal::from(10u128.pow(party_chain_info.decimals.into())))
+            .unwrap();
+        ctx.valid_field(
+            key,
+            field,
+            format!("{:.2} {}", contribution_amount, party_chain_info.display),
+        );
+    }
+}
+
+/// Whether a forwarder amount is a share of the splitter amount: the covenant floor rounds
+/// each share, the unit left on odd amounts may go to either forwarder
+fn is_split_amount(amount: cosmwasm_std::Uint128, floor_amount: cosmwasm_std::Uint128) -> bool {
+    amount == floor_amount
+        || floor_amount
+            .checked_add(cosmwasm_std::Uint128::one())
+            .is_ok_and(|rounded_up| amount == rounded_up)
+}
+
+/// ICS-20 channel between the host chain and the liquid staking provider chain
+struct LsTransferChannel {
+    host_channel_id: String,
+    ls_channel_id: String,
+    preferred: bool,
+}
+
+/// Channels bound to the transfer port on both ends, ICA controller and host channels
+/// on the same connection are left out
+fn get_ls_transfer_channels(path_info: &IBCPath, host_chain_name: &str) -> Vec<LsTransferChannel> {
+    path_info
+        .channels
+        .iter()
+        .filter(|c| c.chain_1.port_id == TRANSFER_PORT_ID && c.chain_2.port_id == TRANSFER_PORT_ID)
+        .map(|c| {
+            let (host_end, ls_end) = if path_info.chain_1.chain_name == host_chain_name {
+                (&c.chain_1, &c.chain_2)
+            } else {
+                (&c.chain_2, &c.chain_1)
+            };
+            LsTransferChannel {
+                host_channel_id: host_end.channel_id.clone(),
+                ls_channel_id: ls_end.channel_id.clone(),
+                preferred: c.tags.preferred,
+            }
+        })
+        .collect()
+}
+
 fn get_party_contribution(cfg: &sppc::CovenantPartyConfig) -> cosmwasm_std::Uint128 {
     match cfg {
         sppc::CovenantPartyConfig::Interchain(interchain) => interchain.contribution.amount,
         sppc::CovenantPartyConfig::Native(native) => native.contribution.amount,
     }
 }
+
+/// Resolve the ICA of the liquid staker contract of a deployed covenant
+async fn get_liquid_staker_ica(
+    ctx: &CliContext,
+    host_chain_name: &str,
+    covenant_addr: &str,
+) -> Result<String, CovenantCliError> {
+    let ls_addr = get_covenant_child_address(
+        ctx,
+        host_chain_name,
+        covenant_addr,
+        "liquid_staker_address",
+        json!({}),
+    )
+    .await?;
+    get_ica_address(ctx, host_chain_name, &ls_addr).await
+}
+
+/// Check the stkToken denom derived from the party base denom and the registration of
+/// the party chain as a host chain of the Persistence liquidstakeibc module
+async fn verify_persistence_host_chain<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    party_chain_name: &str,
+    party_chain_id: &str,
+    remote_chain_denom: &str,
+    ls_denom: &str,
+) {
+    let expected_ls_denom = stk_denom(remote_chain_denom);
+    if expected_ls_denom == ls_denom {
+        ctx.valid_field(
+            key,
+            "ls_denom",
+            format!("stkToken of {}", remote_chain_denom),
+        );
+    } else {
+        ctx.invalid_field(
+            key,
+            "ls_denom",
+            format!(
+                "invalid stkToken denom: expected {} | actual {}",
+                expected_ls_denom, ls_denom
+            ),
+        );
+    }
+
+    match get_host_chain(&ctx.cli_context, party_chain_id).await {
+        Ok(host_chain) if host_chain.chain_id.is_empty() => ctx.invalid(
+            key,
+            format!(
+                "{} is not a Persistence liquid staking host chain",
+                party_chain_id
+            ),
+        ),
+        Ok(host_chain) if host_chain.host_denom != remote_chain_denom => ctx.invalid(
+            key,
+            format!(
+                "invalid Persistence host denom: expected {} | actual {}",
+                remote_chain_denom, host_chain.host_denom
+            ),
+        ),
+        Ok(host_chain) if !host_chain.active => ctx.warn(
+            key,
+            format!("Persistence host chain {} is not active", party_chain_id),
+        ),
+        Ok(host_chain) => {
+            ctx.valid(
+                key,
+                format!(
+                    "Persistence host chain {} registered on {}",
+                    party_chain_id, host_chain.connection_id
+                ),
+            );
+            verify_persistence_exchange_rate(ctx, key, &host_chain).await;
+        }
+        Err(e) => ctx.invalid(
+            key,
+            format!("failed to query the Persistence host chain: {}", e),
+        ),
+    }
+
+    match get_unbonding_period(&ctx.cli_context, party_chain_name).await {
+        Ok(seconds) => ctx.valid(
+            key,
+            format!(
+                "unbonding period of {}: {} days (stkToken redemptions wait at least as long)",
+                party_chain_name,
+                seconds / 86_400
+            ),
+        ),
+        Err(e) => ctx.warn(
+            key,
+            format!(
+                "failed to query the unbonding period of {}: {}",
+                party_chain_name, e
+            ),
+        ),
+    }
+}
+
+/// Report the stkToken exchange rate, informational unless the c value is outside the
+/// module limits (the host chain is then paused)
+async fn verify_persistence_exchange_rate<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    host_chain: &HostChain,
+) {
+    let Some(exchange_rate) = host_chain.exchange_rate() else {
+        ctx.warn(
+            key,
+            format!(
+                "unknown stkToken exchange rate (c value {:?})",
+                host_chain.c_value
+            ),
+        );
+        return;
+    };
+    let limits = get_liquid_stake_params(&ctx.cli_context)
+        .await
+        .ok()
+        .and_then(|params| {
+            Some((
+                params.lower_c_value_limit.parse::<Decimal>().ok()?,
+                params.upper_c_value_limit.parse::<Decimal>().ok()?,
+            ))
+        });
+    let c_value = host_chain.c_value.parse::<Decimal>().unwrap_or_default();
+    match limits {
+        Some((lower, upper)) if c_value < lower || c_value > upper => ctx.warn(
+            key,
+            format!(
+                "stkToken c value {} outside of the module limits [{}, {}]",
+                host_chain.c_value, lower, upper
+            ),
+        ),
+        _ => ctx.valid(
+            key,
+            format!(
+                "stkToken exchange rate: 1 {} = {:.6} {}",
+                stk_denom(&host_chain.host_denom),
+                exchange_rate,
+                host_chain.host_denom
+            ),
+        ),
+    }
+}
+
+/// A derived IBC denom missing from the host chain assetlist and the Astroport coin registry
+/// hints at a derivation from another channel than the canonical one
+async fn verify_denom_registered<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    field: &'a str,
+    denom: &str,
+) {
+    let host_chain_name = ctx.host_chain_name();
+    let in_assetlist = get_chain_assets(&ctx.cli_context, &host_chain_name)
+        .await
+        .is_ok_and(|assets| assets.iter().any(|asset| asset.base == denom));
+    if in_assetlist {
+        ctx.valid_field(
+            key,
+            field,
+            format!("{} registered in the {} assetlist", denom, host_chain_name),
+        );
+        return;
+    }
+    let in_coin_registry = host_chain_name == NEUTRON_CHAIN_NAME
+        && get_astroport_native_token(
+            &ctx.cli_context,
+            &host_chain_name,
+            NEUTRON_COIN_REGISTRY_CONTRACT_ADDRESS,
+            denom,
+        )
+        .await
+        .is_ok();
+    if in_coin_registry {
+        ctx.valid_field(
+            key,
+            field,
+            format!("{} registered in the Astroport coin registry", denom),
+        );
+    } else {
+        ctx.warn_field(
+            key,
+            field,
+            format!(
+                "{} not registered in the {} assetlist nor the Astroport coin registry, the channel it is derived from may not be the canonical one",
+                denom, host_chain_name
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::Uint128;
+    use std::str::FromStr;
+
+    #[test]
+    fn odd_amounts_split_with_a_remainder() {
+        let contribution = Uint128::new(1_000_000_001);
+        let half = cosmwasm_std::Decimal::from_str("0.5").unwrap();
+        let floor_amount = contribution * half;
+        assert_eq!(floor_amount, Uint128::new(500_000_000));
+        assert!(is_split_amount(Uint128::new(500_000_000), floor_amount));
+        assert!(is_split_amount(Uint128::new(500_000_001), floor_amount));
+        assert!(!is_split_amount(Uint128::new(499_999_999), floor_amount));
+        assert!(!is_split_amount(Uint128::new(500_000_002), floor_amount));
+    }
+}
@@ -1,8 +1,31 @@
-use anyhow::Error;
+use crate::error::CovenantCliError;
 use async_trait::async_trait;
+use cosmwasm_std::{Decimal, Uint128};
+use itertools::Itertools;
 use log::{debug, info};
+use serde_json::json;
+use std::collections::BTreeMap;
+use swap_covenant::msg as swc;
 
-use super::{CovenantValidationContext, Validate};
+use super::{
+    fields_of, CovenantValidationContext, Validate, FEES_FIELDS, INTERCHAIN_PARTY_FIELDS,
+    NATIVE_PARTY_FIELDS,
+};
+use crate::utils::assets::get_chain_asset_info;
+use crate::utils::chain::get_chain_info;
+use crate::utils::path::get_path_info;
+use crate::validations::address::{
+    verify_deployed_router_denoms, verify_deployed_split_receivers, verify_split_entries,
+};
+use crate::validations::fees::{verify_ibc_fee, verify_timeout};
+use crate::validations::ibc::{
+    get_path_connection_and_channels, verify_channel_uses_wasm_port, verify_path_clients,
+    verify_pfm_consistency, verify_refund_path, verify_transfer_channel, verify_wasm_port_contract,
+};
+use crate::validations::label::verify_label;
+use crate::validations::neutron::{
+    verify_deadline_after_execution, verify_expiration, verify_timeouts_before_deadline,
+};
 
 /// Validate the swap covenant instantiation message
 pub struct SwapCovenantInstMsg(swap_covenant::msg::InstantiateMsg);
@@ -19,13 +42,646 @@ impl<'a> SwapCovenantInstMsg {
 
 #[async_trait]
 impl<'a> Validate<'a> for SwapCovenantInstMsg {
-    async fn validate(&self, _ctx: &mut CovenantValidationContext) -> Result<(), Error> {
-        // Validate the two party POL covenant instantiation message
+    async fn validate(&self, ctx: &mut CovenantValidationContext) -> Result<(), CovenantCliError> {
+        // Validate the swap covenant instantiation message
         let msg = &self.0;
         debug!("valence-covenant-swap: {:?}", msg);
 
         info!("Processing covenant {:?}", msg.label);
 
+        // Covenant label
+        let mut key = "covenant";
+        verify_label(ctx, key, &msg.label).await;
+
+        // Lockup config
+        verify_expiration(ctx, key, "lockup_config", msg.lockup_config).await?;
+        verify_deadline_after_execution(ctx, key, "lockup_config", msg.lockup_config).await;
+        verify_timeouts_before_deadline(
+            ctx,
+            "fees",
+            "lockup_config",
+            msg.lockup_config,
+            msg.timeouts.ica_timeout.u64(),
+            msg.timeouts.ibc_transfer_timeout.u64(),
+        )
+        .await;
+
+        // Fees and timeouts of the forwarders
+        key = "fees";
+        verify_timeout(ctx, key, "ica_timeout", msg.timeouts.ica_timeout.u64());
+        verify_timeout(
+            ctx,
+            key,
+            "ibc_transfer_timeout",
+            msg.timeouts.ibc_transfer_timeout.u64(),
+        );
+        verify_ibc_fee(
+            ctx,
+            key,
+            msg.preset_ibc_fee.ack_fee.u128(),
+            msg.preset_ibc_fee.timeout_fee.u128(),
+        )
+        .await?;
+
+        // Swap terms
+        key = "covenant";
+        verify_covenant_terms(ctx, key, msg).await;
+
+        // Party A and B forwarding maps
+        verify_denom_to_pfm_map(ctx, "party_a_config", &msg.party_a_config);
+        verify_denom_to_pfm_map(ctx, "party_b_config", &msg.party_b_config);
+
+        // Splits: each receiver is reached through the router of the party on its chain
+        key = "splits";
+        let routes = resolve_routes(ctx, msg).await?;
+        let party_receivers = [
+            msg.party_a_config.get_final_receiver_address(),
+            msg.party_b_config.get_final_receiver_address(),
+        ];
+        verify_native_splitter(ctx, key, msg, &party_receivers);
+        let mut verified = true;
+        for (denom, split) in &msg.splits {
+            verify_split_entries(ctx, key, denom, &split.receivers, &party_receivers);
+            for receiver in split.receivers.keys() {
+                let party_key = party_receivers
+                    .iter()
+                    .position(|party_receiver| party_receiver == receiver)
+                    .map(|i| PARTY_KEYS[i]);
+                if party_key.is_some_and(|party_key| ctx.is_out_of_scope(party_key)) {
+                    continue;
+                }
+                match verify_receiver_route(&routes, party_key, receiver) {
+                    Ok(route) => ctx.valid(key, format!("{} -> {}: {}", denom, receiver, route)),
+                    Err(e) => {
+                        ctx.invalid(key, format!("{} -> {}: {}", denom, receiver, e));
+                        verified = false;
+                    }
+                }
+            }
+        }
+        if verified {
+            ctx.valid_field(key, "", "verified".to_owned());
+        } else {
+            ctx.invalid_field(key, "", "invalid splits: unroutable receivers".to_owned());
+        }
+
+        // Refunds: once the lockup expires the deposits are returned to the party
+        // receivers, which must be reachable on the chain of the party
+        let host_chain_name = ctx.host_chain_name();
+        for (party_key, party_chain_name, party_config) in [
+            (
+                "party_a_config",
+                ctx.party_a_chain_name(),
+                &msg.party_a_config,
+            ),
+            (
+                "party_b_config",
+                ctx.party_b_chain_name(),
+                &msg.party_b_config,
+            ),
+        ] {
+            if ctx.is_out_of_scope(party_key) {
+                continue;
+            }
+            let receiver = party_config.get_final_receiver_address();
+            match verify_receiver_route(&routes, Some(party_key), &receiver) {
+                Ok(route) => ctx.valid_field(
+                    party_key,
+                    "party_receiver_addr",
+                    format!("refund route: {}", route),
+                ),
+                Err(e) => ctx.invalid_field(
+                    party_key,
+                    "party_receiver_addr",
+                    format!("unresolvable refund route: {}", e),
+                ),
+            }
+            if let swc::CovenantPartyConfig::Interchain(party) = party_config {
+                match get_path_info(ctx.cli_context(), &party_chain_name, &host_chain_name).await {
+                    Ok(path_info) => {
+                        verify_refund_path(
+                            ctx,
+                            party_key,
+                            &path_info,
+                            &party_chain_name,
+                            &party.host_to_party_chain_channel_id,
+                            &party.native_denom,
+                            &party.remote_chain_denom,
+                        )
+                        .await
+                    }
+                    Err(e) => ctx.invalid(party_key, format!("refund path: {}", e)),
+                }
+            }
+        }
+
+        if let Some(covenant_addr) = ctx.deployed_address() {
+            verify_deployed_split_receivers(
+                ctx,
+                key,
+                &covenant_addr,
+                ("splitter_address", json!({ "splits": {} })),
+            )
+            .await;
+            let covenant_denoms = [
+                msg.party_a_config.get_native_denom(),
+                msg.party_b_config.get_native_denom(),
+            ]
+            .into_iter()
+            .collect();
+            verify_deployed_router_denoms(ctx, key, &covenant_addr, &covenant_denoms).await;
+        }
+
         Ok(())
     }
+
+    fn fields(&self) -> Vec<(&'static str, &'static str)> {
+        let msg = &self.0;
+        [
+            fields_of(
+                "covenant",
+                &[
+                    "label",
+                    "lockup_config",
+                    "covenant_terms",
+                    "clock_tick_max_gas",
+                    "fallback_split",
+                ],
+            ),
+            fields_of("fees", FEES_FIELDS),
+            party_fields("party_a_config", &msg.party_a_config),
+            party_fields("party_b_config", &msg.party_b_config),
+            fields_of("splits", &[""]),
+        ]
+        .concat()
+    }
+}
+
+fn party_fields(
+    key: &'static str,
+    party_config: &swc::CovenantPartyConfig,
+) -> Vec<(&'static str, &'static str)> {
+    match party_config {
+        swc::CovenantPartyConfig::Interchain(_) => fields_of(key, INTERCHAIN_PARTY_FIELDS),
+        swc::CovenantPartyConfig::Native(_) => fields_of(key, NATIVE_PARTY_FIELDS),
+    }
+}
+
+/// Denom and contribution a party deposits on the host chain
+fn party_deposit(party_config: &swc::CovenantPartyConfig) -> (String, u128) {
+    match party_config {
+        swc::CovenantPartyConfig::Interchain(party) => {
+            (party.native_denom.clone(), party.contribution.amount.u128())
+        }
+        swc::CovenantPartyConfig::Native(party) => {
+            (party.native_denom.clone(), party.contribution.amount.u128())
+        }
+    }
+}
+
+/// The native splitter splits the deposit of each party between the receivers: its denoms
+/// are the party denoms on the host chain, each fully split (shares summing up to 1)
+/// and swapped to the other party
+fn verify_native_splitter<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    msg: &swc::InstantiateMsg,
+    party_receivers: &[String; 2],
+) {
+    let deposits = [
+        (
+            "party_a",
+            party_deposit(&msg.party_a_config),
+            &party_receivers[0],
+            &party_receivers[1],
+        ),
+        (
+            "party_b",
+            party_deposit(&msg.party_b_config),
+            &party_receivers[1],
+            &party_receivers[0],
+        ),
+    ];
+    for (party, (denom, amount), own_receiver, counterparty_receiver) in &deposits {
+        let Some(split) = msg.splits.get(denom) else {
+            ctx.invalid(
+                key,
+                format!("{}: no split for the {} deposit", denom, party),
+            );
+            continue;
+        };
+        let total = split.receivers.values().sum::<Decimal>();
+        if total != Decimal::one() {
+            ctx.invalid(
+                key,
+                format!(
+                    "{}: invalid shares: sum of shares is {}, should be 1.0",
+                    denom, total
+                ),
+            );
+            continue;
+        }
+        let share_of = |receiver: &str| split.receivers.get(receiver).copied().unwrap_or_default();
+        let to_counterparty = Uint128::new(*amount).mul_floor(share_of(counterparty_receiver));
+        if !share_of(own_receiver).is_zero() {
+            ctx.warn(
+                key,
+                format!(
+                    "{}: {} of the {} deposit returns to {}",
+                    denom,
+                    Uint128::new(*amount) - to_counterparty,
+                    party,
+                    party
+                ),
+            );
+        } else {
+            ctx.valid(key, format!("{}: {} {} swapped", denom, amount, party));
+        }
+    }
+    for denom in msg.splits.keys() {
+        if !deposits
+            .iter()
+            .any(|(_, (party_denom, _), _, _)| party_denom == denom)
+        {
+            ctx.invalid(
+                key,
+                format!("{}: split of a denom no party deposits", denom),
+            );
+        }
+    }
+}
+
+/// The swap terms amounts are the contributions of the parties, in base units
+async fn verify_covenant_terms<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    msg: &swc::InstantiateMsg,
+) {
+    let field = "covenant_terms";
+    let terms = &msg.covenant_terms;
+    let mut verified = true;
+    for (party, party_key, party_config, amount, party_chain_name) in [
+        (
+            "party_a",
+            PARTY_KEYS[0],
+            &msg.party_a_config,
+            terms.party_a_amount,
+            ctx.party_a_chain_name(),
+        ),
+        (
+            "party_b",
+            PARTY_KEYS[1],
+            &msg.party_b_config,
+            terms.party_b_amount,
+            ctx.party_b_chain_name(),
+        ),
+    ] {
+        // Reported under the shared key, the terms of the other party are out of scope
+        if ctx.is_out_of_scope(party_key) {
+            continue;
+        }
+        // The contribution is denominated on the chain the party deposits from
+        let (contribution, chain_name) = match party_config {
+            swc::CovenantPartyConfig::Interchain(party) => {
+                (party.contribution.clone(), party_chain_name)
+            }
+            swc::CovenantPartyConfig::Native(party) => {
+                (party.contribution.clone(), ctx.host_chain_name())
+            }
+        };
+        let asset = get_chain_asset_info(ctx.cli_context(), &chain_name, &contribution.denom).await;
+        let decimals = asset.as_ref().map_or(0, |asset| asset.decimals);
+        let display = |amount: Uint128| match &asset {
+            Ok(asset) => Decimal::from_atomics(amount, decimals.into())
+                .map(|value| format!("{} ({} {})", amount, value, asset.symbol))
+                .unwrap_or_else(|_| amount.to_string()),
+            Err(_) => amount.to_string(),
+        };
+        if amount == contribution.amount {
+            ctx.valid(
+                key,
+                format!(
+                    "{}: {} amount {} matches the contribution",
+                    field,
+                    party,
+                    display(amount)
+                ),
+            );
+            continue;
+        }
+        verified = false;
+        let scaled = Uint128::new(10u128.pow(decimals.into()));
+        let message = if decimals > 0 && amount.checked_mul(scaled) == Ok(contribution.amount) {
+            format!(
+                "{} amount {} is in display units, expected the contribution {}",
+                party,
+                amount,
+                display(contribution.amount)
+            )
+        } else {
+            format!(
+                "{} amount {} does not match the contribution {}",
+                party,
+                display(amount),
+                display(contribution.amount)
+            )
+        };
+        ctx.invalid(key, format!("{}: {}", field, message));
+        ctx.suggest_fix(
+            key,
+            field,
+            &format!("{}/{}_amount", field, party),
+            json!(contribution.amount),
+        );
+    }
+    if verified {
+        ctx.valid_field(key, field, "verified".to_owned());
+    } else {
+        ctx.invalid_field(
+            key,
+            field,
+            "amounts not matching the contributions".to_owned(),
+        );
+    }
+}
+
+/// How funds reach the chain of a split receiver from the host chain
+#[derive(Debug)]
+enum Route {
+    /// Receiver is on the host chain, funds are routed by a native router
+    Native,
+    /// Receiver is on a party chain, funds are routed by the party's interchain router
+    Interchain {
+        chain_name: String,
+        expected_channel_id: String,
+        party_channel_id: Option<String>,
+    },
+}
+
+// Keys of the party routes, in the order of the party receivers
+const PARTY_KEYS: [&str; 2] = ["party_a_config", "party_b_config"];
+
+// Key of the host chain route, next to the party routes
+const HOST_ROUTE_KEY: &str = "host";
+
+/// Routes of the host chain and of each party, with the bech32 prefix of their chain.
+/// Parties are keyed separately as their chains may share a prefix.
+async fn resolve_routes(
+    ctx: &mut CovenantValidationContext<'_>,
+    msg: &swc::InstantiateMsg,
+) -> Result<BTreeMap<&'static str, (String, Route)>, CovenantCliError> {
+    let host_chain_name = ctx.host_chain_name();
+    let mut routes = BTreeMap::new();
+    let host_chain_info = get_chain_info(&ctx.cli_context, &host_chain_name).await?;
+    routes.insert(
+        HOST_ROUTE_KEY,
+        (host_chain_info.bech32_prefix.clone(), Route::Native),
+    );
+
+    for (party_key, party_chain_name, party_config, declared_wasm_port, expected_outpost) in [
+        (
+            PARTY_KEYS[0],
+            ctx.party_a_chain_name(),
+            &msg.party_a_config,
+            ctx.party_a_channel_uses_wasm_port(),
+            ctx.party_a_outpost_contract(),
+        ),
+        (
+            PARTY_KEYS[1],
+            ctx.party_b_chain_name(),
+            &msg.party_b_config,
+            None,
+            ctx.party_b_outpost_contract(),
+        ),
+    ] {
+        if ctx.is_out_of_scope(party_key) {
+            continue;
+        }
+        if party_chain_name == host_chain_name {
+            routes.insert(
+                party_key,
+                (host_chain_info.bech32_prefix.clone(), Route::Native),
+            );
+            continue;
+        }
+        let party_chain_info = get_chain_info(&ctx.cli_context, &party_chain_name).await?;
+        let path_info =
+            get_path_info(&ctx.cli_context, &party_chain_name, &host_chain_name).await?;
+        let channel_uses_wasm_port = verify_channel_uses_wasm_port(
+            ctx,
+            party_key,
+            &path_info,
+            &host_chain_name,
+            declared_wasm_port,
+        );
+        let (_, expected_channel_id, _) =
+            get_path_connection_and_channels(&path_info, &host_chain_name, channel_uses_wasm_port);
+        verify_transfer_channel(
+            ctx,
+            "splits",
+            &path_info,
+            &host_chain_name,
+            &expected_channel_id,
+        );
+        verify_path_clients(ctx, "splits", &path_info).await;
+        verify_wasm_port_contract(
+            ctx,
+            party_key,
+            &path_info,
+            &host_chain_name,
+            &party_chain_name,
+            expected_outpost,
+        )
+        .await;
+        let party_channel_id = match party_config {
+            swc::CovenantPartyConfig::Interchain(interchain_party) => {
+                verify_pfm_consistency(
+                    ctx,
+                    party_key,
+                    &party_chain_name,
+                    &expected_channel_id,
+                    interchain_party.denom_to_pfm_map.len(),
+                );
+                Some(interchain_party.host_to_party_chain_channel_id.clone())
+            }
+            swc::CovenantPartyConfig::Native(_) => None,
+        };
+        routes.insert(
+            party_key,
+            (
+                party_chain_info.bech32_prefix,
+                Route::Interchain {
+                    chain_name: party_chain_name,
+                    expected_channel_id,
+                    party_channel_id,
+                },
+            ),
+        );
+    }
+    Ok(routes)
+}
+
+/// Check the route of a receiver: the route of its party for a party receiver (e.g. a
+/// refund), otherwise the only route to a chain with its prefix
+fn verify_receiver_route(
+    routes: &BTreeMap<&'static str, (String, Route)>,
+    party_key: Option<&str>,
+    receiver: &str,
+) -> Result<String, String> {
+    let (hrp, _) = bech32::decode(receiver).map_err(|e| format!("invalid address: {}", e))?;
+    let route = match party_key {
+        Some(party_key) => match routes.get(party_key) {
+            Some((prefix, route)) if prefix == hrp.as_str() => route,
+            Some((prefix, _)) => {
+                return Err(format!(
+                    "receiver is not on the party chain: expected prefix {} | actual {}",
+                    prefix,
+                    hrp.as_str()
+                ))
+            }
+            None => return Err(format!("no route to {}", party_key)),
+        },
+        None => {
+            let mut candidates = routes
+                .values()
+                .filter(|(prefix, _)| prefix == hrp.as_str())
+                .map(|(_, route)| route)
+                .unique_by(|route| match route {
+                    Route::Native => None,
+                    Route::Interchain { chain_name, .. } => Some(chain_name),
+                });
+            match (candidates.next(), candidates.next()) {
+                (Some(route), None) => route,
+                (None, _) => {
+                    return Err(format!("no route to a chain with prefix {}", hrp.as_str()))
+                }
+                (Some(_), Some(_)) => {
+                    return Err(format!(
+                        "ambiguous route: several chains use the prefix {}",
+                        hrp.as_str()
+                    ))
+                }
+            }
+        }
+    };
+    match route {
+        Route::Native => Ok("host chain".to_owned()),
+        Route::Interchain {
+            chain_name,
+            party_channel_id: None,
+            ..
+        } => Err(format!("no interchain party on {}", chain_name)),
+        Route::Interchain {
+            chain_name,
+            expected_channel_id,
+            party_channel_id: Some(party_channel_id),
+        } => {
+            if expected_channel_id.is_empty() {
+                Err(format!("no transfer channel found to {}", chain_name))
+            } else if expected_channel_id != party_channel_id {
+                Err(format!(
+                    "invalid channel to {}: expected {} | actual {}",
+                    chain_name, expected_channel_id, party_channel_id
+                ))
+            } else {
+                Ok(format!("{} via {}", chain_name, party_channel_id))
+            }
+        }
+    }
+}
+
+/// Check that every forwarded denom is a well formed hop to a valid receiver
+fn verify_denom_to_pfm_map<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    key: &'a str,
+    party_config: &swc::CovenantPartyConfig,
+) {
+    let field = "denom_to_pfm_map";
+    let swc::CovenantPartyConfig::Interchain(interchain_party) = party_config else {
+        return;
+    };
+    let mut errors = vec![];
+    for (denom, pfm) in &interchain_party.denom_to_pfm_map {
+        for channel_id in [
+            &pfm.local_to_hop_chain_channel_id,
+            &pfm.hop_to_destination_chain_channel_id,
+        ] {
+            if !channel_id.starts_with("channel-") {
+                errors.push(format!("{}: invalid channel id {}", denom, channel_id));
+            }
+        }
+        if let Err(e) = bech32::decode(&pfm.hop_chain_receiver_address) {
+            errors.push(format!(
+                "{}: invalid hop chain receiver address: {}",
+                denom, e
+            ));
+        }
+    }
+    if errors.is_empty() {
+        ctx.valid_field(
+            key,
+            field,
+            format!(
+                "{} forwarded denoms",
+                interchain_party.denom_to_pfm_map.len()
+            ),
+        );
+    } else {
+        ctx.invalid_field(key, field, errors.join(", "));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARTY_A_RECEIVER: &str = "cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du";
+    const PARTY_B_RECEIVER: &str = "cosmos1qgpqyqszqgpqyqszqgpqyqszqgpqyqszrh8mx2";
+    const HOST_RECEIVER: &str = "neutron1qvpsxqcrqvpsxqcrqvpsxqcrqvpsxqcrxc0ckv";
+
+    fn interchain_route(chain_name: &str, channel_id: &str) -> (String, Route) {
+        (
+            "cosmos".to_owned(),
+            Route::Interchain {
+                chain_name: chain_name.to_owned(),
+                expected_channel_id: channel_id.to_owned(),
+                party_channel_id: Some(channel_id.to_owned()),
+            },
+        )
+    }
+
+    // Both party chains use the `cosmos` prefix
+    fn shared_prefix_routes() -> BTreeMap<&'static str, (String, Route)> {
+        BTreeMap::from([
+            (HOST_ROUTE_KEY, ("neutron".to_owned(), Route::Native)),
+            (PARTY_KEYS[0], interchain_route("cosmoshub", "channel-1")),
+            (PARTY_KEYS[1], interchain_route("othercosmos", "channel-2")),
+        ])
+    }
+
+    #[test]
+    fn party_receivers_use_the_route_of_their_party() {
+        let routes = shared_prefix_routes();
+        assert_eq!(
+            verify_receiver_route(&routes, Some(PARTY_KEYS[0]), PARTY_A_RECEIVER),
+            Ok("cosmoshub via channel-1".to_owned())
+        );
+        assert_eq!(
+            verify_receiver_route(&routes, Some(PARTY_KEYS[1]), PARTY_B_RECEIVER),
+            Ok("othercosmos via channel-2".to_owned())
+        );
+        assert!(verify_receiver_route(&routes, Some(PARTY_KEYS[1]), HOST_RECEIVER).is_err());
+    }
+
+    #[test]
+    fn other_receivers_need_an_unambiguous_prefix() {
+        let routes = shared_prefix_routes();
+        assert_eq!(
+            verify_receiver_route(&routes, None, HOST_RECEIVER),
+            Ok("host chain".to_owned())
+        );
+        assert!(verify_receiver_route(&routes, None, PARTY_A_RECEIVER)
+            .unwrap_err()
+            .starts_with("ambiguous route"));
+    }
 }
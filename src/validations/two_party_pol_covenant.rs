@@ -1,21 +1,40 @@
-use anyhow::Error;
+use crate::error::CovenantCliError;
 use async_trait::async_trait;
 use log::{debug, info};
 use rust_decimal::Decimal;
+use serde_json::json;
 use sha2::{Digest, Sha256};
 use two_party_pol_covenant::msg as tppc;
 
-use super::{CovenantValidationContext, Validate};
+use super::{
+    fields_of, CovenantValidationContext, Validate, FEES_FIELDS, INTERCHAIN_PARTY_FIELDS,
+    LIQUID_POOLER_FIELDS, NATIVE_PARTY_FIELDS, POOL_PRICE_FIELDS,
+};
 use crate::utils::assets::get_chain_asset_info;
 use crate::utils::chain::get_chain_info;
-use crate::utils::path::{get_path_info, IBCPath};
-use crate::validations::astroport::verify_astroport_liquid_pooler_config;
-use crate::validations::neutron::verify_expiration;
+use crate::utils::path::get_path_info;
+use crate::validations::astroport::{
+    verify_astroport_liquid_pooler_config, verify_contribution_parity,
+};
+use crate::validations::label::verify_label;
+use crate::validations::neutron::{
+    verify_deadline_after_execution, verify_expiration, verify_timeouts_before_deadline,
+};
 use crate::validations::{
+    address::{
+        verify_deployed_refund_configs, verify_deployed_router_denoms,
+        verify_deployed_split_receivers, verify_split_entries, ExpectedRefund,
+    },
     contracts::{get_covenant_code_ids, verify_code_id},
-    NEUTRON_CHAIN_NAME, TRANSFER_PORT_ID,
+    contributions::verify_minimum_contribution,
+    fees::{verify_ibc_fee, verify_timeout},
+    ibc::{
+        get_path_connection_and_channels, verify_channel_uses_wasm_port, verify_path_clients,
+        verify_pfm_consistency, verify_refund_path, verify_transfer_channel,
+        verify_wasm_port_contract,
+    },
+    rules::{run_rules, Rule},
 };
-use crate::verify_equals;
 
 /// Validate the two party POL covenant instantiation message
 pub struct TwoPartyPolCovenantInstMsg(two_party_pol_covenant::msg::InstantiateMsg);
@@ -32,29 +51,40 @@ impl<'a> TwoPartyPolCovenantInstMsg {
 
 #[async_trait]
 impl<'a> Validate<'a> for TwoPartyPolCovenantInstMsg {
-    async fn validate(&self, ctx: &mut CovenantValidationContext) -> Result<(), Error> {
+    async fn validate(&self, ctx: &mut CovenantValidationContext) -> Result<(), CovenantCliError> {
         // Validate the two party POL covenant instantiation message
         let msg = &self.0;
         debug!("valence-covenant-two-party-pol: {:?}", msg);
 
         info!("Processing covenant {:?}", msg.label);
 
-        // Covenant label
-        let mut key = "covenant";
-        let mut field = "label";
-        if msg.label.is_empty() {
-            ctx.invalid_field(key, field, "required".to_owned());
-        } else {
-            ctx.valid_field(key, field, "valid".to_owned());
-        }
-
         // Contract Codes
-        key = "contract_codes";
+        let mut key = "contract_codes";
         verify_two_party_pol_covenant_code_ids(ctx, key, &msg.contract_codes).await?;
 
+        // Covenant label, checked against the covenants of the same code
+        verify_label(ctx, "covenant", &msg.label).await;
+
+        // Fees and timeouts
+        key = "fees";
+        verify_timeout(ctx, key, "ica_timeout", msg.timeouts.ica_timeout.u64());
+        verify_timeout(
+            ctx,
+            key,
+            "ibc_transfer_timeout",
+            msg.timeouts.ibc_transfer_timeout.u64(),
+        );
+        verify_ibc_fee(
+            ctx,
+            key,
+            msg.preset_ibc_fee.ack_fee.u128(),
+            msg.preset_ibc_fee.timeout_fee.u128(),
+        )
+        .await?;
+
         // Covenant type
         key = "covenant";
-        field = "covenant_type";
+        let mut field = "covenant_type";
         ctx.valid_field(key, field, "verified".to_owned());
         // match msg.covenant_type {
         //     valence_two_party_pol_holder::msg::CovenantType::Side => {}
@@ -81,6 +111,7 @@ impl<'a> Validate<'a> for TwoPartyPolCovenantInstMsg {
         // Deposit deadline
         field = "deposit_deadline";
         verify_expiration(ctx, key, field, msg.deposit_deadline).await?;
+        verify_deadline_after_execution(ctx, key, field, msg.deposit_deadline).await;
 
         // Lockup config
         field = "lockup_config";
@@ -96,22 +127,57 @@ impl<'a> Validate<'a> for TwoPartyPolCovenantInstMsg {
             );
         }
 
-        // Party A config
-        key = "party_a_config";
-        let party_a_chain_name = ctx.party_a_chain_name();
-        verify_party_config(
+        // Deposits forwarded near the deadline must complete before it
+        verify_timeouts_before_deadline(
             ctx,
-            key,
-            &party_a_chain_name,
-            &msg.party_a_config,
-            ctx.party_a_channel_uses_wasm_port,
+            "fees",
+            "deposit_deadline",
+            msg.deposit_deadline,
+            msg.timeouts.ica_timeout.u64(),
+            msg.timeouts.ibc_transfer_timeout.u64(),
         )
-        .await?;
+        .await;
 
-        // Party B config
-        key = "party_b_config";
+        // Party A and B configs are verified concurrently on forked contexts
+        let party_a_chain_name = ctx.party_a_chain_name();
         let party_b_chain_name = ctx.party_b_chain_name();
-        verify_party_config(ctx, key, &party_b_chain_name, &msg.party_b_config, false).await?;
+        let mut party_a_ctx = ctx.fork();
+        let mut party_b_ctx = ctx.fork();
+        // The party left out by `--party` is not queried, its errors cannot abort the run
+        let (party_a_result, party_b_result) = tokio::join!(
+            async {
+                if ctx.is_out_of_scope("party_a_config") {
+                    return Ok(());
+                }
+                verify_party_config(
+                    &mut party_a_ctx,
+                    "party_a_config",
+                    &party_a_chain_name,
+                    &msg.party_a_config,
+                    ctx.party_a_channel_uses_wasm_port(),
+                    ctx.party_a_outpost_contract(),
+                )
+                .await
+            },
+            async {
+                if ctx.is_out_of_scope("party_b_config") {
+                    return Ok(());
+                }
+                verify_party_config(
+                    &mut party_b_ctx,
+                    "party_b_config",
+                    &party_b_chain_name,
+                    &msg.party_b_config,
+                    None,
+                    ctx.party_b_outpost_contract(),
+                )
+                .await
+            },
+        );
+        ctx.merge(party_a_ctx);
+        ctx.merge(party_b_ctx);
+        party_a_result?;
+        party_b_result?;
 
         // Liquid pooler config
         key = "liquid_pooler_config";
@@ -127,9 +193,23 @@ impl<'a> Validate<'a> for TwoPartyPolCovenantInstMsg {
                     Decimal::from(get_party_contribution(&msg.party_b_config).u128()),
                     lp_cfg,
                     &msg.pool_price_config,
-                    ctx.single_side_lp_limit_pct,
+                    ctx.single_side_lp_limit_pcts(),
                 )
                 .await?;
+
+                // Contributions of lopsided value are only flagged when a tolerance is set
+                if let Some(tolerance_pct) = ctx.contribution_parity_tolerance_pct() {
+                    verify_contribution_parity(
+                        ctx,
+                        "covenant",
+                        &lp_cfg.pool_address,
+                        &msg.party_a_config.get_native_denom(),
+                        Decimal::from(get_party_contribution(&msg.party_a_config).u128()),
+                        Decimal::from(get_party_contribution(&msg.party_b_config).u128()),
+                        tolerance_pct,
+                    )
+                    .await?;
+                }
             }
             tppc::LiquidPoolerConfig::Osmosis(_lp_cfg) => {
                 ctx.invalid(
@@ -191,9 +271,87 @@ impl<'a> Validate<'a> for TwoPartyPolCovenantInstMsg {
                 ctx.invalid_field(key, field, "invalid splits: unexpected denoms".to_owned());
             }
         }
+        let party_receivers = [party_a_receiver, party_b_receiver];
+        for (denom, split) in &msg.splits {
+            verify_split_entries(ctx, key, denom, &split.receivers, &party_receivers);
+        }
+        if let Some(covenant_addr) = ctx.deployed_address() {
+            verify_deployed_split_receivers(
+                ctx,
+                key,
+                &covenant_addr,
+                ("holder_address", json!({ "denom_splits": {} })),
+            )
+            .await;
+            let covenant_denoms = [
+                msg.party_a_config.get_native_denom(),
+                msg.party_b_config.get_native_denom(),
+            ]
+            .into_iter()
+            .collect();
+            verify_deployed_router_denoms(ctx, key, &covenant_addr, &covenant_denoms).await;
+            verify_deployed_refund_configs(
+                ctx,
+                &covenant_addr,
+                [
+                    ("party_a_config", expected_refund(&msg.party_a_config)),
+                    ("party_b_config", expected_refund(&msg.party_b_config)),
+                ],
+            )
+            .await;
+        }
 
         Ok(())
     }
+
+    fn fields(&self) -> Vec<(&'static str, &'static str)> {
+        let msg = &self.0;
+        [
+            fields_of(
+                "covenant",
+                &[
+                    "label",
+                    "covenant_type",
+                    "party_a_share",
+                    "party_b_share",
+                    "deposit_deadline",
+                    "lockup_config",
+                    "ragequit_config",
+                    "clock_tick_max_gas",
+                    "emergency_committee",
+                    "fallback_split",
+                ],
+            ),
+            fields_of("fees", FEES_FIELDS),
+            fields_of(
+                "contract_codes",
+                &[
+                    "ibc_forwarder_code",
+                    "holder_code",
+                    "clock_code",
+                    "interchain_router_code",
+                    "native_router_code",
+                    "liquid_pooler_code",
+                ],
+            ),
+            party_config_fields("party_a_config", &msg.party_a_config),
+            party_config_fields("party_b_config", &msg.party_b_config),
+            fields_of("liquid_pooler_config", LIQUID_POOLER_FIELDS),
+            fields_of("pool_price_config", POOL_PRICE_FIELDS),
+            fields_of("splits", &[""]),
+        ]
+        .concat()
+    }
+}
+
+fn party_config_fields(
+    key: &'static str,
+    party_config: &tppc::CovenantPartyConfig,
+) -> Vec<(&'static str, &'static str)> {
+    match party_config {
+        tppc::CovenantPartyConfig::Interchain(_) => fields_of(key, INTERCHAIN_PARTY_FIELDS),
+        tppc::CovenantPartyConfig::Native(_) => fields_of(key, NATIVE_PARTY_FIELDS),
+    }
 }
 
 async fn verify_party_config<'a>(
@@ -201,8 +359,10 @@ async fn verify_party_config<'a>(
     key: &'a str,
     party_chain_name: &str,
     party_config: &tppc::CovenantPartyConfig,
-    _party_channel_uses_wasm_port: bool,
-) -> Result<(), Error> {
+    party_channel_uses_wasm_port: Option<bool>,
+    expected_outpost: Option<String>,
+) -> Result<(), CovenantCliError> {
+    let host_chain_name = ctx.host_chain_name();
     match party_config {
         tppc::CovenantPartyConfig::Native(native_party) => {
             let mut field = "native_denom";
@@ -215,18 +375,19 @@ async fn verify_party_config<'a>(
 
             if native_denom == party_chain_denom {
                 // Simple case: denom is the chain's native token
-                verify_equals!(
+                run_rules(
                     ctx,
                     key,
-                    field,
-                    party_chain_denom,
-                    native_denom,
-                    "invalid denom: expected {} | actual {}"
+                    vec![Rule::equals(
+                        field,
+                        "denom",
+                        &party_chain_denom,
+                        &native_denom,
+                    )],
                 );
             } else if native_denom.starts_with("ibc/") {
                 // IBC denom
-                match get_chain_asset_info(&ctx.cli_context, NEUTRON_CHAIN_NAME, &native_denom)
-                    .await
+                match get_chain_asset_info(&ctx.cli_context, &host_chain_name, &native_denom).await
                 {
                     Ok(asset_info) => {
                         party_base_denom = asset_info
@@ -249,13 +410,15 @@ async fn verify_party_config<'a>(
                             })
                             .unwrap_or_else(|| asset_info.base.clone());
                         party_base_denom_decimals = asset_info.decimals;
-                        verify_equals!(
+                        run_rules(
                             ctx,
                             key,
-                            field,
-                            asset_info.base,
-                            native_denom,
-                            "invalid denom: expected {} | actual {}"
+                            vec![Rule::equals(
+                                field,
+                                "denom",
+                                &asset_info.base,
+                                &native_denom,
+                            )],
                         );
                     }
                     Err(_) => {
@@ -287,6 +450,12 @@ async fn verify_party_config<'a>(
                     field,
                     format!("{:.2} {}", contribution_amount, party_chain_info.display),
                 );
+                verify_minimum_contribution(
+                    ctx,
+                    key,
+                    &party_base_denom,
+                    native_party.contribution.amount.u128(),
+                );
             }
 
             //TODO: validate addresses
@@ -295,63 +464,89 @@ async fn verify_party_config<'a>(
         }
         tppc::CovenantPartyConfig::Interchain(interchain_party) => {
             let path_info =
-                get_path_info(&ctx.cli_context, party_chain_name, NEUTRON_CHAIN_NAME).await?;
-            debug!(
-                "party_a_uses_wasm_port: {}",
-                ctx.party_a_channel_uses_wasm_port
+                get_path_info(&ctx.cli_context, party_chain_name, &host_chain_name).await?;
+            let party_channel_uses_wasm_port = verify_channel_uses_wasm_port(
+                ctx,
+                key,
+                &path_info,
+                &host_chain_name,
+                party_channel_uses_wasm_port,
             );
+            debug!("party_uses_wasm_port: {}", party_channel_uses_wasm_port);
+            verify_wasm_port_contract(
+                ctx,
+                key,
+                &path_info,
+                &host_chain_name,
+                party_chain_name,
+                expected_outpost,
+            )
+            .await;
             let (expected_connection_id, expected_h2p_channel_id, expected_p2h_channel_id) =
-                get_path_connection_and_channels(&path_info, ctx.party_a_channel_uses_wasm_port);
-
-            let mut field = "party_chain_connection_id";
-            let party_chain_connection_id = interchain_party.party_chain_connection_id.clone();
-            verify_equals!(
+                get_path_connection_and_channels(
+                    &path_info,
+                    &host_chain_name,
+                    party_channel_uses_wasm_port,
+                );
+            verify_transfer_channel(
                 ctx,
                 key,
-                field,
-                expected_connection_id,
-                party_chain_connection_id,
-                "invalid connection id: expected {} | actual {}"
+                &path_info,
+                &host_chain_name,
+                &expected_h2p_channel_id,
             );
-
-            field = "host_to_party_chain_channel_id";
-            let host_to_party_chain_channel_id =
-                interchain_party.host_to_party_chain_channel_id.clone();
-            verify_equals!(
+            verify_path_clients(ctx, key, &path_info).await;
+            verify_pfm_consistency(
                 ctx,
                 key,
-                field,
-                expected_h2p_channel_id,
-                interchain_party.host_to_party_chain_channel_id,
-                "invalid channel id: expected {} | actual {}"
+                party_chain_name,
+                &expected_h2p_channel_id,
+                interchain_party.denom_to_pfm_map.len(),
             );
 
-            field = "party_to_host_chain_channel_id";
-            let party_to_host_chain_channel_id =
-                interchain_party.party_to_host_chain_channel_id.clone();
-            verify_equals!(
+            let host_to_party_chain_channel_id =
+                interchain_party.host_to_party_chain_channel_id.clone();
+            run_rules(
                 ctx,
                 key,
-                field,
-                expected_p2h_channel_id,
-                party_to_host_chain_channel_id,
-                "invalid channel id: expected {} | actual {}"
+                vec![
+                    Rule::equals(
+                        "party_chain_connection_id",
+                        "connection id",
+                        expected_connection_id,
+                        &interchain_party.party_chain_connection_id,
+                    ),
+                    Rule::equals(
+                        "host_to_party_chain_channel_id",
+                        "channel id",
+                        expected_h2p_channel_id,
+                        &host_to_party_chain_channel_id,
+                    ),
+                    Rule::equals(
+                        "party_to_host_chain_channel_id",
+                        "channel id",
+                        expected_p2h_channel_id,
+                        &interchain_party.party_to_host_chain_channel_id,
+                    ),
+                ],
             );
 
-            field = "remote_chain_denom";
+            let mut field = "remote_chain_denom";
             let party_chain_info = get_chain_info(&ctx.cli_context, party_chain_name).await?;
             let party_chain_denom = party_chain_info.denom.clone();
             let mut party_base_denom = party_chain_denom.clone();
             let mut party_base_denom_decimals = party_chain_info.decimals;
             let mut remote_chain_denom = interchain_party.remote_chain_denom.clone();
             if remote_chain_denom == party_chain_denom {
-                verify_equals!(
+                run_rules(
                     ctx,
                     key,
-                    field,
-                    party_chain_denom,
-                    remote_chain_denom,
-                    "invalid denom: expected {} | actual {}"
+                    vec![Rule::equals(
+                        field,
+                        "denom",
+                        &party_chain_denom,
+                        &remote_chain_denom,
+                    )],
                 );
             } else {
                 // Remote denom is not the chain's native token
@@ -361,13 +556,15 @@ async fn verify_party_config<'a>(
                     Ok(asset_info) => {
                         party_base_denom = asset_info.base;
                         party_base_denom_decimals = asset_info.decimals;
-                        verify_equals!(
+                        run_rules(
                             ctx,
                             key,
-                            field,
-                            asset_info.denom,
-                            remote_chain_denom,
-                            "invalid denom: expected {} | actual {}"
+                            vec![Rule::equals(
+                                field,
+                                "denom",
+                                &asset_info.denom,
+                                &remote_chain_denom,
+                            )],
                         );
                     }
                     Err(_) => {
@@ -416,15 +613,28 @@ async fn verify_party_config<'a>(
                 )
             );
             let native_denom = interchain_party.native_denom.clone();
-            verify_equals!(
+            run_rules(
                 ctx,
                 key,
-                field,
-                expected_native_denom,
-                native_denom,
-                "invalid denom: expected {} | actual {}"
+                vec![Rule::equals(
+                    field,
+                    "denom",
+                    &expected_native_denom,
+                    &native_denom,
+                )],
             );
 
+            verify_refund_path(
+                ctx,
+                key,
+                &path_info,
+                party_chain_name,
+                &host_to_party_chain_channel_id,
+                &native_denom,
+                &interchain_party.remote_chain_denom,
+            )
+            .await;
+
             field = "contribution";
             remote_chain_denom.clone_from(&interchain_party.remote_chain_denom);
             debug!("party_base_denom_decimals: {}", party_base_denom_decimals);
@@ -447,12 +657,17 @@ async fn verify_party_config<'a>(
                     field,
                     format!("{:.2} {}", contribution_amount, remote_chain_denom),
                 );
+                verify_minimum_contribution(
+                    ctx,
+                    key,
+                    &remote_chain_denom,
+                    interchain_party.contribution.amount.u128(),
+                );
             }
 
             //TODO: Validate the rest of the covenant party config
             // field = "party_receiver_addr";
             // field = "addr";
-            // field = "denom_to_pfm_map";
             // field = "fallback_address";
         }
     }
@@ -464,8 +679,8 @@ async fn verify_two_party_pol_covenant_code_ids<'a>(
     ctx: &mut CovenantValidationContext<'a>,
     key: &'a str,
     contract_code_ids: &tppc::CovenantContractCodeIds,
-) -> Result<(), Error> {
-    match get_covenant_code_ids("v0.1.0".to_owned()).await {
+) -> Result<(), CovenantCliError> {
+    match get_covenant_code_ids(ctx).await {
         Ok(code_ids) => {
             verify_code_id(
                 ctx,
@@ -517,56 +732,25 @@ async fn verify_two_party_pol_covenant_code_ids<'a>(
     Ok(())
 }
 
-fn get_path_connection_and_channels(
-    path_info: &IBCPath,
-    channel_uses_wasm_port: bool,
-) -> (String, String, String) {
-    if path_info.chain_1.chain_name == NEUTRON_CHAIN_NAME {
-        path_info
-            .channels
-            .iter()
-            .filter_map(|c| {
-                if c.chain_1.port_id == TRANSFER_PORT_ID
-                    && ((channel_uses_wasm_port && c.chain_2.port_id.starts_with("wasm."))
-                        || (!channel_uses_wasm_port && c.chain_2.port_id == TRANSFER_PORT_ID))
-                {
-                    Some((
-                        path_info.chain_1.connection_id.clone(),
-                        c.chain_1.channel_id.clone(),
-                        c.chain_2.channel_id.clone(),
-                    ))
-                } else {
-                    None
-                }
-            })
-            .next()
-            .unwrap()
-    } else {
-        path_info
-            .channels
-            .iter()
-            .filter_map(|c| {
-                if c.chain_2.port_id == TRANSFER_PORT_ID
-                    && ((channel_uses_wasm_port && c.chain_1.port_id.starts_with("wasm."))
-                        || c.chain_1.port_id == TRANSFER_PORT_ID)
-                {
-                    Some((
-                        path_info.chain_2.connection_id.clone(),
-                        c.chain_2.channel_id.clone(),
-                        c.chain_1.channel_id.clone(),
-                    ))
-                } else {
-                    None
-                }
-            })
-            .next()
-            .unwrap()
-    }
-}
-
 fn get_party_contribution(cfg: &tppc::CovenantPartyConfig) -> cosmwasm_std::Uint128 {
     match cfg {
         tppc::CovenantPartyConfig::Interchain(interchain) => interchain.contribution.amount,
         tppc::CovenantPartyConfig::Native(native) => native.contribution.amount,
     }
 }
+
+/// Refund route of a party deposit implied by its config
+fn expected_refund(cfg: &tppc::CovenantPartyConfig) -> ExpectedRefund {
+    match cfg {
+        tppc::CovenantPartyConfig::Interchain(interchain) => ExpectedRefund {
+            receiver: interchain.party_receiver_addr.clone(),
+            channel_id: Some(interchain.host_to_party_chain_channel_id.clone()),
+            denom: interchain.native_denom.clone(),
+        },
+        tppc::CovenantPartyConfig::Native(native) => ExpectedRefund {
+            receiver: native.party_receiver_addr.clone(),
+            channel_id: None,
+            denom: native.native_denom.clone(),
+        },
+    }
+}
@@ -0,0 +1,143 @@
+use serde::Serialize;
+use serde_json::Value;
+
+use super::single_party_pol_covenant::SinglePartyPolCovenantInstMsg;
+use super::swap_covenant::SwapCovenantInstMsg;
+use super::two_party_pol_covenant::TwoPartyPolCovenantInstMsg;
+use super::{CovenantValidationContext, Validate};
+use crate::diagnostics::deserialize_msg;
+use crate::error::CovenantCliError;
+
+/// Covenant releases with supported instantiation message schemas
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize)]
+pub enum CovenantVersion {
+    #[default]
+    V0_1,
+}
+
+impl TryFrom<&str> for CovenantVersion {
+    type Error = CovenantCliError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        if value.starts_with("v0.1.") {
+            Ok(CovenantVersion::V0_1)
+        } else {
+            Err(CovenantCliError::ValidationAbort(format!(
+                "unsupported covenant version {}",
+                value
+            )))
+        }
+    }
+}
+
+/// Deserialize an instantiation message into the validator of its schema, none when it does
+/// not deserialize (the errors are reported)
+type ValidatorBuilder =
+    for<'a> fn(&mut CovenantValidationContext<'a>, Value) -> Option<Box<dyn Validate<'a>>>;
+
+/// Message schemas by release and covenant contract. A release adds the wrappers of its
+/// message types here, next to the previous ones.
+const COVENANT_VALIDATORS: &[(CovenantVersion, &str, ValidatorBuilder)] = &[
+    (
+        CovenantVersion::V0_1,
+        "valence-covenant-single-party-pol",
+        v0_1_single_party_pol,
+    ),
+    (CovenantVersion::V0_1, "valence-covenant-swap", v0_1_swap),
+    (
+        CovenantVersion::V0_1,
+        "valence-covenant-two-party-pol",
+        v0_1_two_party_pol,
+    ),
+];
+
+/// Validator of the instantiation message of a covenant contract release
+pub fn covenant_validator<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    version: CovenantVersion,
+    contract: &str,
+    instantiation: Value,
+) -> Result<Option<Box<dyn Validate<'a>>>, CovenantCliError> {
+    match COVENANT_VALIDATORS
+        .iter()
+        .find(|(v, c, _)| *v == version && *c == contract)
+    {
+        Some((_, _, build)) => Ok(build(ctx, instantiation)),
+        None => Err(CovenantCliError::ValidationAbort(format!(
+            "unsupported covenant contract {} for version {:?}, supported: {}",
+            contract,
+            version,
+            COVENANT_VALIDATORS
+                .iter()
+                .filter(|(v, _, _)| *v == version)
+                .map(|(_, c, _)| *c)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ))),
+    }
+}
+
+fn v0_1_single_party_pol<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    instantiation: Value,
+) -> Option<Box<dyn Validate<'a>>> {
+    deserialize_msg(ctx, instantiation)
+        .map(|msg| SinglePartyPolCovenantInstMsg::new(msg).into_boxed())
+}
+
+fn v0_1_swap<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    instantiation: Value,
+) -> Option<Box<dyn Validate<'a>>> {
+    deserialize_msg(ctx, instantiation).map(|msg| SwapCovenantInstMsg::new(msg).into_boxed())
+}
+
+fn v0_1_two_party_pol<'a>(
+    ctx: &mut CovenantValidationContext<'a>,
+    instantiation: Value,
+) -> Option<Box<dyn Validate<'a>>> {
+    deserialize_msg(ctx, instantiation).map(|msg| TwoPartyPolCovenantInstMsg::new(msg).into_boxed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::CliContext;
+
+    #[test]
+    fn every_release_is_detected() {
+        assert_eq!(
+            CovenantVersion::try_from("v0.1.0").unwrap(),
+            CovenantVersion::V0_1
+        );
+        assert!(CovenantVersion::try_from("v9.0.0").is_err());
+    }
+
+    #[test]
+    fn unsupported_contracts_list_the_supported_ones() {
+        let mut ctx = CovenantValidationContext::new(CliContext::default());
+        let error = covenant_validator(
+            &mut ctx,
+            CovenantVersion::V0_1,
+            "valence-covenant-unknown",
+            Value::Null,
+        )
+        .err()
+        .unwrap();
+        assert!(error.to_string().contains("valence-covenant-swap"));
+    }
+
+    #[test]
+    fn invalid_messages_are_reported() {
+        let mut ctx = CovenantValidationContext::new(CliContext::default());
+        let validator = covenant_validator(
+            &mut ctx,
+            CovenantVersion::V0_1,
+            "valence-covenant-swap",
+            serde_json::json!({}),
+        )
+        .unwrap();
+        assert!(validator.is_none());
+        assert!(ctx.errors().contains_key("instantiation"));
+    }
+}
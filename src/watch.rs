@@ -0,0 +1,241 @@
+use anyhow::Error;
+use log::{error, info, warn};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::context::CliContext;
+use crate::lifecycle::{LifecyclePhase, StuckThresholds};
+use crate::notify::notify;
+use crate::utils::chain::get_balances;
+use crate::utils::cosmwasm::get_last_execute_height;
+use crate::utils::covenant::{get_covenant_child_address, get_holder_state};
+use crate::utils::neutron::get_latest_block;
+
+// Holder states exported as the `covenant_state` gauge value
+const HOLDER_STATES: [&str; 5] = ["instantiated", "active", "ragequit", "expired", "complete"];
+
+#[derive(Clone, Debug, Default)]
+struct CovenantStatus {
+    height: u128,
+    // Host chain height of the last clock tick, none before the first tick
+    tick_height: Option<u128>,
+    state: String,
+    phase: Option<LifecyclePhase>,
+    balances: Vec<(String, u128)>,
+}
+
+/// Poll a deployed covenant and optionally publish its status as Prometheus metrics
+pub async fn run(
+    ctx: &CliContext,
+    host_chain_name: &str,
+    covenant_addr: &str,
+    interval: u64,
+    metrics_listen: Option<&str>,
+    stuck_thresholds: &StuckThresholds,
+) -> Result<(), Error> {
+    let metrics = Arc::new(Mutex::new(String::new()));
+    if let Some(listen_addr) = metrics_listen {
+        let listener = TcpListener::bind(listen_addr).await?;
+        info!("Publishing metrics on http://{}/metrics", listen_addr);
+        tokio::spawn(serve_metrics(listener, metrics.clone()));
+    }
+
+    let holder_addr = get_covenant_child_address(
+        ctx,
+        host_chain_name,
+        covenant_addr,
+        "holder_address",
+        json!({}),
+    )
+    .await?;
+    let clock_addr = get_covenant_child_address(
+        ctx,
+        host_chain_name,
+        covenant_addr,
+        "clock_address",
+        json!({}),
+    )
+    .await?;
+    info!(
+        "Watching covenant {} (holder {}, clock {})",
+        covenant_addr, holder_addr, clock_addr
+    );
+
+    let mut last_state: Option<String> = None;
+    // Current lifecycle phase, since when and whether it was reported as stuck
+    let mut phase: Option<(LifecyclePhase, Instant, bool)> = None;
+    loop {
+        match get_covenant_status(ctx, host_chain_name, &holder_addr, &clock_addr).await {
+            Ok(status) => {
+                info!(
+                    "height {}: state {} | balances {:?}",
+                    status.height, status.state, status.balances
+                );
+                if let Some(last_state) = last_state.filter(|s| *s != status.state) {
+                    notify(
+                        ctx,
+                        &format!(
+                            "Covenant `{}` state changed: {} -> {} (height {})",
+                            covenant_addr, last_state, status.state, status.height
+                        ),
+                    )
+                    .await;
+                }
+                last_state = Some(status.state.clone());
+                phase = match (phase, status.phase) {
+                    (Some((current, since, reported)), Some(next)) if current == next => {
+                        let stuck = stuck_thresholds
+                            .get(current)
+                            .is_some_and(|threshold| since.elapsed() > threshold);
+                        if stuck && !reported {
+                            let message = format!(
+                                "Covenant `{}` stuck in the {} phase for {}s",
+                                covenant_addr,
+                                current,
+                                since.elapsed().as_secs()
+                            );
+                            warn!("{}", message);
+                            notify(ctx, &message).await;
+                        }
+                        Some((current, since, reported || stuck))
+                    }
+                    (Some((current, _, _)), Some(next)) => {
+                        if !current.can_transition_to(next) {
+                            let message = format!(
+                                "Covenant `{}` moved back from the {} to the {} phase",
+                                covenant_addr, current, next
+                            );
+                            error!("{}", message);
+                            notify(ctx, &message).await;
+                        }
+                        Some((next, Instant::now(), false))
+                    }
+                    (None, Some(next)) => Some((next, Instant::now(), false)),
+                    (phase, None) => {
+                        warn!("unknown holder state {}", status.state);
+                        phase
+                    }
+                };
+                *metrics.lock().unwrap() = render_metrics(covenant_addr, &status);
+            }
+            Err(e) => error!("failed fetching covenant status: {}", e),
+        }
+        tokio::time::sleep(Duration::from_secs(interval)).await;
+    }
+}
+
+async fn get_covenant_status(
+    ctx: &CliContext,
+    host_chain_name: &str,
+    holder_addr: &str,
+    clock_addr: &str,
+) -> Result<CovenantStatus, Error> {
+    let height = get_latest_block(ctx, host_chain_name).await?;
+    let tick_height = get_last_execute_height(ctx, host_chain_name, clock_addr, "tick").await?;
+    let state = get_holder_state(ctx, host_chain_name, holder_addr).await?;
+    let balances = get_balances(ctx, host_chain_name, holder_addr)
+        .await?
+        .into_iter()
+        .map(|b| (b.denom, b.amount.parse::<u128>().unwrap_or_default()))
+        .collect::<Vec<_>>();
+    let phase = LifecyclePhase::from_holder_state(&state, !balances.is_empty());
+    Ok(CovenantStatus {
+        height,
+        tick_height,
+        state,
+        phase,
+        balances,
+    })
+}
+
+fn render_metrics(covenant_addr: &str, status: &CovenantStatus) -> String {
+    let state_value = HOLDER_STATES
+        .iter()
+        .position(|s| *s == status.state)
+        .map(|i| i as i64)
+        .unwrap_or(-1);
+    let covenant_addr = escape_label_value(covenant_addr);
+    let mut metrics = String::new();
+    metrics.push_str("# HELP covenant_height Host chain height of the last clock tick\n");
+    metrics.push_str("# TYPE covenant_height gauge\n");
+    if let Some(tick_height) = status.tick_height {
+        metrics.push_str(&format!(
+            "covenant_height{{covenant=\"{}\"}} {}\n",
+            covenant_addr, tick_height
+        ));
+    }
+    metrics.push_str(&format!(
+        "# HELP covenant_state Holder state ({})\n",
+        HOLDER_STATES
+            .iter()
+            .enumerate()
+            .map(|(i, s)| format!("{}={}", i, s))
+            .collect::<Vec<_>>()
+            .join(", ")
+    ));
+    metrics.push_str("# TYPE covenant_state gauge\n");
+    metrics.push_str(&format!(
+        "covenant_state{{covenant=\"{}\",state=\"{}\"}} {}\n",
+        covenant_addr,
+        escape_label_value(&status.state),
+        state_value
+    ));
+    metrics.push_str("# HELP covenant_holder_balance Holder balance per denom\n");
+    metrics.push_str("# TYPE covenant_holder_balance gauge\n");
+    for (denom, amount) in &status.balances {
+        metrics.push_str(&format!(
+            "covenant_holder_balance{{covenant=\"{}\",denom=\"{}\"}} {}\n",
+            covenant_addr,
+            escape_label_value(denom),
+            amount
+        ));
+    }
+    metrics
+}
+
+/// Prometheus text format escaping of a label value
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+async fn serve_metrics(listener: TcpListener, metrics: Arc<Mutex<String>>) {
+    loop {
+        let Ok((mut socket, _)) = listener.accept().await else {
+            continue;
+        };
+        let body = metrics.lock().unwrap().clone();
+        tokio::spawn(async move {
+            let mut request = [0u8; 1024];
+            let _ = socket.read(&mut request).await;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_values_are_escaped() {
+        let status = CovenantStatus {
+            tick_height: Some(42),
+            state: "a\"b\\c\nd".to_owned(),
+            ..Default::default()
+        };
+        let metrics = render_metrics("neutron1covenant", &status);
+        assert!(metrics.contains("covenant_height{covenant=\"neutron1covenant\"} 42\n"));
+        assert!(metrics.contains("state=\"a\\\"b\\\\c\\nd\"} -1\n"));
+    }
+}
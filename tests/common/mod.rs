@@ -0,0 +1,145 @@
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+pub const TEMPLATES: &[&str] = &["atom-ntrn-two-party-pol", "statom-single-party-pol"];
+
+// Committed test data, see `tests/fixtures/README.md`
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+// Template parameters shared by the fixtures, unused parameters are ignored
+const PARAMS: &[(&str, &str)] = &[
+    ("label", "covenant-cli-integration"),
+    ("ibc_forwarder_code", "1"),
+    ("holder_code", "2"),
+    ("clock_code", "3"),
+    ("interchain_router_code", "4"),
+    ("native_router_code", "5"),
+    ("liquid_pooler_code", "6"),
+    ("liquid_staker_code", "7"),
+    ("remote_chain_splitter_code", "8"),
+    ("deposit_deadline_nanos", "1893456000000000000"),
+    ("lockup_time_nanos", "1924992000000000000"),
+    (
+        "party_a_receiver_addr",
+        "cosmos1xqq5x5f9yyvkwjg8a2ewg0k0jr2rucj5jz5tpq",
+    ),
+    (
+        "party_b_receiver_addr",
+        "neutron1xqq5x5f9yyvkwjg8a2ewg0k0jr2rucj5yuv6hf",
+    ),
+    (
+        "party_receiver_addr",
+        "cosmos1xqq5x5f9yyvkwjg8a2ewg0k0jr2rucj5jz5tpq",
+    ),
+    ("party_a_amount", "1000000000"),
+    ("party_b_amount", "1000000000"),
+    ("amount", "1000000000"),
+    ("ls_amount", "500000000"),
+    ("lp_amount", "500000000"),
+    ("party_a_single_side_limit", "100000000"),
+    ("party_b_single_side_limit", "100000000"),
+    ("asset_a_single_side_limit", "100000000"),
+    ("asset_b_single_side_limit", "100000000"),
+    ("expected_spot_price", "1.0"),
+    ("acceptable_price_spread", "0.1"),
+    ("pool_address", "neutron1pooladdress"),
+    ("ls_denom_on_neutron", "ibc/STATOM"),
+    ("ls_chain_to_neutron_channel_id", "channel-123"),
+    ("ls_neutron_connection_id", "connection-12"),
+    ("party_to_ls_chain_channel_id", "channel-391"),
+];
+
+/// Run the CLI binary with the given arguments
+pub fn covenant_cli<P: AsRef<std::ffi::OsStr>>(args: &[P]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_covenant-cli"))
+        .args(args)
+        .output()
+        .expect("failed running covenant-cli")
+}
+
+/// Run `validate` on the responses of a bundle, no remote query is performed and no
+/// incremental cache is written next to the committed fixtures
+pub fn validate_offline(
+    metadata_file: &str,
+    instantiation_file: &str,
+    bundle_file: &str,
+) -> Output {
+    covenant_cli(&[
+        "validate",
+        metadata_file,
+        instantiation_file,
+        "--bundle",
+        bundle_file,
+        "--no-incremental",
+    ])
+}
+
+/// Bundle without any response, the first remote query of a validation fails on it
+pub fn empty_bundle() -> String {
+    format!("{}/empty-bundle.tar.gz", FIXTURES_DIR)
+}
+
+/// Recorded covenants: name, metadata, instantiation and bundle files
+pub fn recorded_covenants() -> Vec<(String, String, String, String)> {
+    let mut covenants = std::fs::read_dir(Path::new(FIXTURES_DIR).join("covenants"))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.is_dir())
+                .map(|path| {
+                    (
+                        path.file_name().unwrap().to_string_lossy().into_owned(),
+                        path.join("metadata.toml").display().to_string(),
+                        path.join("instantiation.json").display().to_string(),
+                        path.join("bundle.tar.gz").display().to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    covenants.sort();
+    covenants
+}
+
+/// Empty directory for the files of a test
+pub fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("covenant-cli-{}-{}", name, std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).expect("failed creating test directory");
+    dir
+}
+
+/// Write the metadata and instantiation files of a template to the given directory
+pub fn init_fixture(template: &str, dir: &Path) -> (String, String) {
+    let mut args = vec![
+        "template".to_owned(),
+        "init".to_owned(),
+        template.to_owned(),
+        "--output-dir".to_owned(),
+        dir.display().to_string(),
+    ];
+    for (key, value) in PARAMS {
+        args.push("--set".to_owned());
+        args.push(format!("{}={}", key, value));
+    }
+    let output = covenant_cli(&args);
+    assert!(
+        output.status.success(),
+        "template init failed: {:?}",
+        output
+    );
+    (
+        dir.join("metadata.toml").display().to_string(),
+        dir.join("instantiation.json").display().to_string(),
+    )
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+pub fn stderr(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stderr).into_owned()
+}
@@ -0,0 +1,45 @@
+//! Integration tests against the live endpoints, run with `cargo test --features live-tests`
+#![cfg(feature = "live-tests")]
+
+mod common;
+
+use common::{covenant_cli, init_fixture, stdout, test_dir, TEMPLATES};
+
+#[test]
+fn recorded_bundle_replays_the_live_report() {
+    for template in TEMPLATES {
+        let dir = test_dir(&format!("live-{}", template));
+        let (metadata_file, instantiation_file) = init_fixture(template, &dir);
+        let bundle_file = dir.join("bundle.tar.gz").display().to_string();
+
+        let output = covenant_cli(&[
+            "bundle",
+            "create",
+            &metadata_file,
+            &instantiation_file,
+            &bundle_file,
+        ]);
+        assert!(output.status.success(), "{:?}", output);
+
+        let live = covenant_cli(&[
+            "validate",
+            &metadata_file,
+            &instantiation_file,
+            "--no-incremental",
+        ]);
+        let replayed = covenant_cli(&[
+            "validate",
+            &metadata_file,
+            &instantiation_file,
+            "--bundle",
+            &bundle_file,
+            "--no-incremental",
+        ]);
+        assert!(
+            stdout(&live).contains("| Key | Field | Message |"),
+            "{:?}",
+            live
+        );
+        assert_eq!(stdout(&live), stdout(&replayed));
+    }
+}
@@ -0,0 +1,171 @@
+//! Offline integration tests, no remote query is performed
+
+mod common;
+
+use common::{
+    covenant_cli, empty_bundle, init_fixture, recorded_covenants, stderr, stdout, test_dir,
+    validate_offline, TEMPLATES,
+};
+
+#[test]
+fn templates_are_listed() {
+    let output = covenant_cli(&["template", "list"]);
+    assert!(output.status.success());
+    for template in TEMPLATES {
+        assert!(stdout(&output).contains(template));
+    }
+}
+
+#[test]
+fn templates_are_valid_instantiation_messages() {
+    for template in TEMPLATES {
+        let dir = test_dir(&format!("schema-{}", template));
+        let (metadata_file, instantiation_file) = init_fixture(template, &dir);
+        // An empty bundle fails on the first remote query, after the message was deserialized
+        let output = validate_offline(&metadata_file, &instantiation_file, &empty_bundle());
+        assert!(!stdout(&output).contains("instantiation"), "{:?}", output);
+        assert!(
+            stderr(&output).contains("response not found in bundle"),
+            "{:?}",
+            output
+        );
+    }
+}
+
+#[test]
+fn misspelled_field_is_reported_with_suggestion() {
+    let dir = test_dir("misspelled-field");
+    let (metadata_file, instantiation_file) = init_fixture(TEMPLATES[0], &dir);
+    let instantiation = std::fs::read_to_string(&instantiation_file).unwrap();
+    std::fs::write(
+        &instantiation_file,
+        instantiation.replacen("\"label\":", "\"lable\":", 1),
+    )
+    .unwrap();
+
+    let output = validate_offline(&metadata_file, &instantiation_file, &empty_bundle());
+    assert!(!output.status.success());
+    assert!(
+        stdout(&output).contains("did you mean `label`?"),
+        "{:?}",
+        output
+    );
+    assert!(stdout(&output).contains("TPP-MSG-001"), "{:?}", output);
+}
+
+#[test]
+fn check_is_explained() {
+    let output = covenant_cli(&["explain-check", "tpp-lp-005"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("liquid_pooler_config.single_side_lp_limits_asset_a"));
+    assert!(stdout(&output).contains("Rationale: "));
+
+    let output = covenant_cli(&["explain-check", "SWP-SEC-001"]);
+    assert!(output.status.success());
+
+    let output = covenant_cli(&["explain-check", "TPP-LP-999"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn saved_result_is_rendered_offline() {
+    let dir = test_dir("render");
+    let result_file = dir.join("result.json").display().to_string();
+    std::fs::write(
+        &result_file,
+        r#"{
+            "version": "0.1.0",
+            "context": {
+                "covenant_label": "test-covenant",
+                "errors": { "liquid_pooler_config": ["pool_address: invalid pool"] }
+            },
+            "symbols": {}
+        }"#,
+    )
+    .unwrap();
+
+    let output = covenant_cli(&["render", &result_file, "--format", "csv"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(
+        stdout(&output).contains("liquid_pooler_config,pool_address"),
+        "{:?}",
+        output
+    );
+}
+
+#[test]
+fn yaml_instantiation_is_deserialized() {
+    let dir = test_dir("yaml-instantiation");
+    let (metadata_file, instantiation_file) = init_fixture(TEMPLATES[0], &dir);
+    let instantiation: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&instantiation_file).unwrap()).unwrap();
+    let yaml_file = dir.join("instantiation.yaml").display().to_string();
+    std::fs::write(&yaml_file, serde_yaml::to_string(&instantiation).unwrap()).unwrap();
+    let output = validate_offline(&metadata_file, &yaml_file, &empty_bundle());
+    assert!(!stdout(&output).contains("instantiation"), "{:?}", output);
+    assert!(
+        stderr(&output).contains("response not found in bundle"),
+        "{:?}",
+        output
+    );
+}
+
+#[test]
+fn invalid_metadata_is_reported() {
+    let dir = test_dir("invalid-metadata");
+    let (metadata_file, instantiation_file) = init_fixture(TEMPLATES[0], &dir);
+    let mut metadata: toml::Value =
+        toml::from_str(&std::fs::read_to_string(&metadata_file).unwrap()).unwrap();
+    metadata["covenant"]
+        .as_table_mut()
+        .unwrap()
+        .insert("ls_provider".to_owned(), "lido".into());
+    std::fs::write(&metadata_file, toml::to_string(&metadata).unwrap()).unwrap();
+    let output = validate_offline(&metadata_file, &instantiation_file, &empty_bundle());
+    assert!(
+        stdout(&output).contains("unknown provider lido"),
+        "{:?}",
+        output
+    );
+    assert!(!stderr(&output).contains("panicked"), "{:?}", output);
+}
+
+#[test]
+fn metadata_schema_is_printed() {
+    let output = covenant_cli(&["schema", "--type", "metadata"]);
+    assert!(output.status.success(), "{:?}", output);
+    let schema: serde_json::Value = serde_json::from_str(&stdout(&output)).unwrap();
+    let properties = &schema["properties"]["covenant"]["properties"];
+    assert_eq!(properties["party_a_chain_name"]["type"], "string");
+    assert_eq!(properties["ls_provider"]["enum"][0], "stride");
+}
+
+#[test]
+fn recorded_covenants_are_replayed() {
+    let covenants = recorded_covenants();
+    assert!(
+        !covenants.is_empty(),
+        "no covenant in tests/fixtures/covenants"
+    );
+    for (name, metadata_file, instantiation_file, bundle_file) in covenants {
+        let output = validate_offline(&metadata_file, &instantiation_file, &bundle_file);
+        assert!(
+            !stderr(&output).contains("response not found in bundle"),
+            "{}: {:?}",
+            name,
+            output
+        );
+        assert!(
+            stdout(&output).contains("| Key | Field | Message |"),
+            "{}: {:?}",
+            name,
+            output
+        );
+        assert!(
+            !stderr(&output).contains("panicked"),
+            "{}: {:?}",
+            name,
+            output
+        );
+    }
+}